@@ -0,0 +1,104 @@
+import { utils } from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import BN from "bn.js";
+
+export const findCampaignMetadataPda = (
+  programId: PublicKey,
+  idoCampaignPda: PublicKey
+): PublicKey =>
+  PublicKey.findProgramAddressSync(
+    [Buffer.from("campaign_metadata"), idoCampaignPda.toBuffer()],
+    programId
+  )[0];
+
+// Null only for campaigns created before initialize_sale set the metadata
+// and never backfilled with initialize_campaign_metadata.
+export async function fetchCampaignMetadata(
+  program: any,
+  idoCampaignPda: PublicKey
+) {
+  const metadataPda = findCampaignMetadataPda(program.programId, idoCampaignPda);
+  return program.account.campaignMetadata.fetchNullable(metadataPda);
+}
+
+export const findCampaignRegistryPda = (programId: PublicKey): PublicKey =>
+  PublicKey.findProgramAddressSync([Buffer.from("campaign_registry")], programId)[0];
+
+export const findRegistryEntryPda = (programId: PublicKey, index: BN): PublicKey =>
+  PublicKey.findProgramAddressSync(
+    [Buffer.from("registry_entry"), index.toArrayLike(Buffer, "le", 8)],
+    programId
+  )[0];
+
+// Variant order of CampaignStatus, a registry entry stores the status as this index.
+const CAMPAIGN_STATUSES = ["created", "funded", "live", "ended", "succeeded", "failed", "cancelled", "finalized"];
+
+// Offsets in a RegistryEntry account, including the 8 byte discriminator.
+export const REGISTRY_ENTRY_AUTHORITY_OFFSET = 8 + 1 + 8 + 32;
+export const REGISTRY_ENTRY_STATUS_OFFSET = 8 + 1 + 8 + 32 + 32 + 32 + 8;
+
+// Without filters, walks the registry by index instead of scanning every program
+// account. Filters are matched on the registry entries with memcmp, so only the
+// matching campaign accounts are fetched.
+//
+// Limits:
+// - Only campaigns created after the registry was introduced are listed, older
+//   campaigns were never appended and are not backfilled.
+// - The entry status is written by the instructions that change it explicitly when
+//   they are given the entry. Clock driven transitions (live, ended, succeeded) and
+//   finalization by the last claim only show up after syncRegistryEntry.
+export async function fetchRegisteredCampaigns(
+  program: any,
+  filters: { authority?: PublicKey; status?: string } = {}
+) {
+  let entries: any[];
+  if (!filters.authority && !filters.status) {
+    const registry = await program.account.campaignRegistry.fetch(
+      findCampaignRegistryPda(program.programId)
+    );
+    const count = (registry.campaignCount as BN).toNumber();
+    const entryPdas = [...Array(count).keys()].map((i) =>
+      findRegistryEntryPda(program.programId, new BN(i))
+    );
+    entries = (await program.account.registryEntry.fetchMultiple(entryPdas)).filter(
+      (entry: any) => entry
+    );
+  } else {
+    const memcmp = [];
+    if (filters.authority) {
+      memcmp.push({
+        memcmp: { offset: REGISTRY_ENTRY_AUTHORITY_OFFSET, bytes: filters.authority.toBase58() },
+      });
+    }
+    if (filters.status) {
+      const status = CAMPAIGN_STATUSES.indexOf(filters.status);
+      if (status < 0) {
+        throw new Error(`Unknown campaign status ${filters.status}`);
+      }
+      memcmp.push({
+        memcmp: { offset: REGISTRY_ENTRY_STATUS_OFFSET, bytes: utils.bytes.bs58.encode([status]) },
+      });
+    }
+    entries = (await program.account.registryEntry.all(memcmp)).map(
+      ({ account }: any) => account
+    );
+  }
+
+  const campaigns = await program.account.idoCampaign.fetchMultiple(
+    entries.map((entry: any) => entry.idoCampaign)
+  );
+
+  return entries.map((entry: any, i: number) => ({ entry, campaign: campaigns[i] }));
+}
+
+// Permissionless, mirrors the current campaign status into its registry entry.
+export async function syncRegistryEntry(
+  program: any,
+  idoCampaignOwner: PublicKey,
+  registryEntry: PublicKey
+) {
+  return program.methods
+    .syncRegistryEntry()
+    .accounts({ idoCampaignOwner, registryEntry })
+    .rpc();
+}
@@ -8,5 +8,13 @@ module.exports = async function (provider: anchor.AnchorProvider) {
   // Configure client to use the provider.
   anchor.setProvider(provider);
 
-  // Add your deploy script here.
+  // Every campaign is appended to the launchpad-wide registry on creation.
+  const program = anchor.workspace.solanaIdo;
+  const [campaignRegistry] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("campaign_registry")],
+    program.programId
+  );
+  if (!(await program.account.campaignRegistry.fetchNullable(campaignRegistry))) {
+    await program.methods.initializeCampaignRegistry().rpc();
+  }
 };
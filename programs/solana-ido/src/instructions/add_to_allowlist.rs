@@ -0,0 +1,133 @@
+use {
+    crate::{
+        instructions::IdoError,
+        state::{AllowlistAddition, AllowlistEntry, CampaignStatus, IdoCampaign, ACCESS_LIST_VERSION},
+    },
+    anchor_lang::{
+        prelude::*,
+        system_program::{self, Allocate, Assign, CreateAccount, Transfer},
+    },
+};
+
+#[derive(Accounts)]
+pub struct AddToAllowlist<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [b"ido_campaign", owner.key().as_ref()], bump,
+    )]
+    pub ido_campaign: Account<'info, IdoCampaign>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Remaining accounts are the `[b"allowlist", ido_campaign, participant]` PDAs of `additions`
+/// in the same order. Existing entries get the new cap.
+///
+/// Every addition costs 73 bytes of the 1232 byte transaction, so a legacy transaction fits
+/// 12 of them, 13 when the owner also pays the fee. Larger lists take several transactions.
+pub fn add_to_allowlist<'info>(
+    ctx: Context<'_, '_, 'info, 'info, AddToAllowlist<'info>>,
+    additions: Vec<AllowlistAddition>,
+) -> Result<()> {
+    let ido_campaign = &ctx.accounts.ido_campaign;
+    check_allowlist_editable(ido_campaign, ctx.accounts.owner.key())?;
+    require!(
+        !additions.is_empty() && additions.len() == ctx.remaining_accounts.len(),
+        IdoError::ErrInvalidAccessListBatch
+    );
+
+    for (addition, entry_info) in additions.iter().zip(ctx.remaining_accounts) {
+        let ido_campaign_key = ido_campaign.key();
+        let seeds: &[&[u8]] = &[b"allowlist", ido_campaign_key.as_ref(), addition.participant.as_ref()];
+        let (entry_key, bump) = Pubkey::find_program_address(seeds, &crate::ID);
+        require_keys_eq!(entry_info.key(), entry_key, IdoError::ErrInvalidAccessListBatch);
+
+        if entry_info.owner != &crate::ID {
+            create_pda_account(
+                &ctx.accounts.owner,
+                entry_info,
+                8 + AllowlistEntry::INIT_SPACE,
+                &[seeds, &[&[bump]]].concat(),
+                &ctx.accounts.system_program,
+            )?;
+        }
+
+        let entry = AllowlistEntry {
+            version: ACCESS_LIST_VERSION,
+            ido_campaign: ido_campaign_key,
+            participant: addition.participant,
+            max_allocations: addition.max_allocations,
+        };
+        entry.try_serialize(&mut &mut entry_info.try_borrow_mut_data()?[..])?;
+    }
+
+    Ok(())
+}
+
+/// The allowlist can change until the sale ends.
+pub(crate) fn check_allowlist_editable(ido_campaign: &IdoCampaign, owner: Pubkey) -> Result<()> {
+    require!(ido_campaign.authority == owner, IdoError::ErrUnauthorized);
+    require!(!ido_campaign.status.is_closed(), IdoError::ErrSaleAlreadyClosed);
+    require!(
+        matches!(
+            ido_campaign.status,
+            CampaignStatus::Created | CampaignStatus::Funded | CampaignStatus::Live
+        ),
+        IdoError::ErrInvalidSalePeriod
+    );
+
+    Ok(())
+}
+
+/// Creates a program-owned PDA the way `init` does, including when someone already
+/// sent lamports to the address to block a plain `create_account`.
+pub(crate) fn create_pda_account<'info>(
+    payer: &Signer<'info>,
+    account: &AccountInfo<'info>,
+    space: usize,
+    signer_seeds: &[&[u8]],
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    let signer_seeds = &[signer_seeds];
+    let rent_lamports = Rent::get()?.minimum_balance(space);
+    let current_lamports = account.lamports();
+
+    if current_lamports == 0 {
+        let cpi_context = CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            CreateAccount {
+                from: payer.to_account_info(),
+                to: account.clone(),
+            },
+            signer_seeds,
+        );
+        return system_program::create_account(cpi_context, rent_lamports, space as u64, &crate::ID);
+    }
+
+    if current_lamports < rent_lamports {
+        let cpi_context = CpiContext::new(
+            system_program.to_account_info(),
+            Transfer {
+                from: payer.to_account_info(),
+                to: account.clone(),
+            },
+        );
+        system_program::transfer(cpi_context, rent_lamports - current_lamports)?;
+    }
+
+    let cpi_context = CpiContext::new_with_signer(
+        system_program.to_account_info(),
+        Allocate { account_to_allocate: account.clone() },
+        signer_seeds,
+    );
+    system_program::allocate(cpi_context, space as u64)?;
+
+    let cpi_context = CpiContext::new_with_signer(
+        system_program.to_account_info(),
+        Assign { account_to_assign: account.clone() },
+        signer_seeds,
+    );
+    system_program::assign(cpi_context, &crate::ID)
+}
@@ -0,0 +1,62 @@
+use {
+    crate::{
+        instructions::{create_pda_account, IdoError},
+        state::{BlocklistEntry, LaunchpadConfig, ACCESS_LIST_VERSION},
+    },
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct AddToBlocklist<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"launchpad_config"], bump,
+        constraint = launchpad_config.admin == admin.key() @ IdoError::ErrUnauthorized,
+    )]
+    pub launchpad_config: Account<'info, LaunchpadConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Remaining accounts are the `[b"blocklist", wallet]` PDAs of `wallets` in the same order,
+/// wallets that are already blocked are skipped.
+///
+/// Every wallet costs 65 bytes of the transaction, a legacy transaction the admin pays for
+/// fits 15 of them.
+pub fn add_to_blocklist<'info>(
+    ctx: Context<'_, '_, 'info, 'info, AddToBlocklist<'info>>,
+    wallets: Vec<Pubkey>,
+) -> Result<()> {
+    require!(
+        !wallets.is_empty() && wallets.len() == ctx.remaining_accounts.len(),
+        IdoError::ErrInvalidAccessListBatch
+    );
+
+    for (wallet, entry_info) in wallets.iter().zip(ctx.remaining_accounts) {
+        let seeds: &[&[u8]] = &[b"blocklist", wallet.as_ref()];
+        let (entry_key, bump) = Pubkey::find_program_address(seeds, &crate::ID);
+        require_keys_eq!(entry_info.key(), entry_key, IdoError::ErrInvalidAccessListBatch);
+
+        if entry_info.owner == &crate::ID {
+            continue;
+        }
+
+        create_pda_account(
+            &ctx.accounts.admin,
+            entry_info,
+            8 + BlocklistEntry::INIT_SPACE,
+            &[seeds, &[&[bump]]].concat(),
+            &ctx.accounts.system_program,
+        )?;
+
+        let entry = BlocklistEntry {
+            version: ACCESS_LIST_VERSION,
+            wallet: *wallet,
+        };
+        entry.try_serialize(&mut &mut entry_info.try_borrow_mut_data()?[..])?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,65 @@
+use {
+    crate::{
+        instructions::{check_allowlist_editable, create_pda_account, IdoError},
+        state::{GateListEntry, IdoCampaign, ACCESS_LIST_VERSION},
+    },
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct AddToGateList<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [b"ido_campaign", owner.key().as_ref()], bump,
+    )]
+    pub ido_campaign: Account<'info, IdoCampaign>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Remaining accounts are the `[b"gate_list", ido_campaign, mint]` PDAs of `mints` in the same
+/// order, mints that are already listed are skipped.
+///
+/// Every mint costs 65 bytes of the transaction, a legacy transaction fits 13 of them,
+/// 15 when the owner also pays the fee.
+pub fn add_to_gate_list<'info>(
+    ctx: Context<'_, '_, 'info, 'info, AddToGateList<'info>>,
+    mints: Vec<Pubkey>,
+) -> Result<()> {
+    let ido_campaign = &ctx.accounts.ido_campaign;
+    check_allowlist_editable(ido_campaign, ctx.accounts.owner.key())?;
+    require!(
+        !mints.is_empty() && mints.len() == ctx.remaining_accounts.len(),
+        IdoError::ErrInvalidAccessListBatch
+    );
+
+    for (mint, entry_info) in mints.iter().zip(ctx.remaining_accounts) {
+        let ido_campaign_key = ido_campaign.key();
+        let seeds: &[&[u8]] = &[b"gate_list", ido_campaign_key.as_ref(), mint.as_ref()];
+        let (entry_key, bump) = Pubkey::find_program_address(seeds, &crate::ID);
+        require_keys_eq!(entry_info.key(), entry_key, IdoError::ErrInvalidAccessListBatch);
+
+        if entry_info.owner == &crate::ID {
+            continue;
+        }
+
+        create_pda_account(
+            &ctx.accounts.owner,
+            entry_info,
+            8 + GateListEntry::INIT_SPACE,
+            &[seeds, &[&[bump]]].concat(),
+            &ctx.accounts.system_program,
+        )?;
+
+        let entry = GateListEntry {
+            version: ACCESS_LIST_VERSION,
+            ido_campaign: ido_campaign_key,
+            mint: *mint,
+        };
+        entry.try_serialize(&mut &mut entry_info.try_borrow_mut_data()?[..])?;
+    }
+
+    Ok(())
+}
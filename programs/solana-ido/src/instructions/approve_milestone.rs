@@ -0,0 +1,47 @@
+use {
+    crate::{
+        instructions::IdoError,
+        state::{IdoCampaign, LaunchpadConfig, MilestoneEscrow, TrancheState, TrancheUnlock},
+    },
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct ApproveMilestone<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"launchpad_config"], bump,
+        constraint = launchpad_config.admin == admin.key() @ IdoError::ErrUnauthorized,
+    )]
+    pub launchpad_config: Account<'info, LaunchpadConfig>,
+
+    /// CHECK: This account is used only as a seed to derive the ido_campaign PDA
+    pub ido_campaign_owner: AccountInfo<'info>,
+
+    #[account(
+        seeds = [b"ido_campaign", ido_campaign_owner.key().as_ref()], bump,
+    )]
+    pub ido_campaign: Account<'info, IdoCampaign>,
+
+    #[account(
+        mut,
+        seeds = [b"milestone_escrow", ido_campaign.key().as_ref()], bump,
+    )]
+    pub milestone_escrow: Account<'info, MilestoneEscrow>,
+}
+
+/// The launchpad admin confirms the milestone was delivered, the owner can release the tranche.
+pub fn approve_milestone(ctx: Context<ApproveMilestone>, tranche_index: u8) -> Result<()> {
+    let milestone_escrow = &mut ctx.accounts.milestone_escrow;
+    require!(!milestone_escrow.failed, IdoError::ErrEscrowFailed);
+    require!(!milestone_escrow.voting_enabled, IdoError::ErrMilestoneVotingEnabled);
+
+    let tranche = milestone_escrow.tranche(tranche_index)?;
+    require!(tranche.terms.unlock == TrancheUnlock::Milestone, IdoError::ErrInvalidTranche);
+    require!(tranche.state == TrancheState::Locked, IdoError::ErrTrancheAlreadySettled);
+
+    milestone_escrow.tranches[tranche_index as usize].state = TrancheState::Approved;
+
+    Ok(())
+}
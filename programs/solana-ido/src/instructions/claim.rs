@@ -1,9 +1,9 @@
 use {
-    crate::{instructions::IdoError, state::{IdoCampaign, User}}, 
+    crate::{instructions::IdoError, state::{CampaignStatus, IdoCampaign, User}}, 
     anchor_lang::prelude::*,
     anchor_spl::{
         associated_token::AssociatedToken,
-        token::{self, Mint, Token, TokenAccount, TransferChecked},
+        token::{self, Mint, MintTo, Token, TokenAccount, TransferChecked},
     },
 };
 
@@ -30,6 +30,8 @@ pub struct Claim<'info> {
     )]
     pub tokens_treasury: Account<'info, TokenAccount>,
 
+    /// Writable so mint-on-claim campaigns can mint the claimed tokens.
+    #[account(mut)]
     pub token_mint: Account<'info, Mint>,
 
     #[account(
@@ -52,70 +54,139 @@ pub struct Claim<'info> {
 }
 
 pub fn claim(ctx: Context<Claim>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp as u64;
     let user = &mut ctx.accounts.user;
     let ido_campaign = &mut ctx.accounts.ido_campaign;
 
-    require!(!ido_campaign.sale_closed, IdoError::ErrSaleAlreadyClosed);
+    ido_campaign.sync_status(now)?;
+    require!(!ido_campaign.status.is_closed(), IdoError::ErrSaleAlreadyClosed);
     require!(ctx.accounts.token_mint.decimals == 0, IdoError::ErrInvalidTokenDecimals);
     require!(ctx.accounts.token_mint.key() == ido_campaign.token_mint, IdoError::ErrInvalidTokensTreasuryMint);
-    require!(ido_campaign.token_supply_deposited, IdoError::ErrTokenSupplyNotDeposited);
-    require!(ctx.accounts.tokens_treasury.amount > 0, IdoError::ErrInvalidTokensTreasuryAmount);
+    check_claimable_status(ido_campaign.status)?;
+    require!(!ido_campaign.claim_deadline_passed(now), IdoError::ErrClaimDeadlinePassed);
+    require!(
+        ido_campaign.mint_on_claim || ctx.accounts.tokens_treasury.amount > 0,
+        IdoError::ErrInvalidTokensTreasuryAmount
+    );
+    user.settle(ido_campaign)?;
     check_user(user, ctx.accounts.participant.key(), ido_campaign.key())?;
 
     let amount_to_claim = calculate_amount_to_claim(user, ido_campaign)?;
     require!(amount_to_claim > 0, IdoError::ErrNothingToClaim);
-    require!(ctx.accounts.tokens_treasury.amount >= amount_to_claim, IdoError::ErrInsufficientFundsInTreasury);
-
     user.claimed = user.claimed.checked_add(amount_to_claim).ok_or(IdoError::ErrMathOverflow)?;
     ido_campaign.total_claimed = ido_campaign.total_claimed.checked_add(amount_to_claim).ok_or(IdoError::ErrMathOverflow)?;
 
-    transfer_tokens_to_participant(&ctx, amount_to_claim)?;
-
-    Ok(())
+    ido_campaign.sync_status(now)?;
+
+    pay_out_claimed_tokens(
+        &ctx.accounts.ido_campaign,
+        ctx.bumps.ido_campaign,
+        &ctx.accounts.tokens_treasury,
+        &ctx.accounts.participant_token_account,
+        &ctx.accounts.token_mint,
+        &ctx.accounts.token_program,
+        amount_to_claim,
+    )
 }
 
-fn transfer_tokens_to_participant(ctx: &Context<Claim>, amount_to_claim: u64) -> Result<()> {
-    let cpi_accounts = TransferChecked {
-        from: ctx.accounts.tokens_treasury.to_account_info(),
-        to: ctx.accounts.participant_token_account.to_account_info(),
-        authority: ctx.accounts.ido_campaign.to_account_info(),
-        mint: ctx.accounts.token_mint.to_account_info(),
-    };
-
-    let cpi_program = ctx.accounts.token_program.to_account_info();
+pub(crate) fn check_claimable_status(status: CampaignStatus) -> Result<()> {
+    match status {
+        CampaignStatus::Succeeded | CampaignStatus::Finalized => Ok(()),
+        CampaignStatus::Created => err!(IdoError::ErrTokenSupplyNotDeposited),
+        CampaignStatus::Ended => err!(IdoError::ErrSoftCapNotReached),
+        // The cliff is always after the sale, so nothing is vested while it is running.
+        _ => err!(IdoError::ErrNothingToClaim),
+    }
+}
 
-    let ido_campaign_owner_key = ctx.accounts.ido_campaign_owner.key();
-    let ido_campaign_bump = ctx.bumps.ido_campaign;
+/// Transfers claimed tokens out of the treasury, or mints them when the campaign mints on claim.
+/// Expects `total_claimed` to include `amount` already, so the minted supply stays within `total_sold`.
+pub(crate) fn pay_out_claimed_tokens<'info>(
+    ido_campaign: &Account<'info, IdoCampaign>,
+    ido_campaign_bump: u8,
+    tokens_treasury: &Account<'info, TokenAccount>,
+    to: &Account<'info, TokenAccount>,
+    token_mint: &Account<'info, Mint>,
+    token_program: &Program<'info, Token>,
+    amount: u64,
+) -> Result<()> {
     let ido_campaign_bump_bytes = [ido_campaign_bump];
     let signer_seeds: [&[u8]; 3] = [
         b"ido_campaign",
-        ido_campaign_owner_key.as_ref(),
+        ido_campaign.authority.as_ref(),
         &ido_campaign_bump_bytes,
     ];
     let signer = [&signer_seeds[..]];
 
-    let cpi_context = CpiContext::new_with_signer(cpi_program, cpi_accounts, &signer);
-    token::transfer_checked(cpi_context, amount_to_claim, ctx.accounts.token_mint.decimals)?;
-    
-    Ok(())
+    if ido_campaign.mint_on_claim {
+        require!(
+            ido_campaign.total_claimed <= ido_campaign.total_sold,
+            IdoError::ErrMintCapExceeded
+        );
+
+        let cpi_context = CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            MintTo {
+                mint: token_mint.to_account_info(),
+                to: to.to_account_info(),
+                authority: ido_campaign.to_account_info(),
+            },
+            &signer,
+        );
+        return token::mint_to(cpi_context, amount);
+    }
+
+    require!(tokens_treasury.amount >= amount, IdoError::ErrInsufficientFundsInTreasury);
+
+    let cpi_accounts = TransferChecked {
+        from: tokens_treasury.to_account_info(),
+        to: to.to_account_info(),
+        authority: ido_campaign.to_account_info(),
+        mint: token_mint.to_account_info(),
+    };
+
+    let cpi_context = CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, &signer);
+    token::transfer_checked(cpi_context, amount, token_mint.decimals)
 }
 
-fn calculate_amount_to_claim(user: &User, ido_campaign: &IdoCampaign) -> Result<u64> {
+pub(crate) fn calculate_amount_to_claim(user: &User, ido_campaign: &IdoCampaign) -> Result<u64> {
+    // Revoking cuts the position down to what had vested, all of which is unlocked.
+    if user.revoked {
+        return Ok(user.amount.saturating_sub(user.claimed));
+    }
+
     let now = Clock::get()?.unix_timestamp as u64;
 
-    let total: u64 = user.amount;
-    let claimed: u64 = user.claimed;
+    let unlocked_total = unlocked_amount(
+        user.amount,
+        ido_campaign.cliff,
+        ido_campaign.vesting_end_time,
+        ido_campaign.available_tokens_after_cliff_ptc,
+        now,
+    )?;
 
-    if now < ido_campaign.cliff {
+    Ok(unlocked_total.saturating_sub(user.claimed))
+}
+
+/// Part of `total` vested at `now`: `available_tokens_after_cliff_ptc` percent at the cliff,
+/// the rest linearly until `vesting_end_time`. Shared by sale positions and token locks.
+pub(crate) fn unlocked_amount(
+    total: u64,
+    cliff: u64,
+    vesting_end_time: u64,
+    available_tokens_after_cliff_ptc: i32,
+    now: u64,
+) -> Result<u64> {
+    if now < cliff {
         return Ok(0);
     }
 
     require!(
-        ido_campaign.vesting_end_time > ido_campaign.cliff,
+        vesting_end_time > cliff,
         IdoError::ErrInvalidVestingEndTime
     );
 
-    let pct_i32 = ido_campaign.available_tokens_after_cliff_ptc;
+    let pct_i32 = available_tokens_after_cliff_ptc;
     require!(
         pct_i32 >= 0 && pct_i32 <= 100,
         IdoError::ErrInvalidAvailableTokensAfterCliffPtc
@@ -123,16 +194,15 @@ fn calculate_amount_to_claim(user: &User, ido_campaign: &IdoCampaign) -> Result<
     let pct: u128 = pct_i32 as u128;
 
     let total_u128 = total as u128;
-    let claimed_u128 = claimed as u128;
 
     let cliff_unlocked: u128 = total_u128
         .checked_mul(pct)
         .ok_or(IdoError::ErrMathOverflow)?
         / 100u128;
 
-    let unlocked_total: u128 = if now >= ido_campaign.vesting_end_time {
+    let unlocked_total: u128 = if now >= vesting_end_time {
         total_u128
-    } else if now == ido_campaign.cliff {
+    } else if now == cliff {
         cliff_unlocked
     } else {
         // Linear unlock of the remaining part between [cliff, vesting_end_time].
@@ -140,8 +210,8 @@ fn calculate_amount_to_claim(user: &User, ido_campaign: &IdoCampaign) -> Result<
             .checked_sub(cliff_unlocked)
             .ok_or(IdoError::ErrMathOverflow)?;
 
-        let elapsed: u128 = (now - ido_campaign.cliff) as u128;
-        let duration: u128 = (ido_campaign.vesting_end_time - ido_campaign.cliff) as u128;
+        let elapsed: u128 = (now - cliff) as u128;
+        let duration: u128 = (vesting_end_time - cliff) as u128;
 
         let linear: u128 = remaining
             .checked_mul(elapsed)
@@ -156,15 +226,7 @@ fn calculate_amount_to_claim(user: &User, ido_campaign: &IdoCampaign) -> Result<
         sum.min(total_u128)
     };
 
-    if unlocked_total <= claimed_u128 {
-        return Ok(0);
-    }
-
-    let claimable = unlocked_total
-        .checked_sub(claimed_u128)
-        .ok_or(IdoError::ErrMathOverflow)?;
-
-    Ok(claimable as u64)
+    Ok(unlocked_total as u64)
 }
 
 fn check_user(user: &User, participant_key: Pubkey, ido_campaign_key: Pubkey) -> Result<()> {
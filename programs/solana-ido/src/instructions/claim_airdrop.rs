@@ -0,0 +1,121 @@
+use {
+    crate::{
+        instructions::{calculate_amount_to_claim, check_claimable_status, pay_out_claimed_tokens, IdoError},
+        state::{Airdrop, IdoCampaign, SaleMode, User, USER_VERSION},
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::{
+        associated_token::AssociatedToken,
+        token::{Mint, Token, TokenAccount},
+    },
+};
+
+#[derive(Accounts)]
+pub struct ClaimAirdrop<'info> {
+    #[account(mut)]
+    pub participant: Signer<'info>,
+
+    /// CHECK: This account is used only as a seed to derive the ido_campaign PDA
+    pub ido_campaign_owner: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"ido_campaign", ido_campaign_owner.key().as_ref()], bump,
+    )]
+    pub ido_campaign: Account<'info, IdoCampaign>,
+
+    #[account(
+        seeds = [b"airdrop", ido_campaign.key().as_ref()], bump,
+    )]
+    pub airdrop: Account<'info, Airdrop>,
+
+    #[account(
+        mut,
+        seeds = [b"tokens_treasury", ido_campaign.key().as_ref()], bump,
+        constraint = tokens_treasury.key() == ido_campaign.token_treasury @ IdoError::ErrInvalidIdoCampaign,
+    )]
+    pub tokens_treasury: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = token_mint.key() == ido_campaign.token_mint @ IdoError::ErrInvalidTokensTreasuryMint,
+    )]
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = participant,
+        associated_token::mint = token_mint,
+        associated_token::authority = participant,
+    )]
+    pub participant_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = participant,
+        space = 8 + User::INIT_SPACE,
+        seeds = [b"user", ido_campaign.key().as_ref(), participant.key().as_ref()], bump,
+    )]
+    pub user: Account<'info, User>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/// The first call proves the allocation and opens the position, it and every later call
+/// pay out what vested so far. Once the position exists `claim` works as well.
+pub fn claim_airdrop(ctx: Context<ClaimAirdrop>, amount: u64, proof: Vec<[u8; 32]>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp as u64;
+    let ido_campaign = &mut ctx.accounts.ido_campaign;
+    let user = &mut ctx.accounts.user;
+
+    require!(ido_campaign.sale_mode == SaleMode::Airdrop, IdoError::ErrInvalidSaleMode);
+    check_claimable_status(ido_campaign.sync_status(now)?)?;
+    require!(!ido_campaign.claim_deadline_passed(now), IdoError::ErrClaimDeadlinePassed);
+
+    if user.joined_at == 0 {
+        require!(now < ctx.accounts.airdrop.claim_deadline, IdoError::ErrClaimDeadlinePassed);
+        require!(
+            ctx.accounts.airdrop.verify(&ctx.accounts.participant.key(), amount, &proof),
+            IdoError::ErrInvalidMerkleProof
+        );
+
+        let new_total_sold = ido_campaign.total_sold
+            .checked_add(amount)
+            .ok_or(IdoError::ErrMathOverflow)?;
+        require!(new_total_sold <= ido_campaign.hard_cap, IdoError::ErrThisAllocationIsNotAvailable);
+
+        user.version = USER_VERSION;
+        user.joined_at = now;
+        user.ido_campaign = ido_campaign.key();
+        user.participant = ctx.accounts.participant.key();
+        user.amount = amount;
+        user.settled = true;
+
+        ido_campaign.total_sold = new_total_sold;
+        ido_campaign.total_committed = new_total_sold;
+        ido_campaign.total_participants += 1;
+    }
+
+    let amount_to_claim = calculate_amount_to_claim(user, ido_campaign)?;
+    if amount_to_claim == 0 {
+        return Ok(());
+    }
+
+    user.claimed = user.claimed.checked_add(amount_to_claim).ok_or(IdoError::ErrMathOverflow)?;
+    ido_campaign.total_claimed = ido_campaign.total_claimed
+        .checked_add(amount_to_claim)
+        .ok_or(IdoError::ErrMathOverflow)?;
+    ido_campaign.sync_status(now)?;
+
+    pay_out_claimed_tokens(
+        &ctx.accounts.ido_campaign,
+        ctx.bumps.ido_campaign,
+        &ctx.accounts.tokens_treasury,
+        &ctx.accounts.participant_token_account,
+        &ctx.accounts.token_mint,
+        &ctx.accounts.token_program,
+        amount_to_claim,
+    )
+}
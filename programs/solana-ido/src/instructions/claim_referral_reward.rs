@@ -0,0 +1,66 @@
+use {
+    crate::{
+        instructions::{check_claimable_status, pay_from_sol_treasury, IdoError},
+        state::{IdoCampaign, Referral, ReferralPayout},
+    },
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct ClaimReferralReward<'info> {
+    #[account(mut)]
+    pub referrer: Signer<'info>,
+
+    /// CHECK: This account is used only as a seed to derive the ido_campaign PDA
+    pub ido_campaign_owner: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"ido_campaign", ido_campaign_owner.key().as_ref()], bump,
+    )]
+    pub ido_campaign: Account<'info, IdoCampaign>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_treasury", ido_campaign.key().as_ref()], bump,
+        constraint = sol_treasury.key() == ido_campaign.sol_treasury @ IdoError::ErrInvalidIdoCampaign,
+    )]
+    pub sol_treasury: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"referral", ido_campaign.key().as_ref(), referrer.key().as_ref()], bump,
+    )]
+    pub referral: Account<'info, Referral>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Unclaimed rewards are kept in the treasury by `withdraw_funds`.
+pub fn claim_referral_reward(ctx: Context<ClaimReferralReward>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp as u64;
+    let ido_campaign = &mut ctx.accounts.ido_campaign;
+    match ido_campaign.referral_payout {
+        ReferralPayout::OnSuccess => check_claimable_status(ido_campaign.sync_status(now)?)?,
+        ReferralPayout::AtWithdraw => require!(ido_campaign.funds_withdrawn, IdoError::ErrFundsNotWithdrawn),
+    }
+
+    let referral = &mut ctx.accounts.referral;
+    let amount = referral.reward_lamports
+        .checked_sub(referral.claimed_lamports)
+        .ok_or(IdoError::ErrMathOverflow)?;
+    require!(amount > 0, IdoError::ErrNothingToClaim);
+    referral.claimed_lamports = referral.reward_lamports;
+    ido_campaign.referral_rewards_lamports = ido_campaign.referral_rewards_lamports
+        .checked_sub(amount)
+        .ok_or(IdoError::ErrMathOverflow)?;
+
+    pay_from_sol_treasury(
+        &ctx.accounts.sol_treasury,
+        &ctx.accounts.referrer.to_account_info(),
+        ctx.accounts.ido_campaign.key(),
+        ctx.bumps.sol_treasury,
+        &ctx.accounts.system_program,
+        amount,
+    )
+}
@@ -0,0 +1,117 @@
+use {
+    crate::{
+        instructions::{unlocked_amount, IdoError},
+        state::{IdoCampaign, TokenLock},
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::{
+        associated_token::AssociatedToken,
+        token::{self, Mint, Token, TokenAccount, TransferChecked},
+    },
+};
+
+#[derive(Accounts)]
+pub struct ClaimTokenLock<'info> {
+    #[account(mut)]
+    pub beneficiary: Signer<'info>,
+
+    /// CHECK: This account is used only as a seed to derive the ido_campaign PDA
+    pub ido_campaign_owner: AccountInfo<'info>,
+
+    #[account(
+        seeds = [b"ido_campaign", ido_campaign_owner.key().as_ref()], bump,
+    )]
+    pub ido_campaign: Account<'info, IdoCampaign>,
+
+    #[account(
+        mut,
+        seeds = [b"token_lock", ido_campaign.key().as_ref(), beneficiary.key().as_ref()], bump,
+    )]
+    pub token_lock: Account<'info, TokenLock>,
+
+    #[account(
+        mut,
+        seeds = [b"token_lock_vault", token_lock.key().as_ref()], bump,
+    )]
+    pub token_lock_vault: Account<'info, TokenAccount>,
+
+    #[account(constraint = token_mint.key() == ido_campaign.token_mint @ IdoError::ErrInvalidTokenMint)]
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = beneficiary,
+        associated_token::mint = token_mint,
+        associated_token::authority = beneficiary,
+    )]
+    pub beneficiary_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn claim_token_lock(ctx: Context<ClaimTokenLock>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp as u64;
+    let token_lock = &mut ctx.accounts.token_lock;
+
+    // Revoking cuts the lock down to what had vested, all of which is unlocked.
+    let unlocked = if token_lock.revoked {
+        token_lock.amount
+    } else {
+        unlocked_amount(
+            token_lock.amount,
+            token_lock.cliff,
+            token_lock.vesting_end_time,
+            token_lock.available_tokens_after_cliff_ptc,
+            now,
+        )?
+    };
+    let amount_to_claim = unlocked.saturating_sub(token_lock.claimed);
+    require!(amount_to_claim > 0, IdoError::ErrNothingToClaim);
+
+    token_lock.claimed = token_lock.claimed
+        .checked_add(amount_to_claim)
+        .ok_or(IdoError::ErrMathOverflow)?;
+
+    transfer_from_token_lock_vault(
+        &ctx.accounts.token_lock,
+        ctx.bumps.token_lock,
+        &ctx.accounts.token_lock_vault,
+        &ctx.accounts.beneficiary_token_account,
+        &ctx.accounts.token_mint,
+        &ctx.accounts.token_program,
+        amount_to_claim,
+    )
+}
+
+pub(crate) fn transfer_from_token_lock_vault<'info>(
+    token_lock: &Account<'info, TokenLock>,
+    token_lock_bump: u8,
+    token_lock_vault: &Account<'info, TokenAccount>,
+    to: &Account<'info, TokenAccount>,
+    token_mint: &Account<'info, Mint>,
+    token_program: &Program<'info, Token>,
+    amount: u64,
+) -> Result<()> {
+    let bump_bytes = [token_lock_bump];
+    let seeds: [&[u8]; 4] = [
+        b"token_lock",
+        token_lock.ido_campaign.as_ref(),
+        token_lock.beneficiary.as_ref(),
+        &bump_bytes,
+    ];
+    let signer = [&seeds[..]];
+
+    let cpi_context = CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        TransferChecked {
+            from: token_lock_vault.to_account_info(),
+            to: to.to_account_info(),
+            authority: token_lock.to_account_info(),
+            mint: token_mint.to_account_info(),
+        },
+        &signer,
+    );
+    token::transfer_checked(cpi_context, amount, token_mint.decimals)
+}
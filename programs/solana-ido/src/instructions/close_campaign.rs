@@ -1,5 +1,5 @@
 use {
-    crate::{instructions::IdoError, state::IdoCampaign}, 
+    crate::{instructions::IdoError, state::{CampaignStatus, IdoCampaign, RegistryEntry}}, 
     anchor_lang::prelude::*,
     anchor_spl::token::{self, Mint, Token, TokenAccount, TransferChecked},
 };
@@ -37,18 +37,28 @@ pub struct CloseCampaign<'info> {
     )]
     pub token_mint: Account<'info, Mint>,
 
+    /// Gets the new status mirrored, missing for campaigns created before the registry.
+    #[account(
+        mut,
+        constraint = registry_entry.ido_campaign == ido_campaign.key() @ IdoError::ErrInvalidIdoCampaign,
+    )]
+    pub registry_entry: Option<Account<'info, RegistryEntry>>,
+
     pub token_program: Program<'info, Token>,
 }
 
 pub fn close_campaign(ctx: Context<CloseCampaign>) -> Result<()> {
-    let now = Clock::get()?.unix_timestamp;
+    let now = Clock::get()?.unix_timestamp as u64;
     let ido_campaign = &mut ctx.accounts.ido_campaign;
 
-    require!(!ido_campaign.sale_closed, IdoError::ErrSaleAlreadyClosed);
-    require!(now < ido_campaign.end_sale_time as i64, IdoError::ErrSaleEnded);
-    require!(!ido_campaign.funds_withdrawn, IdoError::ErrFundsAlreadyWithdrawn);
+    ido_campaign.sync_status(now)?;
+    require!(!ido_campaign.status.is_closed(), IdoError::ErrSaleAlreadyClosed);
+    require!(ido_campaign.status != CampaignStatus::Created, IdoError::ErrTokenSupplyNotDeposited);
+    require!(
+        matches!(ido_campaign.status, CampaignStatus::Funded | CampaignStatus::Live),
+        IdoError::ErrSaleEnded
+    );
     require!(ido_campaign.total_claimed == 0, IdoError::ErrTotalClaimedNotZero);
-    require!(ido_campaign.token_supply_deposited, IdoError::ErrTokenSupplyNotDeposited);
     require!(ctx.accounts.token_mint.decimals == 0, IdoError::ErrInvalidTokenDecimals);
 
     let owner_key = ctx.accounts.owner.key();
@@ -72,10 +82,14 @@ pub fn close_campaign(ctx: Context<CloseCampaign>) -> Result<()> {
         &signer,
     );
 
-    token::transfer_checked(cpi_context, ctx.accounts.tokens_treasury.amount, ctx.accounts.token_mint.decimals)?;
+    // Third-party funders take their deposits back with return_funder_deposit.
+    let owner_tokens = ctx.accounts.tokens_treasury.amount.saturating_sub(ido_campaign.funder_deposits);
+    token::transfer_checked(cpi_context, owner_tokens, ctx.accounts.token_mint.decimals)?;
 
-    ido_campaign.sale_closed = true;
-    ido_campaign.funds_withdrawn = true;
+    ido_campaign.transition(CampaignStatus::Cancelled)?;
+    if let Some(registry_entry) = ctx.accounts.registry_entry.as_mut() {
+        registry_entry.record_status(ido_campaign);
+    }
 
     Ok(())
 }
\ No newline at end of file
@@ -1,5 +1,5 @@
 use {
-    crate::{instructions::IdoError, state::IdoCampaign},
+    crate::{instructions::IdoError, state::{CampaignStatus, IdoCampaign, RegistryEntry, SaleMode}},
     anchor_lang::prelude::*,
 };
 
@@ -17,17 +17,33 @@ pub struct CloseCampaignIfSoftCapNotReached<'info> {
         constraint = ido_campaign.authority == ido_campaign_owner.key() @ IdoError::ErrUnauthorized,
     )]
     pub ido_campaign: Account<'info, IdoCampaign>,
+
+    /// Gets the new status mirrored, missing for campaigns created before the registry.
+    #[account(
+        mut,
+        constraint = registry_entry.ido_campaign == ido_campaign.key() @ IdoError::ErrInvalidIdoCampaign,
+    )]
+    pub registry_entry: Option<Account<'info, RegistryEntry>>,
 }
 
 pub fn close_campaign_if_soft_cap_not_reached(ctx: Context<CloseCampaignIfSoftCapNotReached>) -> Result<()> {
     let now = Clock::get()?.unix_timestamp as u64;
     let ido_campaign = &mut ctx.accounts.ido_campaign;
 
-    require!(!ido_campaign.sale_closed, IdoError::ErrSaleAlreadyClosed);
-    require!(now >= ido_campaign.end_sale_time, IdoError::ErrInvalidEndSaleTime);
+    ido_campaign.sync_status(now)?;
+    require!(!ido_campaign.status.is_closed(), IdoError::ErrSaleAlreadyClosed);
+    require!(now > ido_campaign.end_sale_time, IdoError::ErrInvalidEndSaleTime);
+    // total_sold of a batch auction is only known once the bids are settled.
+    require!(
+        ido_campaign.sale_mode != SaleMode::BatchAuction || ido_campaign.auction_settled,
+        IdoError::ErrAuctionNotSettled
+    );
     require!(ido_campaign.total_sold < ido_campaign.soft_cap, IdoError::ErrSoftCapReached);
 
-    ido_campaign.sale_closed = true;
+    ido_campaign.transition(CampaignStatus::Failed)?;
+    if let Some(registry_entry) = ctx.accounts.registry_entry.as_mut() {
+        registry_entry.record_status(ido_campaign);
+    }
 
     return Ok(());
 }
\ No newline at end of file
@@ -0,0 +1,53 @@
+use {
+    crate::{
+        instructions::IdoError,
+        state::{IdoCampaign, Lottery, LotteryTicket},
+    },
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct CloseLotteryTicket<'info> {
+    #[account(mut)]
+    pub participant: Signer<'info>,
+
+    /// CHECK: This account is used only as a seed to derive the ido_campaign PDA
+    pub ido_campaign_owner: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"ido_campaign", ido_campaign_owner.key().as_ref()], bump,
+    )]
+    pub ido_campaign: Account<'info, IdoCampaign>,
+
+    #[account(
+        seeds = [b"lottery", ido_campaign.key().as_ref()], bump,
+    )]
+    pub lottery: Account<'info, Lottery>,
+
+    #[account(
+        mut,
+        close = participant,
+        seeds = [b"lottery_ticket", ido_campaign.key().as_ref(), participant.key().as_ref()], bump,
+    )]
+    pub lottery_ticket: Account<'info, LotteryTicket>,
+}
+
+/// Returns the deposit and the rent once the ticket can no longer be used to join.
+pub fn close_lottery_ticket(ctx: Context<CloseLotteryTicket>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp as u64;
+    ctx.accounts.ido_campaign.sync_status(now)?;
+
+    if ctx.accounts.ido_campaign.status.is_closed() {
+        return Ok(());
+    }
+
+    let lottery = &ctx.accounts.lottery;
+    require!(lottery.drawn, IdoError::ErrLotteryNotDrawn);
+    require!(
+        !lottery.is_winner(ctx.accounts.lottery_ticket.index) || now > lottery.guaranteed_window_end,
+        IdoError::ErrLotteryTicketStillNeeded
+    );
+
+    Ok(())
+}
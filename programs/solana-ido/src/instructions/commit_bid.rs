@@ -0,0 +1,74 @@
+use {
+    crate::{instructions::IdoError, state::{CampaignStatus, IdoCampaign, SaleMode, User, USER_VERSION}},
+    anchor_lang::{prelude::*, system_program::{self, Transfer}},
+};
+
+#[derive(Accounts)]
+pub struct CommitBid<'info> {
+    #[account(mut)]
+    pub participant: Signer<'info>,
+
+    /// CHECK: This account is used only as a seed to derive the ido_campaign PDA
+    pub ido_campaign_owner: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"ido_campaign", ido_campaign_owner.key().as_ref()], bump,
+    )]
+    pub ido_campaign: Account<'info, IdoCampaign>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_treasury", ido_campaign.key().as_ref()], bump,
+    )]
+    pub sol_treasury: SystemAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = participant,
+        space = 8 + User::INIT_SPACE,
+        seeds = [b"user", ido_campaign.key().as_ref(), participant.key().as_ref()], bump,
+    )]
+    pub user: Account<'info, User>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// `bid_commitment` is `sha256(price_lamports_le || quantity_le || salt || participant)`,
+/// the deposit has to cover `price_lamports * quantity` for the bid to be revealed.
+pub fn commit_bid(ctx: Context<CommitBid>, bid_commitment: [u8; 32], deposit_lamports: u64) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp as u64;
+    ctx.accounts.ido_campaign.sync_status(now)?;
+
+    let ido_campaign = &ctx.accounts.ido_campaign;
+
+    require!(ido_campaign.sale_mode == SaleMode::BatchAuction, IdoError::ErrInvalidSaleMode);
+    require!(!ido_campaign.status.is_closed(), IdoError::ErrSaleAlreadyClosed);
+    require!(ido_campaign.status != CampaignStatus::Created, IdoError::ErrTokenSupplyNotDeposited);
+    require!(ido_campaign.status == CampaignStatus::Live, IdoError::ErrInvalidSalePeriod);
+    require!(ctx.accounts.user.joined_at == 0, IdoError::ErrUserAlreadyJoined);
+    require!(deposit_lamports > 0, IdoError::ErrInsufficientFunds);
+
+    let cpi_context = CpiContext::new(
+        ctx.accounts.system_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.participant.to_account_info(),
+            to: ctx.accounts.sol_treasury.to_account_info(),
+        },
+    );
+    system_program::transfer(cpi_context, deposit_lamports)?;
+
+    let user = &mut ctx.accounts.user;
+    user.version = USER_VERSION;
+    user.joined_at = now;
+    user.ido_campaign = ctx.accounts.ido_campaign.key();
+    user.participant = ctx.accounts.participant.key();
+    user.amount = 0;
+    user.paid_lamports = deposit_lamports;
+    user.claimed = 0;
+    user.bid_commitment = bid_commitment;
+
+    ctx.accounts.ido_campaign.total_participants += 1;
+
+    Ok(())
+}
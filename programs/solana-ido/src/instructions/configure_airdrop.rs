@@ -0,0 +1,47 @@
+use {
+    crate::{
+        instructions::{check_sale_mode_configurable, IdoError},
+        state::{Airdrop, IdoCampaign, SaleMode, AIRDROP_VERSION},
+    },
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct ConfigureAirdrop<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"ido_campaign", owner.key().as_ref()], bump,
+    )]
+    pub ido_campaign: Account<'info, IdoCampaign>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + Airdrop::INIT_SPACE,
+        seeds = [b"airdrop", ido_campaign.key().as_ref()], bump,
+    )]
+    pub airdrop: Account<'info, Airdrop>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// `hard_cap` is the airdrop supply deposited by `deposit_tokens_to_sale`.
+pub fn configure_airdrop(ctx: Context<ConfigureAirdrop>, merkle_root: [u8; 32], claim_deadline: u64) -> Result<()> {
+    let ido_campaign = &mut ctx.accounts.ido_campaign;
+    check_sale_mode_configurable(ido_campaign, ctx.accounts.owner.key())?;
+    require!(claim_deadline > ido_campaign.end_sale_time, IdoError::ErrInvalidClaimDeadline);
+
+    ido_campaign.sale_mode = SaleMode::Airdrop;
+    ido_campaign.price_lamports = 0;
+
+    let airdrop = &mut ctx.accounts.airdrop;
+    airdrop.version = AIRDROP_VERSION;
+    airdrop.ido_campaign = ido_campaign.key();
+    airdrop.merkle_root = merkle_root;
+    airdrop.claim_deadline = claim_deadline;
+
+    Ok(())
+}
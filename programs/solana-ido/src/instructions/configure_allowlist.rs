@@ -0,0 +1,23 @@
+use {
+    crate::{instructions::check_sale_configurable, state::IdoCampaign},
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct ConfigureAllowlist<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"ido_campaign", owner.key().as_ref()], bump,
+    )]
+    pub ido_campaign: Account<'info, IdoCampaign>,
+}
+
+pub fn configure_allowlist(ctx: Context<ConfigureAllowlist>, enabled: bool) -> Result<()> {
+    check_sale_configurable(&ctx.accounts.ido_campaign, ctx.accounts.owner.key())?;
+
+    ctx.accounts.ido_campaign.allowlist_enabled = enabled;
+
+    Ok(())
+}
@@ -0,0 +1,58 @@
+use {
+    crate::{
+        instructions::{check_sale_configurable, IdoError},
+        state::IdoCampaign,
+    },
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct ConfigureAntiBot<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"ido_campaign", owner.key().as_ref()], bump,
+    )]
+    pub ido_campaign: Account<'info, IdoCampaign>,
+}
+
+/// Zero `max_joins_per_slot` or `ramp_up_duration` turns the matching protection off.
+/// `max_joins_per_slot` counts the wallet's joins into any campaign, its single join
+/// into this one never exceeds the limit on its own.
+pub fn configure_anti_bot(
+    ctx: Context<ConfigureAntiBot>,
+    reject_foreign_instructions: bool,
+    max_joins_per_slot: u64,
+    ramp_up_duration: u64,
+    ramp_up_start_allocations: u64,
+) -> Result<()> {
+    let ido_campaign = &mut ctx.accounts.ido_campaign;
+    check_sale_configurable(ido_campaign, ctx.accounts.owner.key())?;
+    check_ramp_up(ido_campaign, ramp_up_duration, ramp_up_start_allocations)?;
+
+    ido_campaign.reject_foreign_instructions = reject_foreign_instructions;
+    ido_campaign.max_joins_per_slot = max_joins_per_slot;
+    ido_campaign.ramp_up_duration = ramp_up_duration;
+    ido_campaign.ramp_up_start_allocations = ramp_up_start_allocations;
+
+    Ok(())
+}
+
+fn check_ramp_up(ido_campaign: &IdoCampaign, ramp_up_duration: u64, ramp_up_start_allocations: u64) -> Result<()> {
+    if ramp_up_duration == 0 {
+        return Ok(());
+    }
+
+    require!(
+        ramp_up_duration <= ido_campaign.end_sale_time - ido_campaign.start_sale_time,
+        IdoError::ErrInvalidAntiBotParameters
+    );
+    require!(
+        ramp_up_start_allocations > 0
+            && ramp_up_start_allocations <= ido_campaign.available_allocations_per_participant,
+        IdoError::ErrInvalidAntiBotParameters
+    );
+
+    Ok(())
+}
@@ -0,0 +1,86 @@
+use {
+    crate::{
+        instructions::{check_sale_mode_configurable, fund_sol_treasury_rent_reserve, IdoError},
+        state::{BidBook, IdoCampaign, SaleMode, BID_BOOK_VERSION, MAX_BID_PRICE_LEVELS},
+    },
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct ConfigureBatchAuction<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"ido_campaign", owner.key().as_ref()], bump,
+    )]
+    pub ido_campaign: Account<'info, IdoCampaign>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_treasury", ido_campaign.key().as_ref()], bump,
+        constraint = sol_treasury.key() == ido_campaign.sol_treasury @ IdoError::ErrInvalidSolTreasury,
+    )]
+    pub sol_treasury: SystemAccount<'info>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + BidBook::INIT_SPACE,
+        seeds = [b"bid_book", ido_campaign.key().as_ref()], bump,
+    )]
+    pub bid_book: Account<'info, BidBook>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn configure_batch_auction(
+    ctx: Context<ConfigureBatchAuction>,
+    bid_tick_lamports: u64,
+    reveal_end_time: u64,
+) -> Result<()> {
+    check_sale_mode_configurable(&ctx.accounts.ido_campaign, ctx.accounts.owner.key())?;
+    check_batch_auction_parameters(&ctx.accounts.ido_campaign, bid_tick_lamports, reveal_end_time)?;
+
+    fund_sol_treasury_rent_reserve(
+        &ctx.accounts.owner,
+        &ctx.accounts.sol_treasury,
+        &ctx.accounts.system_program,
+    )?;
+
+    let ido_campaign = &mut ctx.accounts.ido_campaign;
+    ido_campaign.sale_mode = SaleMode::BatchAuction;
+    ido_campaign.bid_tick_lamports = bid_tick_lamports;
+    ido_campaign.reveal_end_time = reveal_end_time;
+    ido_campaign.clearing_price_lamports = ido_campaign.price_lamports;
+
+    let bid_book = &mut ctx.accounts.bid_book;
+    bid_book.version = BID_BOOK_VERSION;
+    bid_book.ido_campaign = ido_campaign.key();
+    bid_book.demand = [0; MAX_BID_PRICE_LEVELS];
+
+    Ok(())
+}
+
+fn check_batch_auction_parameters(
+    ido_campaign: &IdoCampaign,
+    bid_tick_lamports: u64,
+    reveal_end_time: u64,
+) -> Result<()> {
+    require!(bid_tick_lamports > 0, IdoError::ErrInvalidBidPrice);
+
+    // The highest level has to be payable for the whole hard cap.
+    ido_campaign.price_lamports
+        .checked_add(
+            bid_tick_lamports
+                .checked_mul(MAX_BID_PRICE_LEVELS as u64 - 1)
+                .ok_or(IdoError::ErrInvalidBidPrice)?,
+        )
+        .and_then(|max_price| max_price.checked_mul(ido_campaign.hard_cap))
+        .ok_or(IdoError::ErrInvalidBidPrice)?;
+
+    require!(reveal_end_time > ido_campaign.end_sale_time, IdoError::ErrInvalidRevealPeriod);
+
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use {
+    crate::{
+        instructions::{check_sale_mode_configurable, IdoError},
+        state::{BondingCurve, IdoCampaign, SaleMode, MAX_BONDING_CURVE_STEPS},
+    },
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct ConfigureBondingCurve<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"ido_campaign", owner.key().as_ref()], bump,
+    )]
+    pub ido_campaign: Account<'info, IdoCampaign>,
+}
+
+pub fn configure_bonding_curve(ctx: Context<ConfigureBondingCurve>, bonding_curve: BondingCurve) -> Result<()> {
+    check_sale_mode_configurable(&ctx.accounts.ido_campaign, ctx.accounts.owner.key())?;
+    check_bonding_curve(&ctx.accounts.ido_campaign, bonding_curve)?;
+
+    let ido_campaign = &mut ctx.accounts.ido_campaign;
+    ido_campaign.sale_mode = SaleMode::BondingCurve;
+    ido_campaign.bonding_curve = bonding_curve;
+
+    Ok(())
+}
+
+fn check_bonding_curve(ido_campaign: &IdoCampaign, bonding_curve: BondingCurve) -> Result<()> {
+    match bonding_curve {
+        BondingCurve::Linear { slope_lamports } => {
+            require!(slope_lamports > 0, IdoError::ErrInvalidBondingCurve);
+        }
+        BondingCurve::ExponentialSteps { step_tokens, growth_bps } => {
+            require!(step_tokens > 0 && growth_bps > 0, IdoError::ErrInvalidBondingCurve);
+            require!(
+                ido_campaign.hard_cap.div_ceil(step_tokens) <= MAX_BONDING_CURVE_STEPS,
+                IdoError::ErrInvalidBondingCurve
+            );
+        }
+    }
+
+    // Selling out the whole supply has to be payable, so no purchase can overflow later.
+    bonding_curve
+        .cost(ido_campaign.price_lamports, 0, ido_campaign.hard_cap)
+        .map_err(|_| error!(IdoError::ErrInvalidBondingCurve))?;
+
+    Ok(())
+}
@@ -0,0 +1,33 @@
+use {
+    crate::{
+        instructions::{check_sale_configurable, IdoError},
+        state::IdoCampaign,
+    },
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct ConfigureClaimDeadline<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"ido_campaign", owner.key().as_ref()], bump,
+    )]
+    pub ido_campaign: Account<'info, IdoCampaign>,
+}
+
+/// 0 as `claim_deadline` lets claims run forever. The deadline comes after the vesting end,
+/// so every participant had the chance to claim the full position.
+pub fn configure_claim_deadline(ctx: Context<ConfigureClaimDeadline>, claim_deadline: u64) -> Result<()> {
+    let ido_campaign = &mut ctx.accounts.ido_campaign;
+    check_sale_configurable(ido_campaign, ctx.accounts.owner.key())?;
+    require!(
+        claim_deadline == 0 || claim_deadline > ido_campaign.vesting_end_time,
+        IdoError::ErrInvalidClaimDeadline
+    );
+
+    ido_campaign.claim_deadline = claim_deadline;
+
+    Ok(())
+}
@@ -0,0 +1,71 @@
+use {
+    crate::{
+        instructions::{check_sale_mode_configurable, fund_sol_treasury_rent_reserve, IdoError},
+        state::{IdoCampaign, PriceDecay, SaleMode},
+    },
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct ConfigureDutchAuction<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"ido_campaign", owner.key().as_ref()], bump,
+    )]
+    pub ido_campaign: Account<'info, IdoCampaign>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_treasury", ido_campaign.key().as_ref()], bump,
+        constraint = sol_treasury.key() == ido_campaign.sol_treasury @ IdoError::ErrInvalidSolTreasury,
+    )]
+    pub sol_treasury: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn configure_dutch_auction(
+    ctx: Context<ConfigureDutchAuction>,
+    floor_price_lamports: u64,
+    price_decay: PriceDecay,
+    settle_at_clearing_price: bool,
+) -> Result<()> {
+    check_sale_mode_configurable(&ctx.accounts.ido_campaign, ctx.accounts.owner.key())?;
+    check_dutch_auction_parameters(&ctx.accounts.ido_campaign, floor_price_lamports, price_decay)?;
+
+    if settle_at_clearing_price {
+        fund_sol_treasury_rent_reserve(
+            &ctx.accounts.owner,
+            &ctx.accounts.sol_treasury,
+            &ctx.accounts.system_program,
+        )?;
+    }
+
+    let ido_campaign = &mut ctx.accounts.ido_campaign;
+    ido_campaign.sale_mode = SaleMode::DutchAuction;
+    ido_campaign.floor_price_lamports = floor_price_lamports;
+    ido_campaign.price_decay = price_decay;
+    ido_campaign.settle_at_clearing_price = settle_at_clearing_price;
+    ido_campaign.clearing_price_lamports = ido_campaign.price_lamports;
+
+    Ok(())
+}
+
+fn check_dutch_auction_parameters(
+    ido_campaign: &IdoCampaign,
+    floor_price_lamports: u64,
+    price_decay: PriceDecay,
+) -> Result<()> {
+    require!(floor_price_lamports > 0, IdoError::ErrInvalidPrice);
+    require!(floor_price_lamports < ido_campaign.price_lamports, IdoError::ErrInvalidPrice);
+
+    if let PriceDecay::Stepped { interval } = price_decay {
+        let duration = ido_campaign.end_sale_time - ido_campaign.start_sale_time;
+        require!(interval > 0 && interval <= duration, IdoError::ErrInvalidPriceDecay);
+    }
+
+    Ok(())
+}
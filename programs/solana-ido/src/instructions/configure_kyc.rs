@@ -0,0 +1,24 @@
+use {
+    crate::{instructions::check_sale_configurable, state::IdoCampaign},
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct ConfigureKyc<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"ido_campaign", owner.key().as_ref()], bump,
+    )]
+    pub ido_campaign: Account<'info, IdoCampaign>,
+}
+
+/// `Pubkey::default()` turns the KYC requirement off again.
+pub fn configure_kyc(ctx: Context<ConfigureKyc>, kyc_signer: Pubkey) -> Result<()> {
+    check_sale_configurable(&ctx.accounts.ido_campaign, ctx.accounts.owner.key())?;
+
+    ctx.accounts.ido_campaign.kyc_signer = kyc_signer;
+
+    Ok(())
+}
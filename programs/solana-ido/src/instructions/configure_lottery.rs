@@ -0,0 +1,64 @@
+use {
+    crate::{
+        instructions::{check_sale_configurable, IdoError},
+        state::{IdoCampaign, Lottery, LOTTERY_VERSION, MAX_LOTTERY_REGISTRANTS},
+    },
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct ConfigureLottery<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"ido_campaign", owner.key().as_ref()], bump,
+    )]
+    pub ido_campaign: Account<'info, IdoCampaign>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + Lottery::INIT_SPACE,
+        seeds = [b"lottery", ido_campaign.key().as_ref()], bump,
+    )]
+    pub lottery: Account<'info, Lottery>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn configure_lottery(
+    ctx: Context<ConfigureLottery>,
+    registration_deposit_lamports: u64,
+    winners_count: u64,
+    guaranteed_window_end: u64,
+) -> Result<()> {
+    check_sale_configurable(&ctx.accounts.ido_campaign, ctx.accounts.owner.key())?;
+    check_lottery_parameters(&ctx.accounts.ido_campaign, winners_count, guaranteed_window_end)?;
+
+    // Registration only opens once the supply is deposited, so there are no tickets yet.
+    let lottery = &mut ctx.accounts.lottery;
+    lottery.version = LOTTERY_VERSION;
+    lottery.ido_campaign = ctx.accounts.ido_campaign.key();
+    lottery.registration_deposit_lamports = registration_deposit_lamports;
+    lottery.winners_count = winners_count;
+    lottery.guaranteed_window_end = guaranteed_window_end;
+
+    ctx.accounts.ido_campaign.lottery_enabled = true;
+
+    Ok(())
+}
+
+fn check_lottery_parameters(ido_campaign: &IdoCampaign, winners_count: u64, guaranteed_window_end: u64) -> Result<()> {
+    require!(
+        winners_count > 0 && winners_count <= MAX_LOTTERY_REGISTRANTS,
+        IdoError::ErrInvalidLotteryParameters
+    );
+    require!(
+        guaranteed_window_end >= ido_campaign.start_sale_time && guaranteed_window_end <= ido_campaign.end_sale_time,
+        IdoError::ErrInvalidLotteryParameters
+    );
+
+    Ok(())
+}
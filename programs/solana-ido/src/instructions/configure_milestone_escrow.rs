@@ -0,0 +1,87 @@
+use {
+    crate::{
+        instructions::{check_sale_configurable, fund_sol_treasury_rent_reserve, IdoError},
+        state::{
+            IdoCampaign, MilestoneEscrow, Tranche, TrancheState, TrancheTerms, TrancheUnlock,
+            MAX_ESCROW_TRANCHES, MILESTONE_ESCROW_VERSION,
+        },
+    },
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct ConfigureMilestoneEscrow<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"ido_campaign", owner.key().as_ref()], bump,
+    )]
+    pub ido_campaign: Account<'info, IdoCampaign>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_treasury", ido_campaign.key().as_ref()], bump,
+        constraint = sol_treasury.key() == ido_campaign.sol_treasury @ IdoError::ErrInvalidSolTreasury,
+    )]
+    pub sol_treasury: SystemAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + MilestoneEscrow::INIT_SPACE,
+        seeds = [b"milestone_escrow", ido_campaign.key().as_ref()], bump,
+    )]
+    pub milestone_escrow: Account<'info, MilestoneEscrow>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn configure_milestone_escrow(ctx: Context<ConfigureMilestoneEscrow>, tranches: Vec<TrancheTerms>) -> Result<()> {
+    check_sale_configurable(&ctx.accounts.ido_campaign, ctx.accounts.owner.key())?;
+    check_tranches(&ctx.accounts.ido_campaign, &tranches)?;
+
+    // Escrow refunds round down, the dust has to leave the treasury rent exempt.
+    fund_sol_treasury_rent_reserve(
+        &ctx.accounts.owner,
+        &ctx.accounts.sol_treasury,
+        &ctx.accounts.system_program,
+    )?;
+
+    let milestone_escrow = &mut ctx.accounts.milestone_escrow;
+    milestone_escrow.version = MILESTONE_ESCROW_VERSION;
+    milestone_escrow.ido_campaign = ctx.accounts.ido_campaign.key();
+    milestone_escrow.tranches = tranches
+        .into_iter()
+        .map(|terms| Tranche { terms, state: TrancheState::Locked })
+        .collect();
+
+    ctx.accounts.ido_campaign.milestone_escrow_enabled = true;
+
+    Ok(())
+}
+
+fn check_tranches(ido_campaign: &IdoCampaign, tranches: &[TrancheTerms]) -> Result<()> {
+    require!(
+        !tranches.is_empty() && tranches.len() <= MAX_ESCROW_TRANCHES,
+        IdoError::ErrInvalidEscrowTranches
+    );
+    require!(
+        tranches.iter().all(|terms| terms.release_bps > 0),
+        IdoError::ErrInvalidEscrowTranches
+    );
+    require!(
+        tranches.iter().map(|terms| terms.release_bps as u64).sum::<u64>() == 10_000,
+        IdoError::ErrInvalidEscrowTranches
+    );
+    require!(
+        tranches.iter().all(|terms| match terms.unlock {
+            TrancheUnlock::Time { unlock_time } => unlock_time > ido_campaign.end_sale_time,
+            TrancheUnlock::Milestone => true,
+        }),
+        IdoError::ErrInvalidEscrowTranches
+    );
+
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use {
+    crate::{
+        instructions::{check_sale_configurable, IdoError},
+        state::{IdoCampaign, MilestoneEscrow},
+    },
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct ConfigureMilestoneVoting<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [b"ido_campaign", owner.key().as_ref()], bump,
+    )]
+    pub ido_campaign: Account<'info, IdoCampaign>,
+
+    #[account(
+        mut,
+        seeds = [b"milestone_escrow", ido_campaign.key().as_ref()], bump,
+    )]
+    pub milestone_escrow: Account<'info, MilestoneEscrow>,
+}
+
+pub fn configure_milestone_voting(
+    ctx: Context<ConfigureMilestoneVoting>,
+    quorum_bps: u16,
+    approval_threshold_bps: u16,
+) -> Result<()> {
+    check_sale_configurable(&ctx.accounts.ido_campaign, ctx.accounts.owner.key())?;
+    require!(
+        quorum_bps <= 10_000 && approval_threshold_bps > 0 && approval_threshold_bps <= 10_000,
+        IdoError::ErrInvalidVotingParameters
+    );
+
+    let milestone_escrow = &mut ctx.accounts.milestone_escrow;
+    milestone_escrow.voting_enabled = true;
+    milestone_escrow.quorum_bps = quorum_bps;
+    milestone_escrow.approval_threshold_bps = approval_threshold_bps;
+
+    Ok(())
+}
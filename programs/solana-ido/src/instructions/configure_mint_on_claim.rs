@@ -0,0 +1,51 @@
+use {
+    crate::{
+        instructions::{check_sale_configurable, IdoError},
+        state::IdoCampaign,
+    },
+    anchor_lang::{prelude::*, solana_program::program_option::COption},
+    anchor_spl::token::{self, spl_token::instruction::AuthorityType, Mint, SetAuthority, Token},
+};
+
+#[derive(Accounts)]
+pub struct ConfigureMintOnClaim<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"ido_campaign", owner.key().as_ref()], bump,
+    )]
+    pub ido_campaign: Account<'info, IdoCampaign>,
+
+    #[account(
+        mut,
+        constraint = token_mint.key() == ido_campaign.token_mint @ IdoError::ErrInvalidTokenMint,
+    )]
+    pub token_mint: Account<'info, Mint>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Hands the mint authority to the campaign, `deposit_tokens_to_sale` then only opens the sale.
+/// `release_mint_authority` gives it back once the campaign is over.
+pub fn configure_mint_on_claim(ctx: Context<ConfigureMintOnClaim>) -> Result<()> {
+    check_sale_configurable(&ctx.accounts.ido_campaign, ctx.accounts.owner.key())?;
+    require!(!ctx.accounts.ido_campaign.mint_on_claim, IdoError::ErrMintOnClaimEnabled);
+    require!(
+        ctx.accounts.token_mint.mint_authority == COption::Some(ctx.accounts.owner.key()),
+        IdoError::ErrInvalidMintAuthority
+    );
+
+    let cpi_context = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        SetAuthority {
+            current_authority: ctx.accounts.owner.to_account_info(),
+            account_or_mint: ctx.accounts.token_mint.to_account_info(),
+        },
+    );
+    token::set_authority(cpi_context, AuthorityType::MintTokens, Some(ctx.accounts.ido_campaign.key()))?;
+
+    ctx.accounts.ido_campaign.mint_on_claim = true;
+
+    Ok(())
+}
@@ -0,0 +1,85 @@
+use {
+    crate::{instructions::IdoError, state::{CampaignStatus, IdoCampaign, SaleMode}},
+    anchor_lang::{prelude::*, system_program::{self, Transfer}},
+};
+
+#[derive(Accounts)]
+pub struct ConfigureProRataSale<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"ido_campaign", owner.key().as_ref()], bump,
+    )]
+    pub ido_campaign: Account<'info, IdoCampaign>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_treasury", ido_campaign.key().as_ref()], bump,
+        constraint = sol_treasury.key() == ido_campaign.sol_treasury @ IdoError::ErrInvalidSolTreasury,
+    )]
+    pub sol_treasury: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn configure_pro_rata_sale(ctx: Context<ConfigureProRataSale>) -> Result<()> {
+    check_sale_mode_configurable(&ctx.accounts.ido_campaign, ctx.accounts.owner.key())?;
+
+    fund_sol_treasury_rent_reserve(
+        &ctx.accounts.owner,
+        &ctx.accounts.sol_treasury,
+        &ctx.accounts.system_program,
+    )?;
+
+    ctx.accounts.ido_campaign.sale_mode = SaleMode::ProRata;
+
+    Ok(())
+}
+
+/// Sale terms can only change before the supply is deposited, so nobody joins under other terms.
+pub(crate) fn check_sale_configurable(ido_campaign: &IdoCampaign, owner: Pubkey) -> Result<()> {
+    require!(ido_campaign.authority == owner, IdoError::ErrUnauthorized);
+    require!(!ido_campaign.status.is_closed(), IdoError::ErrSaleAlreadyClosed);
+    require!(
+        ido_campaign.status == CampaignStatus::Created,
+        IdoError::ErrTokenSupplyAlreadyDeposited
+    );
+
+    Ok(())
+}
+
+/// Sale modes are exclusive, a campaign leaves `SaleMode::FixedPrice` at most once.
+pub(crate) fn check_sale_mode_configurable(ido_campaign: &IdoCampaign, owner: Pubkey) -> Result<()> {
+    check_sale_configurable(ido_campaign, owner)?;
+    require!(ido_campaign.sale_mode == SaleMode::FixedPrice, IdoError::ErrInvalidSaleMode);
+
+    Ok(())
+}
+
+/// Payouts in modes with partial refunds leave rounding dust in the treasury, the reserve
+/// keeps it rent exempt so the last transfers out of it never fail.
+pub(crate) fn fund_sol_treasury_rent_reserve<'info>(
+    owner: &Signer<'info>,
+    sol_treasury: &SystemAccount<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    let reserve = Rent::get()?
+        .minimum_balance(0)
+        .saturating_sub(sol_treasury.lamports());
+    if reserve == 0 {
+        return Ok(());
+    }
+
+    let cpi_context = CpiContext::new(
+        system_program.to_account_info(),
+        Transfer {
+            from: owner.to_account_info(),
+            to: sol_treasury.to_account_info(),
+        },
+    );
+    system_program::transfer(cpi_context, reserve)?;
+
+    Ok(())
+}
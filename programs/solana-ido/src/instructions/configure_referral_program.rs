@@ -0,0 +1,50 @@
+use {
+    crate::{
+        instructions::{check_sale_configurable, IdoError},
+        state::{IdoCampaign, ReferralPayout, SaleMode, MAX_REFERRAL_BPS},
+    },
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct ConfigureReferralProgram<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"ido_campaign", owner.key().as_ref()], bump,
+    )]
+    pub ido_campaign: Account<'info, IdoCampaign>,
+}
+
+/// 0 as `referral_bps` turns the referral program off. Only sales where every purchase
+/// is final when it is made can reward referrers.
+pub fn configure_referral_program(
+    ctx: Context<ConfigureReferralProgram>,
+    referral_bps: u16,
+    referral_payout: ReferralPayout,
+    referral_budget_lamports: u64,
+) -> Result<()> {
+    let ido_campaign = &mut ctx.accounts.ido_campaign;
+    check_sale_configurable(ido_campaign, ctx.accounts.owner.key())?;
+    require!(
+        referral_bps <= MAX_REFERRAL_BPS && (referral_bps == 0 || referral_budget_lamports > 0),
+        IdoError::ErrInvalidReferralParameters
+    );
+    require!(
+        referral_bps == 0 || supports_referrals(ido_campaign),
+        IdoError::ErrInvalidSaleMode
+    );
+
+    ido_campaign.referral_bps = referral_bps;
+    ido_campaign.referral_payout = referral_payout;
+    ido_campaign.referral_budget_lamports = referral_budget_lamports;
+
+    Ok(())
+}
+
+/// Pro-rata and clearing price sales refund part of a purchase after the sale,
+/// which would leave rewards paid on lamports the campaign never kept.
+pub(crate) fn supports_referrals(ido_campaign: &IdoCampaign) -> bool {
+    ido_campaign.sale_mode != SaleMode::ProRata && !ido_campaign.sells_at_clearing_price()
+}
@@ -0,0 +1,57 @@
+use {
+    crate::{
+        instructions::{check_sale_configurable, IdoError},
+        state::{IdoCampaign, StakeTier, StakeTiers, MAX_STAKE_TIERS, STAKE_TIERS_VERSION},
+    },
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct ConfigureStakeTiers<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"ido_campaign", owner.key().as_ref()], bump,
+    )]
+    pub ido_campaign: Account<'info, IdoCampaign>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + StakeTiers::INIT_SPACE,
+        seeds = [b"stake_tiers", ido_campaign.key().as_ref()], bump,
+    )]
+    pub stake_tiers: Account<'info, StakeTiers>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn configure_stake_tiers(ctx: Context<ConfigureStakeTiers>, tiers: Vec<StakeTier>) -> Result<()> {
+    check_sale_configurable(&ctx.accounts.ido_campaign, ctx.accounts.owner.key())?;
+    check_stake_tiers(&tiers)?;
+
+    let stake_tiers = &mut ctx.accounts.stake_tiers;
+    stake_tiers.version = STAKE_TIERS_VERSION;
+    stake_tiers.ido_campaign = ctx.accounts.ido_campaign.key();
+    stake_tiers.tiers = tiers;
+
+    ctx.accounts.ido_campaign.stake_tiered = true;
+
+    Ok(())
+}
+
+fn check_stake_tiers(tiers: &[StakeTier]) -> Result<()> {
+    require!(!tiers.is_empty() && tiers.len() <= MAX_STAKE_TIERS, IdoError::ErrInvalidStakeTiers);
+    require!(
+        tiers.iter().all(|tier| tier.max_allocations > 0),
+        IdoError::ErrInvalidStakeTiers
+    );
+    require!(
+        tiers.windows(2).all(|pair| pair[0].min_stake < pair[1].min_stake),
+        IdoError::ErrInvalidStakeTiers
+    );
+
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use {
+    crate::{
+        instructions::{check_sale_configurable, IdoError},
+        state::IdoCampaign,
+    },
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct ConfigureTokenGate<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"ido_campaign", owner.key().as_ref()], bump,
+    )]
+    pub ido_campaign: Account<'info, IdoCampaign>,
+}
+
+/// `Pubkey::default()` as `gate_mint` opens the sale to everyone again, unless `gate_mint_list`
+/// gates it on the mints added with `add_to_gate_list`.
+pub fn configure_token_gate(
+    ctx: Context<ConfigureTokenGate>,
+    gate_mint: Pubkey,
+    gate_mint_list: bool,
+    gate_min_balance: u64,
+    gate_tokens_per_allocation: u64,
+) -> Result<()> {
+    let ido_campaign = &mut ctx.accounts.ido_campaign;
+    check_sale_configurable(ido_campaign, ctx.accounts.owner.key())?;
+    require!(
+        !(gate_mint_list && gate_mint != Pubkey::default()),
+        IdoError::ErrInvalidTokenGate
+    );
+    require!(
+        (gate_mint == Pubkey::default() && !gate_mint_list) || gate_min_balance > 0,
+        IdoError::ErrInvalidTokenGate
+    );
+
+    ido_campaign.gate_mint = gate_mint;
+    ido_campaign.gate_mint_list = gate_mint_list;
+    ido_campaign.gate_min_balance = gate_min_balance;
+    ido_campaign.gate_tokens_per_allocation = gate_tokens_per_allocation;
+
+    Ok(())
+}
@@ -0,0 +1,24 @@
+use {
+    crate::{instructions::check_sale_configurable, state::IdoCampaign},
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct ConfigureUnsoldTokenBurn<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"ido_campaign", owner.key().as_ref()], bump,
+    )]
+    pub ido_campaign: Account<'info, IdoCampaign>,
+}
+
+pub fn configure_unsold_token_burn(ctx: Context<ConfigureUnsoldTokenBurn>, burn_unsold_tokens: bool) -> Result<()> {
+    let ido_campaign = &mut ctx.accounts.ido_campaign;
+    check_sale_configurable(ido_campaign, ctx.accounts.owner.key())?;
+
+    ido_campaign.burn_unsold_tokens = burn_unsold_tokens;
+
+    Ok(())
+}
@@ -1,5 +1,8 @@
 use {
-    crate::{instructions::IdoError, state::IdoCampaign}, 
+    crate::{instructions::IdoError, state::{
+        CampaignMetadata, CampaignMetadataArgs, CampaignRegistry, CampaignStatus, IdoCampaign,
+        RegistryEntry, SaleMode, CAMPAIGN_METADATA_VERSION, CAMPAIGN_REGISTRY_VERSION, IDO_CAMPAIGN_VERSION,
+    }}, 
     anchor_lang::prelude::*, anchor_spl::token::{Mint, Token, TokenAccount}
 };
 
@@ -11,7 +14,7 @@ pub struct CreateIdoCampaign<'info> {
     #[account(
         init,
         payer = owner,
-        space = 8 + std::mem::size_of::<IdoCampaign>(),
+        space = 8 + IdoCampaign::INIT_SPACE,
         seeds = [b"ido_campaign", owner.key().as_ref()], bump,
     )]
     pub ido_campaign: Account<'info, IdoCampaign>,
@@ -32,6 +35,28 @@ pub struct CreateIdoCampaign<'info> {
 
     pub token_mint: Account<'info, Mint>,
 
+    #[account(
+        mut,
+        seeds = [b"campaign_registry"], bump,
+    )]
+    pub campaign_registry: Account<'info, CampaignRegistry>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + RegistryEntry::INIT_SPACE,
+        seeds = [b"registry_entry", campaign_registry.campaign_count.to_le_bytes().as_ref()], bump,
+    )]
+    pub registry_entry: Account<'info, RegistryEntry>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + CampaignMetadata::INIT_SPACE,
+        seeds = [b"campaign_metadata", ido_campaign.key().as_ref()], bump,
+    )]
+    pub campaign_metadata: Box<Account<'info, CampaignMetadata>>,
+
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
 }
@@ -47,12 +72,14 @@ pub fn initialize_sale(ctx: Context<CreateIdoCampaign>,
     hard_cap: u64,
     available_tokens_after_cliff_ptc: i32,
     available_allocations_per_participant: u64,
+    metadata: CampaignMetadataArgs,
 ) -> Result<()> {
     check_time(start_sale_time, end_sale_time, cliff, vesting_end_time)?;
     check_economic_parameters(price_lamports, allocation, available_allocations_per_participant, soft_cap, hard_cap, available_tokens_after_cliff_ptc)?;
     require!(ctx.accounts.token_mint.decimals == 0, IdoError::ErrInvalidTokenDecimals);
 
     let ido_campaign = &mut ctx.accounts.ido_campaign;
+    ido_campaign.version = IDO_CAMPAIGN_VERSION;
     ido_campaign.authority = ctx.accounts.owner.key();
     ido_campaign.token_treasury = ctx.accounts.tokens_treasury.key();
     ido_campaign.sol_treasury = ctx.accounts.sol_treasury.key();
@@ -71,11 +98,55 @@ pub fn initialize_sale(ctx: Context<CreateIdoCampaign>,
     ido_campaign.token_mint = ctx.accounts.token_mint.key();
     ido_campaign.available_allocations_per_participant = available_allocations_per_participant;
     ido_campaign.funds_withdrawn = false;
-    ido_campaign.sale_closed = false;
+    ido_campaign.status = CampaignStatus::Created;
+    ido_campaign.sale_mode = SaleMode::FixedPrice;
+    ido_campaign.total_committed = 0;
+
+    register_campaign(
+        &mut ctx.accounts.campaign_registry,
+        &mut ctx.accounts.registry_entry,
+        ctx.accounts.ido_campaign.key(),
+        ctx.accounts.owner.key(),
+        ctx.accounts.token_mint.key(),
+    )?;
+
+    let campaign_metadata = &mut ctx.accounts.campaign_metadata;
+    campaign_metadata.version = CAMPAIGN_METADATA_VERSION;
+    campaign_metadata.ido_campaign = ctx.accounts.ido_campaign.key();
+    campaign_metadata.set(
+        metadata.name,
+        metadata.symbol,
+        metadata.uri,
+        metadata.description,
+        metadata.social_links,
+        Clock::get()?.unix_timestamp as u64,
+    )?;
 
     return Ok(());
 }
 
+fn register_campaign(
+    campaign_registry: &mut CampaignRegistry,
+    registry_entry: &mut RegistryEntry,
+    ido_campaign: Pubkey,
+    authority: Pubkey,
+    token_mint: Pubkey,
+) -> Result<()> {
+    registry_entry.version = CAMPAIGN_REGISTRY_VERSION;
+    registry_entry.index = campaign_registry.campaign_count;
+    registry_entry.ido_campaign = ido_campaign;
+    registry_entry.authority = authority;
+    registry_entry.token_mint = token_mint;
+    registry_entry.created_at = Clock::get()?.unix_timestamp as u64;
+    registry_entry.status = CampaignStatus::Created;
+
+    campaign_registry.campaign_count = campaign_registry.campaign_count
+        .checked_add(1)
+        .ok_or(IdoError::ErrMathOverflow)?;
+
+    Ok(())
+}
+
 fn check_time(start_sale_time: u64, end_sale_time: u64, cliff: u64, vesting_end_time: u64) -> Result<()> {
     let now = Clock::get()?.unix_timestamp as u64;
 
@@ -0,0 +1,65 @@
+use {
+    crate::{
+        instructions::IdoError,
+        state::{
+            IdoCampaign, MilestoneEscrow, MilestoneProposal, TrancheState, TrancheUnlock,
+            MILESTONE_ESCROW_VERSION,
+        },
+    },
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+#[instruction(tranche_index: u8)]
+pub struct CreateMilestoneProposal<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [b"ido_campaign", owner.key().as_ref()], bump,
+    )]
+    pub ido_campaign: Account<'info, IdoCampaign>,
+
+    #[account(
+        seeds = [b"milestone_escrow", ido_campaign.key().as_ref()], bump,
+    )]
+    pub milestone_escrow: Account<'info, MilestoneEscrow>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + MilestoneProposal::INIT_SPACE,
+        seeds = [b"milestone_proposal", ido_campaign.key().as_ref(), &[tranche_index]], bump,
+    )]
+    pub milestone_proposal: Account<'info, MilestoneProposal>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Opens the vote on a milestone tranche once the owner claims the milestone is delivered.
+pub fn create_milestone_proposal(
+    ctx: Context<CreateMilestoneProposal>,
+    tranche_index: u8,
+    voting_end: u64,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp as u64;
+    require!(ctx.accounts.ido_campaign.funds_withdrawn, IdoError::ErrFundsNotWithdrawn);
+
+    let milestone_escrow = &ctx.accounts.milestone_escrow;
+    require!(milestone_escrow.voting_enabled, IdoError::ErrInvalidVotingParameters);
+    require!(!milestone_escrow.failed, IdoError::ErrEscrowFailed);
+
+    let tranche = milestone_escrow.tranche(tranche_index)?;
+    require!(tranche.terms.unlock == TrancheUnlock::Milestone, IdoError::ErrInvalidTranche);
+    require!(tranche.state == TrancheState::Locked, IdoError::ErrTrancheAlreadySettled);
+    require!(voting_end > now, IdoError::ErrInvalidVotingParameters);
+
+    let milestone_proposal = &mut ctx.accounts.milestone_proposal;
+    milestone_proposal.version = MILESTONE_ESCROW_VERSION;
+    milestone_proposal.ido_campaign = ctx.accounts.ido_campaign.key();
+    milestone_proposal.tranche_index = tranche_index;
+    milestone_proposal.voting_start = now;
+    milestone_proposal.voting_end = voting_end;
+
+    Ok(())
+}
@@ -0,0 +1,93 @@
+use {
+    crate::{
+        instructions::IdoError,
+        state::{IdoCampaign, TokenLock, MAX_TOKEN_LOCK_NAME_LEN, TOKEN_LOCK_VERSION},
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::token::{self, Mint, Token, TokenAccount, TransferChecked},
+};
+
+#[derive(Accounts)]
+pub struct CreateTokenLock<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(mut, constraint = owner_token_account.owner == owner.key() @ IdoError::ErrInvalidOwner)]
+    pub owner_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"ido_campaign", owner.key().as_ref()], bump,
+        constraint = ido_campaign.authority == owner.key() @ IdoError::ErrUnauthorized,
+    )]
+    pub ido_campaign: Account<'info, IdoCampaign>,
+
+    /// CHECK: This account is used only as a seed to derive the token_lock PDA
+    pub beneficiary: AccountInfo<'info>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + TokenLock::INIT_SPACE,
+        seeds = [b"token_lock", ido_campaign.key().as_ref(), beneficiary.key().as_ref()], bump,
+    )]
+    pub token_lock: Account<'info, TokenLock>,
+
+    #[account(
+        init,
+        payer = owner,
+        token::mint = token_mint,
+        token::authority = token_lock,
+        seeds = [b"token_lock_vault", token_lock.key().as_ref()], bump,
+    )]
+    pub token_lock_vault: Account<'info, TokenAccount>,
+
+    #[account(constraint = token_mint.key() == ido_campaign.token_mint @ IdoError::ErrInvalidTokenMint)]
+    pub token_mint: Account<'info, Mint>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_token_lock(
+    ctx: Context<CreateTokenLock>,
+    name: String,
+    amount: u64,
+    cliff: u64,
+    vesting_end_time: u64,
+    available_tokens_after_cliff_ptc: i32,
+    revocable: bool,
+) -> Result<()> {
+    require!(
+        name.len() <= MAX_TOKEN_LOCK_NAME_LEN
+            && amount > 0
+            && vesting_end_time > cliff
+            && (0..=100).contains(&available_tokens_after_cliff_ptc),
+        IdoError::ErrInvalidTokenLock
+    );
+
+    let token_lock = &mut ctx.accounts.token_lock;
+    token_lock.version = TOKEN_LOCK_VERSION;
+    token_lock.ido_campaign = ctx.accounts.ido_campaign.key();
+    token_lock.beneficiary = ctx.accounts.beneficiary.key();
+    token_lock.name = name;
+    token_lock.amount = amount;
+    token_lock.claimed = 0;
+    token_lock.cliff = cliff;
+    token_lock.vesting_end_time = vesting_end_time;
+    token_lock.available_tokens_after_cliff_ptc = available_tokens_after_cliff_ptc;
+    token_lock.revocable = revocable;
+    token_lock.revoked = false;
+
+    let cpi_context = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        TransferChecked {
+            from: ctx.accounts.owner_token_account.to_account_info(),
+            to: ctx.accounts.token_lock_vault.to_account_info(),
+            authority: ctx.accounts.owner.to_account_info(),
+            mint: ctx.accounts.token_mint.to_account_info(),
+        },
+    );
+    token::transfer_checked(cpi_context, amount, ctx.accounts.token_mint.decimals)?;
+
+    Ok(())
+}
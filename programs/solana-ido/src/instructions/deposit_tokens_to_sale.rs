@@ -1,5 +1,5 @@
 use {
-    crate::{instructions::IdoError, state::IdoCampaign},
+    crate::{instructions::IdoError, state::{CampaignStatus, IdoCampaign, RegistryEntry}},
     anchor_lang::prelude::*,
     anchor_spl::token::{self, Mint, Token, TokenAccount, TransferChecked},
 };
@@ -23,6 +23,14 @@ pub struct DepositTokensToSale<'info> {
     pub tokens_treasury: Account<'info, TokenAccount>,
 
     pub token_mint: Account<'info, Mint>,
+
+    /// Gets the new status mirrored, missing for campaigns created before the registry.
+    #[account(
+        mut,
+        constraint = registry_entry.ido_campaign == ido_campaign.key() @ IdoError::ErrInvalidIdoCampaign,
+    )]
+    pub registry_entry: Option<Account<'info, RegistryEntry>>,
+
     pub token_program: Program<'info, Token>,
 }
 
@@ -32,11 +40,9 @@ pub fn deposit_tokens_to_sale(ctx: Context<DepositTokensToSale>) -> Result<()> {
     let token_mint_account = &ctx.accounts.token_mint;
     let tokens_treasury = &ctx.accounts.tokens_treasury;
 
-    require!(!ido_campaign.sale_closed, IdoError::ErrSaleAlreadyClosed);
-    require!(!ido_campaign.funds_withdrawn, IdoError::ErrFundsAlreadyWithdrawn);
-    require!(!ido_campaign.token_supply_deposited, IdoError::ErrTokenSupplyAlreadyDeposited);
+    require!(!ido_campaign.status.is_closed(), IdoError::ErrSaleAlreadyClosed);
+    require!(ido_campaign.status == CampaignStatus::Created, IdoError::ErrTokenSupplyAlreadyDeposited);
     require!(token_mint_account.decimals == 0, IdoError::ErrInvalidTokenDecimals);
-    require!(!ido_campaign.token_supply_deposited, IdoError::ErrTokenSupplyAlreadyDeposited);
 
     check_token_accounts(
         owner_token_account, 
@@ -45,24 +51,35 @@ pub fn deposit_tokens_to_sale(ctx: Context<DepositTokensToSale>) -> Result<()> {
         tokens_treasury, 
     )?;
     
-    require!(
-        owner_token_account.amount >= ido_campaign.hard_cap,
-        IdoError::ErrInvalidBalanceOfTokensToDeposit
-    );
+    // Mint-on-claim campaigns mint every claim, there is no supply to deposit.
+    if !ido_campaign.mint_on_claim {
+        // Third-party funders may have covered part of the supply already.
+        let remaining_supply = ido_campaign.remaining_supply();
+        require!(
+            owner_token_account.amount >= remaining_supply,
+            IdoError::ErrInvalidBalanceOfTokensToDeposit
+        );
 
-    let cpi_accounts = TransferChecked {
-        from: owner_token_account.to_account_info(),
-        to: tokens_treasury.to_account_info(),
-        authority: ctx.accounts.owner.to_account_info(),
-        mint: token_mint_account.to_account_info(),
-    };
+        let cpi_accounts = TransferChecked {
+            from: owner_token_account.to_account_info(),
+            to: tokens_treasury.to_account_info(),
+            authority: ctx.accounts.owner.to_account_info(),
+            mint: token_mint_account.to_account_info(),
+        };
 
-    let cpi_program = ctx.accounts.token_program.to_account_info();
-    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
 
-    token::transfer_checked(cpi_ctx, ido_campaign.hard_cap, token_mint_account.decimals)?;
+        token::transfer_checked(cpi_ctx, remaining_supply, token_mint_account.decimals)?;
+        ido_campaign.total_deposited = ido_campaign.hard_cap;
+    }
 
-    ido_campaign.token_supply_deposited = true;
+    let now = Clock::get()?.unix_timestamp as u64;
+    ido_campaign.transition(CampaignStatus::Funded)?;
+    ido_campaign.sync_status(now)?;
+    if let Some(registry_entry) = ctx.accounts.registry_entry.as_mut() {
+        registry_entry.record_status(ido_campaign);
+    }
 
     return Ok(());
 }
@@ -0,0 +1,114 @@
+use {
+    crate::{
+        instructions::{draw_slot_expired, IdoError},
+        state::{IdoCampaign, Lottery},
+    },
+    anchor_lang::{
+        prelude::*,
+        solana_program::{hash::hashv, sysvar::slot_hashes},
+    },
+};
+
+#[derive(Accounts)]
+pub struct DrawLottery<'info> {
+    /// CHECK: This account is used only as a seed to derive the ido_campaign PDA
+    pub ido_campaign_owner: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"ido_campaign", ido_campaign_owner.key().as_ref()], bump,
+    )]
+    pub ido_campaign: Account<'info, IdoCampaign>,
+
+    #[account(
+        mut,
+        seeds = [b"lottery", ido_campaign.key().as_ref()], bump,
+    )]
+    pub lottery: Account<'info, Lottery>,
+
+    /// CHECK: Read raw, the sysvar is too large to deserialize on-chain
+    #[account(address = slot_hashes::ID)]
+    pub slot_hashes: UncheckedAccount<'info>,
+}
+
+/// Seeds the draw with the hash of `draw_slot`, or of the first block after it when the
+/// slot was skipped, which nobody could know when the draw was scheduled.
+pub fn draw_lottery(ctx: Context<DrawLottery>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp as u64;
+    let slot = Clock::get()?.slot;
+    ctx.accounts.ido_campaign.sync_status(now)?;
+
+    let ido_campaign = &ctx.accounts.ido_campaign;
+    require!(!ido_campaign.status.is_closed(), IdoError::ErrSaleAlreadyClosed);
+    require!(!ctx.accounts.lottery.drawn, IdoError::ErrLotteryAlreadyDrawn);
+
+    let draw_slot = ctx.accounts.lottery.draw_slot;
+    require!(draw_slot > 0, IdoError::ErrLotteryDrawNotScheduled);
+    require!(slot > draw_slot, IdoError::ErrLotteryDrawSlotNotReached);
+    require!(!draw_slot_expired(draw_slot, slot), IdoError::ErrLotteryDrawSlotExpired);
+
+    let slot_hashes_data = ctx.accounts.slot_hashes.try_borrow_data()?;
+    let draw_slot_hash = find_draw_slot_hash(&slot_hashes_data, draw_slot)?;
+    let seed = hashv(&[draw_slot_hash, ido_campaign.key().as_ref()]).to_bytes();
+    drop(slot_hashes_data);
+
+    let lottery = &mut ctx.accounts.lottery;
+    lottery.seed = seed;
+    draw_winners(lottery);
+    lottery.drawn = true;
+
+    Ok(())
+}
+
+/// `SlotHashes` is a u64 entry count, then (slot, hash) pairs from the most recent slot
+/// down. Returns the hash of the oldest entry at or after `draw_slot`, as long as the
+/// entries reach back far enough to tell that no earlier block was dropped.
+fn find_draw_slot_hash(slot_hashes_data: &[u8], draw_slot: u64) -> Result<&[u8]> {
+    let count = slot_hashes_data
+        .get(..8)
+        .map(|bytes| u64::from_le_bytes(bytes.try_into().unwrap()))
+        .ok_or(IdoError::ErrInvalidAccountData)?;
+
+    let mut draw_slot_hash = None;
+    for index in 0..count as usize {
+        let entry = slot_hashes_data
+            .get(8 + index * 40..8 + (index + 1) * 40)
+            .ok_or(IdoError::ErrInvalidAccountData)?;
+        let entry_slot = u64::from_le_bytes(entry[..8].try_into().unwrap());
+        if entry_slot < draw_slot {
+            return draw_slot_hash.ok_or(error!(IdoError::ErrLotteryDrawSlotNotReached));
+        }
+
+        draw_slot_hash = Some(&entry[8..]);
+        if entry_slot == draw_slot {
+            return Ok(&entry[8..]);
+        }
+    }
+
+    err!(IdoError::ErrLotteryDrawSlotExpired)
+}
+
+/// Selection sampling: walks the tickets once and picks each with probability
+/// needed / remaining, which yields exactly `winners_count` distinct winners.
+fn draw_winners(lottery: &mut Lottery) {
+    let mut state = u64::from_le_bytes(lottery.seed[..8].try_into().unwrap()) | 1;
+    let mut needed = lottery.winners_count.min(lottery.registrant_count);
+    let mut remaining = lottery.registrant_count;
+
+    for index in 0..lottery.registrant_count {
+        if needed == 0 {
+            break;
+        }
+
+        // xorshift64
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+
+        if state % remaining < needed {
+            lottery.set_winner(index);
+            needed -= 1;
+        }
+        remaining -= 1;
+    }
+}
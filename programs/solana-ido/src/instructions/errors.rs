@@ -88,4 +88,162 @@ pub enum IdoError {
     ErrSoftCapReached,
     #[msg("Nothing to withdraw")]
     ErrNothingToWithdraw,
-}
\ No newline at end of file
+    #[msg("Invalid campaign status transition")]
+    ErrInvalidStatusTransition,
+    #[msg("Account already migrated")]
+    ErrAccountAlreadyMigrated,
+    #[msg("Invalid account data")]
+    ErrInvalidAccountData,
+    #[msg("Invalid campaign metadata")]
+    ErrInvalidMetadata,
+    #[msg("Sale already started")]
+    ErrSaleAlreadyStarted,
+    #[msg("Invalid sale mode")]
+    ErrInvalidSaleMode,
+    #[msg("Sale not settled")]
+    ErrSaleNotSettled,
+    #[msg("Invalid price decay")]
+    ErrInvalidPriceDecay,
+    #[msg("Invalid bonding curve")]
+    ErrInvalidBondingCurve,
+    #[msg("Invalid bid price")]
+    ErrInvalidBidPrice,
+    #[msg("Invalid reveal period")]
+    ErrInvalidRevealPeriod,
+    #[msg("Bid does not match commitment")]
+    ErrInvalidBidCommitment,
+    #[msg("Bid already revealed")]
+    ErrBidAlreadyRevealed,
+    #[msg("Auction already settled")]
+    ErrAuctionAlreadySettled,
+    #[msg("Auction not settled")]
+    ErrAuctionNotSettled,
+    #[msg("Stake is locked")]
+    ErrStakeLocked,
+    #[msg("Insufficient stake")]
+    ErrInsufficientStake,
+    #[msg("Invalid stake tiers")]
+    ErrInvalidStakeTiers,
+    #[msg("Stake tiers required")]
+    ErrStakeTiersRequired,
+    #[msg("Stake too low for any tier")]
+    ErrStakeTooLow,
+    #[msg("Invalid stake mint")]
+    ErrInvalidStakeMint,
+    #[msg("Invalid lottery parameters")]
+    ErrInvalidLotteryParameters,
+    #[msg("Lottery registration closed")]
+    ErrLotteryRegistrationClosed,
+    #[msg("Lottery is full")]
+    ErrLotteryFull,
+    #[msg("Lottery already drawn")]
+    ErrLotteryAlreadyDrawn,
+    #[msg("Lottery not drawn")]
+    ErrLotteryNotDrawn,
+    #[msg("Lottery required")]
+    ErrLotteryRequired,
+    #[msg("Not a lottery winner")]
+    ErrNotLotteryWinner,
+    #[msg("Lottery ticket still needed")]
+    ErrLotteryTicketStillNeeded,
+    #[msg("KYC ticket required")]
+    ErrKycTicketRequired,
+    #[msg("Invalid KYC ticket")]
+    ErrInvalidKycTicket,
+    #[msg("KYC ticket expired")]
+    ErrKycTicketExpired,
+    #[msg("Invalid anti-bot parameters")]
+    ErrInvalidAntiBotParameters,
+    #[msg("Instructions sysvar required")]
+    ErrInstructionsSysvarRequired,
+    #[msg("Join via CPI not allowed")]
+    ErrCpiNotAllowed,
+    #[msg("Transaction contains foreign instructions")]
+    ErrForeignInstruction,
+    #[msg("Join throttle required")]
+    ErrJoinThrottleRequired,
+    #[msg("Too many joins in this slot")]
+    ErrTooManyJoinsInSlot,
+    #[msg("Invalid access list batch")]
+    ErrInvalidAccessListBatch,
+    #[msg("Participant not allowlisted")]
+    ErrNotAllowlisted,
+    #[msg("Participant is blocked")]
+    ErrParticipantBlocked,
+    #[msg("Invalid token gate")]
+    ErrInvalidTokenGate,
+    #[msg("Gate token account required")]
+    ErrGateTokenAccountRequired,
+    #[msg("Insufficient gate token balance")]
+    ErrInsufficientGateBalance,
+    #[msg("Invalid escrow tranches")]
+    ErrInvalidEscrowTranches,
+    #[msg("Milestone escrow required")]
+    ErrMilestoneEscrowRequired,
+    #[msg("Invalid tranche")]
+    ErrInvalidTranche,
+    #[msg("Tranche is locked")]
+    ErrTrancheLocked,
+    #[msg("Tranche already settled")]
+    ErrTrancheAlreadySettled,
+    #[msg("Escrow already failed")]
+    ErrEscrowFailed,
+    #[msg("Funds not withdrawn")]
+    ErrFundsNotWithdrawn,
+    #[msg("Invalid voting parameters")]
+    ErrInvalidVotingParameters,
+    #[msg("Milestones are decided by vote")]
+    ErrMilestoneVotingEnabled,
+    #[msg("Voting is not open")]
+    ErrVotingClosed,
+    #[msg("Voting has not ended")]
+    ErrVotingNotEnded,
+    #[msg("Proposal already executed")]
+    ErrProposalAlreadyExecuted,
+    #[msg("No voting weight")]
+    ErrNoVotingWeight,
+    #[msg("Invalid referral parameters")]
+    ErrInvalidReferralParameters,
+    #[msg("Referral program is disabled")]
+    ErrReferralsDisabled,
+    #[msg("Self referral is not allowed")]
+    ErrSelfReferral,
+    #[msg("Invalid referrer")]
+    ErrInvalidReferrer,
+    #[msg("Invalid token lock")]
+    ErrInvalidTokenLock,
+    #[msg("Token lock is not revocable")]
+    ErrTokenLockNotRevocable,
+    #[msg("Token lock already revoked")]
+    ErrTokenLockRevoked,
+    #[msg("Position is not revocable")]
+    ErrPositionNotRevocable,
+    #[msg("Position already revoked")]
+    ErrPositionRevoked,
+    #[msg("Invalid claim deadline")]
+    ErrInvalidClaimDeadline,
+    #[msg("Claim deadline passed")]
+    ErrClaimDeadlinePassed,
+    #[msg("Claim deadline not reached")]
+    ErrClaimDeadlineNotReached,
+    #[msg("Invalid merkle proof")]
+    ErrInvalidMerkleProof,
+    #[msg("Mint cap exceeded")]
+    ErrMintCapExceeded,
+    #[msg("Invalid mint authority")]
+    ErrInvalidMintAuthority,
+    #[msg("Mint on claim already enabled")]
+    ErrMintOnClaimEnabled,
+    #[msg("Mint on claim disabled")]
+    ErrMintOnClaimDisabled,
+    #[msg("Gate mint not listed")]
+    ErrGateMintNotListed,
+    #[msg("Lottery draw not scheduled")]
+    ErrLotteryDrawNotScheduled,
+    #[msg("Lottery draw slot not reached")]
+    ErrLotteryDrawSlotNotReached,
+    #[msg("Lottery draw slot expired")]
+    ErrLotteryDrawSlotExpired,
+    #[msg("Lottery draw already scheduled")]
+    ErrLotteryDrawScheduled,
+}
@@ -0,0 +1,77 @@
+use {
+    crate::{
+        instructions::{mark_tranche_released, pay_from_sol_treasury, refund_tranche, IdoError},
+        state::{IdoCampaign, MilestoneEscrow, MilestoneProposal, TrancheState},
+    },
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct ExecuteMilestoneProposal<'info> {
+    /// CHECK: Seed of the ido_campaign PDA, receives the tranche when the proposal passes
+    #[account(mut)]
+    pub ido_campaign_owner: AccountInfo<'info>,
+
+    #[account(
+        seeds = [b"ido_campaign", ido_campaign_owner.key().as_ref()], bump,
+    )]
+    pub ido_campaign: Account<'info, IdoCampaign>,
+
+    #[account(
+        mut,
+        seeds = [b"milestone_escrow", ido_campaign.key().as_ref()], bump,
+    )]
+    pub milestone_escrow: Account<'info, MilestoneEscrow>,
+
+    #[account(
+        mut,
+        constraint = milestone_proposal.ido_campaign == ido_campaign.key() @ IdoError::ErrInvalidIdoCampaign,
+    )]
+    pub milestone_proposal: Account<'info, MilestoneProposal>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_treasury", ido_campaign.key().as_ref()], bump,
+        constraint = sol_treasury.key() == ido_campaign.sol_treasury @ IdoError::ErrInvalidSolTreasury,
+    )]
+    pub sol_treasury: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Permissionless once voting ended: releases the tranche to the owner when the proposal
+/// passed, otherwise makes it refundable to the participants.
+pub fn execute_milestone_proposal(ctx: Context<ExecuteMilestoneProposal>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp as u64;
+
+    let milestone_proposal = &mut ctx.accounts.milestone_proposal;
+    require!(!milestone_proposal.executed, IdoError::ErrProposalAlreadyExecuted);
+    require!(now > milestone_proposal.voting_end, IdoError::ErrVotingNotEnded);
+    milestone_proposal.executed = true;
+
+    let tranche_index = milestone_proposal.tranche_index;
+    let milestone_escrow = &mut ctx.accounts.milestone_escrow;
+    require!(
+        milestone_escrow.tranche(tranche_index)?.state == TrancheState::Locked,
+        IdoError::ErrTrancheAlreadySettled
+    );
+
+    let passed = milestone_proposal.passed(
+        ctx.accounts.ido_campaign.total_sold,
+        milestone_escrow.quorum_bps,
+        milestone_escrow.approval_threshold_bps,
+    );
+    if !passed {
+        return refund_tranche(milestone_escrow, tranche_index);
+    }
+
+    let amount = mark_tranche_released(milestone_escrow, tranche_index)?;
+    pay_from_sol_treasury(
+        &ctx.accounts.sol_treasury,
+        &ctx.accounts.ido_campaign_owner,
+        ctx.accounts.ido_campaign.key(),
+        ctx.bumps.sol_treasury,
+        &ctx.accounts.system_program,
+        amount,
+    )
+}
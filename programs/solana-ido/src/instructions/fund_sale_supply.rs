@@ -0,0 +1,110 @@
+use {
+    crate::{
+        instructions::IdoError,
+        state::{CampaignStatus, IdoCampaign, RegistryEntry, SupplyDeposit, SUPPLY_DEPOSIT_VERSION},
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::token::{self, Mint, Token, TokenAccount, TransferChecked},
+};
+
+#[derive(Accounts)]
+pub struct FundSaleSupply<'info> {
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = funder_token_account.owner == funder.key() @ IdoError::ErrInvalidOwner,
+        constraint = funder_token_account.mint == ido_campaign.token_mint @ IdoError::ErrInvalidOwnerTokenAccount,
+    )]
+    pub funder_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: This account is used only as a seed to derive the ido_campaign PDA
+    pub ido_campaign_owner: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"ido_campaign", ido_campaign_owner.key().as_ref()], bump,
+    )]
+    pub ido_campaign: Account<'info, IdoCampaign>,
+
+    #[account(
+        mut,
+        seeds = [b"tokens_treasury", ido_campaign.key().as_ref()], bump,
+        constraint = tokens_treasury.key() == ido_campaign.token_treasury @ IdoError::ErrInvalidIdoCampaign,
+    )]
+    pub tokens_treasury: Account<'info, TokenAccount>,
+
+    #[account(constraint = token_mint.key() == ido_campaign.token_mint @ IdoError::ErrInvalidMintAccount)]
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = funder,
+        space = 8 + SupplyDeposit::INIT_SPACE,
+        seeds = [b"supply_deposit", ido_campaign.key().as_ref(), funder.key().as_ref()], bump,
+    )]
+    pub supply_deposit: Account<'info, SupplyDeposit>,
+
+    /// Gets the new status mirrored, missing for campaigns created before the registry.
+    #[account(
+        mut,
+        constraint = registry_entry.ido_campaign == ido_campaign.key() @ IdoError::ErrInvalidIdoCampaign,
+    )]
+    pub registry_entry: Option<Account<'info, RegistryEntry>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Deposits part of the sale supply on behalf of the authority, the campaign is funded
+/// by the deposit that completes `hard_cap`.
+pub fn fund_sale_supply(ctx: Context<FundSaleSupply>, amount: u64) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp as u64;
+    let ido_campaign = &mut ctx.accounts.ido_campaign;
+
+    require!(!ido_campaign.status.is_closed(), IdoError::ErrSaleAlreadyClosed);
+    require!(ido_campaign.status == CampaignStatus::Created, IdoError::ErrTokenSupplyAlreadyDeposited);
+    require!(!ido_campaign.mint_on_claim, IdoError::ErrMintOnClaimEnabled);
+    require!(ctx.accounts.token_mint.decimals == 0, IdoError::ErrInvalidTokenDecimals);
+    require!(
+        amount > 0 && amount <= ido_campaign.remaining_supply(),
+        IdoError::ErrInvalidBalanceOfTokensToDeposit
+    );
+
+    let supply_deposit = &mut ctx.accounts.supply_deposit;
+    supply_deposit.version = SUPPLY_DEPOSIT_VERSION;
+    supply_deposit.ido_campaign = ido_campaign.key();
+    supply_deposit.funder = ctx.accounts.funder.key();
+    supply_deposit.amount = supply_deposit.amount
+        .checked_add(amount)
+        .ok_or(IdoError::ErrMathOverflow)?;
+
+    ido_campaign.total_deposited = ido_campaign.total_deposited
+        .checked_add(amount)
+        .ok_or(IdoError::ErrMathOverflow)?;
+    ido_campaign.funder_deposits = ido_campaign.funder_deposits
+        .checked_add(amount)
+        .ok_or(IdoError::ErrMathOverflow)?;
+
+    let cpi_context = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        TransferChecked {
+            from: ctx.accounts.funder_token_account.to_account_info(),
+            to: ctx.accounts.tokens_treasury.to_account_info(),
+            authority: ctx.accounts.funder.to_account_info(),
+            mint: ctx.accounts.token_mint.to_account_info(),
+        },
+    );
+    token::transfer_checked(cpi_context, amount, ctx.accounts.token_mint.decimals)?;
+
+    if ido_campaign.remaining_supply() == 0 {
+        ido_campaign.transition(CampaignStatus::Funded)?;
+        ido_campaign.sync_status(now)?;
+        if let Some(registry_entry) = ctx.accounts.registry_entry.as_mut() {
+            registry_entry.record_status(ido_campaign);
+        }
+    }
+
+    Ok(())
+}
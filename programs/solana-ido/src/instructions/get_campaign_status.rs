@@ -0,0 +1,51 @@
+use {
+    crate::state::{CampaignStatus, IdoCampaign, SaleMode},
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct GetCampaignStatus<'info> {
+    /// CHECK: This account is used only as a seed to derive the ido_campaign PDA
+    pub ido_campaign_owner: AccountInfo<'info>,
+
+    #[account(
+        seeds = [b"ido_campaign", ido_campaign_owner.key().as_ref()], bump,
+    )]
+    pub ido_campaign: Account<'info, IdoCampaign>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CampaignStatusView {
+    pub now: u64,
+    pub status: CampaignStatus,
+    pub sale_active: bool,
+    pub sale_ended: bool,
+    pub soft_cap_reached: bool,
+    pub refund_available: bool,
+    pub funds_withdrawable: bool,
+    pub total_sold: u64,
+    pub remaining_tokens: u64,
+    pub current_price_lamports: u64,
+}
+
+pub fn get_campaign_status(ctx: Context<GetCampaignStatus>) -> Result<CampaignStatusView> {
+    let now = Clock::get()?.unix_timestamp as u64;
+
+    // Project the clock-driven transitions without persisting them.
+    let mut ido_campaign = (*ctx.accounts.ido_campaign).clone();
+    let status = ido_campaign.sync_status(now)?;
+
+    Ok(CampaignStatusView {
+        now,
+        status,
+        sale_active: status == CampaignStatus::Live
+            && (ido_campaign.sale_mode == SaleMode::ProRata || ido_campaign.total_sold < ido_campaign.hard_cap),
+        sale_ended: now > ido_campaign.end_sale_time,
+        soft_cap_reached: ido_campaign.total_sold >= ido_campaign.soft_cap,
+        refund_available: status.is_closed(),
+        funds_withdrawable: status == CampaignStatus::Succeeded && !ido_campaign.funds_withdrawn,
+        total_sold: ido_campaign.total_sold,
+        remaining_tokens: ido_campaign.hard_cap.saturating_sub(ido_campaign.total_sold),
+        current_price_lamports: ido_campaign.current_price(now)?,
+    })
+}
@@ -0,0 +1,53 @@
+use {
+    crate::{instructions::{calculate_amount_to_claim, IdoError}, state::{CampaignStatus, IdoCampaign, User}},
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct GetClaimable<'info> {
+    /// CHECK: This account is used only as a seed to derive the user PDA
+    pub participant: AccountInfo<'info>,
+
+    /// CHECK: This account is used only as a seed to derive the ido_campaign PDA
+    pub ido_campaign_owner: AccountInfo<'info>,
+
+    #[account(
+        seeds = [b"ido_campaign", ido_campaign_owner.key().as_ref()], bump,
+    )]
+    pub ido_campaign: Account<'info, IdoCampaign>,
+
+    #[account(
+        seeds = [b"user", ido_campaign.key().as_ref(), participant.key().as_ref()], bump,
+        constraint = user.ido_campaign == ido_campaign.key() @ IdoError::ErrInvalidIdoCampaign,
+    )]
+    pub user: Account<'info, User>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ClaimableAmount {
+    pub claimable_now: u64,
+    pub claimed: u64,
+    pub total: u64,
+    pub refundable_lamports: u64,
+}
+
+pub fn get_claimable(ctx: Context<GetClaimable>) -> Result<ClaimableAmount> {
+    let now = Clock::get()?.unix_timestamp as u64;
+    let mut user = (*ctx.accounts.user).clone();
+
+    let mut ido_campaign = (*ctx.accounts.ido_campaign).clone();
+    let claimable_now = match ido_campaign.sync_status(now)? {
+        CampaignStatus::Succeeded | CampaignStatus::Finalized => {
+            user.settle(&mut ido_campaign)?;
+            calculate_amount_to_claim(&user, &ido_campaign)?
+        }
+        _ => 0,
+    };
+
+    Ok(ClaimableAmount {
+        claimable_now,
+        claimed: user.claimed,
+        total: user.amount,
+        refundable_lamports: user.refundable_lamports,
+    })
+}
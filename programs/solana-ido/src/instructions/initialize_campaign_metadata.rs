@@ -0,0 +1,61 @@
+use {
+    crate::{
+        instructions::IdoError,
+        state::{CampaignMetadata, IdoCampaign, CAMPAIGN_METADATA_VERSION},
+    },
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct InitializeCampaignMetadata<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"ido_campaign", owner.key().as_ref()], bump,
+        constraint = ido_campaign.authority == owner.key() @ IdoError::ErrUnauthorized,
+    )]
+    pub ido_campaign: Account<'info, IdoCampaign>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + CampaignMetadata::INIT_SPACE,
+        seeds = [b"campaign_metadata", ido_campaign.key().as_ref()], bump,
+    )]
+    pub campaign_metadata: Account<'info, CampaignMetadata>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Campaigns get their metadata in `initialize_sale`, this only fills it in for campaigns
+/// created before that, so they can still be listed with project info.
+pub fn initialize_campaign_metadata(
+    ctx: Context<InitializeCampaignMetadata>,
+    name: String,
+    symbol: String,
+    uri: String,
+    description: String,
+    social_links: Vec<String>,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp as u64;
+    check_metadata_editable(&mut ctx.accounts.ido_campaign, now)?;
+
+    let campaign_metadata = &mut ctx.accounts.campaign_metadata;
+    campaign_metadata.version = CAMPAIGN_METADATA_VERSION;
+    campaign_metadata.ido_campaign = ctx.accounts.ido_campaign.key();
+    campaign_metadata.set(name, symbol, uri, description, social_links, now)?;
+
+    Ok(())
+}
+
+/// Metadata is frozen once buyers can act on it.
+pub(crate) fn check_metadata_editable(ido_campaign: &mut IdoCampaign, now: u64) -> Result<()> {
+    ido_campaign.sync_status(now)?;
+
+    require!(!ido_campaign.status.is_closed(), IdoError::ErrSaleAlreadyClosed);
+    require!(now < ido_campaign.start_sale_time, IdoError::ErrSaleAlreadyStarted);
+
+    Ok(())
+}
@@ -0,0 +1,28 @@
+use {
+    crate::state::{CampaignRegistry, CAMPAIGN_REGISTRY_VERSION},
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct InitializeCampaignRegistry<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + CampaignRegistry::INIT_SPACE,
+        seeds = [b"campaign_registry"], bump,
+    )]
+    pub campaign_registry: Account<'info, CampaignRegistry>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_campaign_registry(ctx: Context<InitializeCampaignRegistry>) -> Result<()> {
+    let campaign_registry = &mut ctx.accounts.campaign_registry;
+    campaign_registry.version = CAMPAIGN_REGISTRY_VERSION;
+    campaign_registry.campaign_count = 0;
+
+    Ok(())
+}
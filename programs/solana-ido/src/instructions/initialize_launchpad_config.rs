@@ -0,0 +1,50 @@
+use {
+    crate::{instructions::IdoError, program::SolanaIdo, state::{LaunchpadConfig, LAUNCHPAD_CONFIG_VERSION}},
+    anchor_lang::prelude::*,
+    anchor_spl::token::{Mint, Token, TokenAccount},
+};
+
+#[derive(Accounts)]
+pub struct InitializeLaunchpadConfig<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + LaunchpadConfig::INIT_SPACE,
+        seeds = [b"launchpad_config"], bump,
+    )]
+    pub launchpad_config: Account<'info, LaunchpadConfig>,
+
+    pub stake_mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = admin,
+        token::mint = stake_mint,
+        token::authority = launchpad_config,
+        seeds = [b"stake_vault"], bump,
+    )]
+    pub stake_vault: Account<'info, TokenAccount>,
+
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()) @ IdoError::ErrUnauthorized)]
+    pub program: Program<'info, SolanaIdo>,
+
+    #[account(constraint = program_data.upgrade_authority_address == Some(admin.key()) @ IdoError::ErrUnauthorized)]
+    pub program_data: Account<'info, ProgramData>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_launchpad_config(ctx: Context<InitializeLaunchpadConfig>, stake_lock_period: u64) -> Result<()> {
+    let launchpad_config = &mut ctx.accounts.launchpad_config;
+    launchpad_config.version = LAUNCHPAD_CONFIG_VERSION;
+    launchpad_config.admin = ctx.accounts.admin.key();
+    launchpad_config.stake_mint = ctx.accounts.stake_mint.key();
+    launchpad_config.stake_vault = ctx.accounts.stake_vault.key();
+    launchpad_config.stake_lock_period = stake_lock_period;
+
+    Ok(())
+}
@@ -1,8 +1,25 @@
 use {
-    crate::{instructions::IdoError, state::{IdoCampaign, User}}, 
-    anchor_lang::{prelude::*, system_program::{self, Transfer}},
+    crate::{instructions::{supports_referrals, IdoError}, state::{
+        AllowlistEntry, CampaignStatus, GateListEntry, IdoCampaign, JoinThrottle, JOIN_THROTTLE_VERSION, KycTicket, Lottery, LotteryTicket, Referral,
+        SaleMode, StakeAccount, StakeTiers, User, USER_VERSION,
+    }}, 
+    anchor_lang::{
+        prelude::*,
+        solana_program::{
+            ed25519_program,
+            instruction::{get_stack_height, TRANSACTION_LEVEL_STACK_HEIGHT},
+            pubkey,
+            sysvar::instructions::{
+                self as instructions_sysvar, load_current_index_checked, load_instruction_at_checked,
+            },
+        },
+        system_program::{self, Transfer},
+    },
+    anchor_spl::token::TokenAccount,
 };
 
+const COMPUTE_BUDGET_PROGRAM_ID: Pubkey = pubkey!("ComputeBudget111111111111111111111111111111");
+
 #[derive(Accounts)]
 pub struct JoinIdo<'info> {
     #[account(mut)]
@@ -26,15 +43,78 @@ pub struct JoinIdo<'info> {
     #[account(
         init_if_needed,
         payer = participant,
-        space = 8 + std::mem::size_of::<User>(),
+        space = 8 + User::INIT_SPACE,
         seeds = [b"user", ido_campaign.key().as_ref(), participant.key().as_ref()], bump,
     )]
     pub user: Account<'info, User>,
 
+    /// Only read when the campaign is stake tiered, missing means nothing staked.
+    #[account(constraint = stake_account.owner == participant.key() @ IdoError::ErrInvalidOwner)]
+    pub stake_account: Option<Account<'info, StakeAccount>>,
+
+    #[account(constraint = stake_tiers.ido_campaign == ido_campaign.key() @ IdoError::ErrInvalidIdoCampaign)]
+    pub stake_tiers: Option<Account<'info, StakeTiers>>,
+
+    #[account(constraint = lottery.ido_campaign == ido_campaign.key() @ IdoError::ErrInvalidIdoCampaign)]
+    pub lottery: Option<Account<'info, Lottery>>,
+
+    /// Only read during the lottery guaranteed window.
+    #[account(
+        constraint = lottery_ticket.ido_campaign == ido_campaign.key() @ IdoError::ErrInvalidIdoCampaign,
+        constraint = lottery_ticket.participant == participant.key() @ IdoError::ErrInvalidOwner,
+    )]
+    pub lottery_ticket: Option<Account<'info, LotteryTicket>>,
+
+    /// CHECK: Instructions sysvar, required when the campaign has a KYC signer or rejects foreign instructions
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions_sysvar: Option<UncheckedAccount<'info>>,
+
+    /// Required when the campaign limits joins per slot, created on the wallet's first
+    /// throttled join. A wallet joins a campaign once, so the limit only ever counts the
+    /// joins it made into other campaigns in the same slot.
+    #[account(
+        init_if_needed,
+        payer = participant,
+        space = 8 + JoinThrottle::INIT_SPACE,
+        seeds = [b"join_throttle", participant.key().as_ref()], bump,
+    )]
+    pub join_throttle: Option<Account<'info, JoinThrottle>>,
+
+    /// Required when the campaign has an allowlist.
+    #[account(
+        constraint = allowlist_entry.ido_campaign == ido_campaign.key() @ IdoError::ErrInvalidIdoCampaign,
+        constraint = allowlist_entry.participant == participant.key() @ IdoError::ErrInvalidOwner,
+    )]
+    pub allowlist_entry: Option<Account<'info, AllowlistEntry>>,
+
+    /// CHECK: Only checked to be empty, an initialized account means the participant is blocked
+    #[account(
+        seeds = [b"blocklist", participant.key().as_ref()], bump,
+    )]
+    pub blocklist_entry: UncheckedAccount<'info>,
+
+    /// Required when the campaign is token gated.
+    #[account(constraint = gate_token_account.owner == participant.key() @ IdoError::ErrInvalidOwner)]
+    pub gate_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// Required when the campaign is gated by a mint list, the entry of `gate_token_account.mint`.
+    #[account(constraint = gate_list_entry.ido_campaign == ido_campaign.key() @ IdoError::ErrInvalidIdoCampaign)]
+    pub gate_list_entry: Option<Account<'info, GateListEntry>>,
+
+    /// Set when the participant was referred, created by `register_referrer`.
+    #[account(
+        mut,
+        constraint = referral.ido_campaign == ido_campaign.key() @ IdoError::ErrInvalidIdoCampaign,
+    )]
+    pub referral: Option<Account<'info, Referral>>,
+
     pub system_program: Program<'info, System>,
 }
 
 pub fn join_ido(ctx: Context<JoinIdo>, number_of_allocations: u64) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp as u64;
+    ctx.accounts.ido_campaign.sync_status(now)?;
+
     let ido_campaign = &ctx.accounts.ido_campaign;
     let participant = &ctx.accounts.participant;
 
@@ -45,15 +125,64 @@ pub fn join_ido(ctx: Context<JoinIdo>, number_of_allocations: u64) -> Result<()>
         IdoError::ErrUserAlreadyJoined
     );
 
-    let amount_to_buy = number_of_allocations
-        .checked_mul(ido_campaign.allocation)
-        .ok_or(IdoError::ErrMathOverflow)?;
+    let (amount_to_buy, total_cost_lamports) = calculate_purchase(ido_campaign, number_of_allocations, now)?;
 
-    let total_cost_lamports = amount_to_buy
-        .checked_mul(ido_campaign.price_lamports)
-        .ok_or(IdoError::ErrMathOverflow)?;
+    check_anti_bot(
+        ido_campaign,
+        ctx.accounts.instructions_sysvar.as_ref(),
+        ctx.accounts.join_throttle.as_deref_mut(),
+        participant.key(),
+    )?;
+
+    require!(ctx.accounts.blocklist_entry.data_is_empty(), IdoError::ErrParticipantBlocked);
 
-    check_campaign(ido_campaign, participant, number_of_allocations, total_cost_lamports, amount_to_buy)?;
+    check_lottery(
+        ido_campaign,
+        ctx.accounts.lottery.as_deref(),
+        ctx.accounts.lottery_ticket.as_deref(),
+        now,
+    )?;
+
+    let max_allocations = max_allocations_per_participant(
+        ido_campaign,
+        ctx.accounts.stake_account.as_deref(),
+        ctx.accounts.stake_tiers.as_deref(),
+    )?;
+    let max_allocations = check_allowlist(
+        ido_campaign,
+        ctx.accounts.allowlist_entry.as_deref(),
+        max_allocations,
+    )?;
+    let max_allocations = check_token_gate(
+        ido_campaign,
+        ctx.accounts.gate_token_account.as_deref(),
+        ctx.accounts.gate_list_entry.as_deref(),
+        max_allocations,
+    )?;
+    let max_allocations = check_kyc(
+        ido_campaign,
+        participant.key(),
+        ctx.accounts.instructions_sysvar.as_ref(),
+        max_allocations,
+        now,
+    )?
+    .min(ido_campaign.ramp_up_allocations(now));
+
+    check_campaign(
+        ido_campaign,
+        participant,
+        number_of_allocations,
+        max_allocations,
+        total_cost_lamports,
+        amount_to_buy,
+    )?;
+
+    let referral_reward = check_referral(
+        ido_campaign,
+        ctx.accounts.referral.as_deref(),
+        participant.key(),
+        total_cost_lamports,
+    )?;
 
     let cpi_context = CpiContext::new(
         ctx.accounts.system_program.to_account_info(),
@@ -64,8 +193,7 @@ pub fn join_ido(ctx: Context<JoinIdo>, number_of_allocations: u64) -> Result<()>
     );
     system_program::transfer(cpi_context, total_cost_lamports)?;
 
-    let now = Clock::get()?.unix_timestamp as u64;
-
+    ctx.accounts.user.version = USER_VERSION;
     ctx.accounts.user.joined_at = now;
     ctx.accounts.user.ido_campaign = ido_campaign.key();
     ctx.accounts.user.participant = participant.key();
@@ -73,53 +201,287 @@ pub fn join_ido(ctx: Context<JoinIdo>, number_of_allocations: u64) -> Result<()>
     ctx.accounts.user.paid_lamports = total_cost_lamports;
     ctx.accounts.user.claimed = 0;
 
-    ctx.accounts.ido_campaign.total_sold = ctx.accounts.ido_campaign.total_sold
-        .checked_add(amount_to_buy)
-        .ok_or(IdoError::ErrMathOverflow)?;
+    record_purchase(&mut ctx.accounts.ido_campaign, amount_to_buy, now)?;
     ctx.accounts.ido_campaign.total_participants += 1;
+
+    if let Some(referral) = ctx.accounts.referral.as_mut() {
+        referral.record(total_cost_lamports, referral_reward)?;
+        ctx.accounts.ido_campaign.referral_rewards_lamports = ctx.accounts.ido_campaign.referral_rewards_lamports
+            .checked_add(referral_reward)
+            .ok_or(IdoError::ErrMathOverflow)?;
+    }
     
     Ok(())
 }
 
+pub(crate) fn calculate_purchase(
+    ido_campaign: &IdoCampaign,
+    number_of_allocations: u64,
+    now: u64,
+) -> Result<(u64, u64)> {
+    let amount_to_buy = number_of_allocations
+        .checked_mul(ido_campaign.allocation)
+        .ok_or(IdoError::ErrMathOverflow)?;
+
+    let total_cost_lamports = match ido_campaign.sale_mode {
+        SaleMode::BondingCurve => ido_campaign.bonding_curve.cost(
+            ido_campaign.price_lamports,
+            ido_campaign.total_sold,
+            amount_to_buy,
+        )?,
+        _ => amount_to_buy
+            .checked_mul(ido_campaign.current_price(now)?)
+            .ok_or(IdoError::ErrMathOverflow)?,
+    };
+
+    Ok((amount_to_buy, total_cost_lamports))
+}
+
+fn record_purchase(ido_campaign: &mut IdoCampaign, amount_to_buy: u64, now: u64) -> Result<()> {
+    ido_campaign.total_committed = ido_campaign.total_committed
+        .checked_add(amount_to_buy)
+        .ok_or(IdoError::ErrMathOverflow)?;
+
+    if ido_campaign.sale_mode == SaleMode::DutchAuction {
+        ido_campaign.clearing_price_lamports = ido_campaign.current_price(now)?;
+    }
+
+    ido_campaign.total_sold = match ido_campaign.sale_mode {
+        // Every participant is scaled down after the sale, so at most hard_cap is ever sold.
+        SaleMode::ProRata => ido_campaign.total_committed.min(ido_campaign.hard_cap),
+        _ => ido_campaign.total_sold
+            .checked_add(amount_to_buy)
+            .ok_or(IdoError::ErrMathOverflow)?,
+    };
+
+    Ok(())
+}
+
+/// Returns the reward owed to the referrer, 0 when the participant was not referred.
+fn check_referral(
+    ido_campaign: &IdoCampaign,
+    referral: Option<&Referral>,
+    participant: Pubkey,
+    total_cost_lamports: u64,
+) -> Result<u64> {
+    let Some(referral) = referral else {
+        return Ok(0);
+    };
+
+    require!(ido_campaign.referral_bps > 0, IdoError::ErrReferralsDisabled);
+    require!(supports_referrals(ido_campaign), IdoError::ErrInvalidSaleMode);
+    require!(referral.referrer != participant, IdoError::ErrSelfReferral);
+
+    Ok(ido_campaign.referral_reward(total_cost_lamports))
+}
+
+fn check_anti_bot(
+    ido_campaign: &IdoCampaign,
+    instructions_sysvar: Option<&UncheckedAccount>,
+    join_throttle: Option<&mut JoinThrottle>,
+    participant: Pubkey,
+) -> Result<()> {
+    if ido_campaign.reject_foreign_instructions {
+        require!(
+            get_stack_height() == TRANSACTION_LEVEL_STACK_HEIGHT,
+            IdoError::ErrCpiNotAllowed
+        );
+
+        let instructions_sysvar = instructions_sysvar.ok_or(IdoError::ErrInstructionsSysvarRequired)?;
+        // The sysvar data starts with the number of instructions in the transaction.
+        let instructions_count = {
+            let data = instructions_sysvar.try_borrow_data()?;
+            u16::from_le_bytes([data[0], data[1]])
+        };
+        for index in 0..instructions_count {
+            let program_id = load_instruction_at_checked(index as usize, instructions_sysvar)?.program_id;
+            require!(
+                program_id == crate::ID || program_id == ed25519_program::ID || program_id == COMPUTE_BUDGET_PROGRAM_ID,
+                IdoError::ErrForeignInstruction
+            );
+        }
+    }
+
+    // Recorded whenever passed so a later campaign with a limit sees earlier joins in the slot.
+    if let Some(join_throttle) = join_throttle {
+        if join_throttle.version == 0 {
+            join_throttle.version = JOIN_THROTTLE_VERSION;
+            join_throttle.participant = participant;
+        }
+        let joins_in_slot = join_throttle.record_join(Clock::get()?.slot);
+
+        if ido_campaign.max_joins_per_slot > 0 {
+            require!(
+                joins_in_slot <= ido_campaign.max_joins_per_slot,
+                IdoError::ErrTooManyJoinsInSlot
+            );
+        }
+    } else {
+        require!(ido_campaign.max_joins_per_slot == 0, IdoError::ErrJoinThrottleRequired);
+    }
+
+    Ok(())
+}
+
+fn check_lottery(
+    ido_campaign: &IdoCampaign,
+    lottery: Option<&Lottery>,
+    lottery_ticket: Option<&LotteryTicket>,
+    now: u64,
+) -> Result<()> {
+    if !ido_campaign.lottery_enabled {
+        return Ok(());
+    }
+
+    let lottery = lottery.ok_or(IdoError::ErrLotteryRequired)?;
+    require!(lottery.drawn, IdoError::ErrLotteryNotDrawn);
+
+    if now > lottery.guaranteed_window_end {
+        return Ok(());
+    }
+
+    let lottery_ticket = lottery_ticket.ok_or(IdoError::ErrNotLotteryWinner)?;
+    require!(lottery.is_winner(lottery_ticket.index), IdoError::ErrNotLotteryWinner);
+
+    Ok(())
+}
+
+fn check_allowlist(
+    ido_campaign: &IdoCampaign,
+    allowlist_entry: Option<&AllowlistEntry>,
+    max_allocations: u64,
+) -> Result<u64> {
+    if !ido_campaign.allowlist_enabled {
+        return Ok(max_allocations);
+    }
+
+    let allowlist_entry = allowlist_entry.ok_or(IdoError::ErrNotAllowlisted)?;
+    if allowlist_entry.max_allocations == 0 {
+        return Ok(max_allocations);
+    }
+
+    Ok(max_allocations.min(allowlist_entry.max_allocations))
+}
+
+fn check_token_gate(
+    ido_campaign: &IdoCampaign,
+    gate_token_account: Option<&TokenAccount>,
+    gate_list_entry: Option<&GateListEntry>,
+    max_allocations: u64,
+) -> Result<u64> {
+    if ido_campaign.gate_mint == Pubkey::default() && !ido_campaign.gate_mint_list {
+        return Ok(max_allocations);
+    }
+
+    let gate_token_account = gate_token_account.ok_or(IdoError::ErrGateTokenAccountRequired)?;
+    if ido_campaign.gate_mint_list {
+        let gate_list_entry = gate_list_entry.ok_or(IdoError::ErrGateMintNotListed)?;
+        require_keys_eq!(gate_list_entry.mint, gate_token_account.mint, IdoError::ErrGateMintNotListed);
+    } else {
+        require_keys_eq!(gate_token_account.mint, ido_campaign.gate_mint, IdoError::ErrInvalidTokenMint);
+    }
+    require!(
+        gate_token_account.amount >= ido_campaign.gate_min_balance,
+        IdoError::ErrInsufficientGateBalance
+    );
+
+    if ido_campaign.gate_tokens_per_allocation == 0 {
+        return Ok(max_allocations);
+    }
+
+    Ok(max_allocations.min(gate_token_account.amount / ido_campaign.gate_tokens_per_allocation))
+}
+
+/// The `KycTicket` has to be signed through the Ed25519 program in the instruction right
+/// before `join_ido`, its max allocations caps whatever the campaign otherwise allows.
+fn check_kyc(
+    ido_campaign: &Account<IdoCampaign>,
+    participant: Pubkey,
+    instructions_sysvar: Option<&UncheckedAccount>,
+    max_allocations: u64,
+    now: u64,
+) -> Result<u64> {
+    if ido_campaign.kyc_signer == Pubkey::default() {
+        return Ok(max_allocations);
+    }
+
+    let instructions_sysvar = instructions_sysvar.ok_or(IdoError::ErrKycTicketRequired)?;
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+    require!(current_index > 0, IdoError::ErrKycTicketRequired);
+    let ed25519_instruction = load_instruction_at_checked(current_index as usize - 1, instructions_sysvar)?;
+
+    let kyc_ticket = KycTicket::from_ed25519_instruction(&ed25519_instruction, &ido_campaign.kyc_signer)?;
+    require_keys_eq!(kyc_ticket.ido_campaign, ido_campaign.key(), IdoError::ErrInvalidKycTicket);
+    require_keys_eq!(kyc_ticket.participant, participant, IdoError::ErrInvalidKycTicket);
+    require!(now <= kyc_ticket.expires_at, IdoError::ErrKycTicketExpired);
+
+    Ok(max_allocations.min(kyc_ticket.max_allocations))
+}
+
+fn max_allocations_per_participant(
+    ido_campaign: &IdoCampaign,
+    stake_account: Option<&StakeAccount>,
+    stake_tiers: Option<&StakeTiers>,
+) -> Result<u64> {
+    if !ido_campaign.stake_tiered {
+        return Ok(ido_campaign.available_allocations_per_participant);
+    }
+
+    let stake_tiers = stake_tiers.ok_or(IdoError::ErrStakeTiersRequired)?;
+    let staked = stake_account.map_or(0, |stake_account| stake_account.amount);
+
+    let max_allocations = stake_tiers.max_allocations(staked);
+    require!(max_allocations > 0, IdoError::ErrStakeTooLow);
+
+    Ok(max_allocations)
+}
+
 fn check_campaign<'info>(
     ido_campaign: &IdoCampaign,
     participant: &Signer<'info>,
     number_of_allocations: u64,
+    max_allocations: u64,
     total_cost_lamports: u64,
     amount_to_buy: u64,
 ) -> Result<()> {
-    let now = Clock::get()?.unix_timestamp as u64;
-
     require!(
-        !ido_campaign.sale_closed,
+        !ido_campaign.status.is_closed(),
         IdoError::ErrSaleAlreadyClosed,
     );
 
     require!(
-        ido_campaign.token_supply_deposited,
+        ido_campaign.status != CampaignStatus::Created,
         IdoError::ErrTokenSupplyNotDeposited,
     );
+
+    // Batch auction bids go through commit_bid and reveal_bid.
+    require!(
+        ido_campaign.sale_mode != SaleMode::BatchAuction,
+        IdoError::ErrInvalidSaleMode,
+    );
     
     require!(
-        number_of_allocations > 0 && number_of_allocations <= ido_campaign.available_allocations_per_participant, 
+        number_of_allocations > 0 && number_of_allocations <= max_allocations, 
         IdoError::ErrInvalidNumberOfAllocations,
     );
     
     require!(
-        now >= ido_campaign.start_sale_time && now <= ido_campaign.end_sale_time, 
+        ido_campaign.status == CampaignStatus::Live, 
         IdoError::ErrInvalidSalePeriod,
     );
     
-    let new_total_sold = ido_campaign.total_sold
-        .checked_add(amount_to_buy)
-        .ok_or(IdoError::ErrMathOverflow)?;
-    require!(
-        new_total_sold <= ido_campaign.hard_cap,
-        IdoError::ErrThisAllocationIsNotAvailable,
-    );
+    if ido_campaign.sale_mode != SaleMode::ProRata {
+        let new_total_sold = ido_campaign.total_sold
+            .checked_add(amount_to_buy)
+            .ok_or(IdoError::ErrMathOverflow)?;
+        require!(
+            new_total_sold <= ido_campaign.hard_cap,
+            IdoError::ErrThisAllocationIsNotAvailable,
+        );
+    }
 
     let participant_lamports = participant.to_account_info().lamports();
-    let user_rent = Rent::get()?.minimum_balance(std::mem::size_of::<User>() + 8);
+    let user_rent = Rent::get()?.minimum_balance(User::INIT_SPACE + 8);
     
     let required_lamports = total_cost_lamports
         .checked_add(user_rent)
@@ -0,0 +1,63 @@
+use {
+    crate::{
+        instructions::IdoError,
+        state::{IdoCampaign, LaunchpadConfig, MilestoneEscrow, TrancheState},
+    },
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct MarkEscrowFailed<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"launchpad_config"], bump,
+        constraint = launchpad_config.admin == admin.key() @ IdoError::ErrUnauthorized,
+    )]
+    pub launchpad_config: Account<'info, LaunchpadConfig>,
+
+    /// CHECK: This account is used only as a seed to derive the ido_campaign PDA
+    pub ido_campaign_owner: AccountInfo<'info>,
+
+    #[account(
+        seeds = [b"ido_campaign", ido_campaign_owner.key().as_ref()], bump,
+    )]
+    pub ido_campaign: Account<'info, IdoCampaign>,
+
+    #[account(
+        mut,
+        seeds = [b"milestone_escrow", ido_campaign.key().as_ref()], bump,
+    )]
+    pub milestone_escrow: Account<'info, MilestoneEscrow>,
+}
+
+/// The project failed to deliver, every tranche not released yet becomes refundable.
+pub fn mark_escrow_failed(ctx: Context<MarkEscrowFailed>) -> Result<()> {
+    require!(ctx.accounts.ido_campaign.funds_withdrawn, IdoError::ErrFundsNotWithdrawn);
+
+    let milestone_escrow = &mut ctx.accounts.milestone_escrow;
+    require!(!milestone_escrow.failed, IdoError::ErrEscrowFailed);
+
+    for index in 0..milestone_escrow.tranches.len() as u8 {
+        if matches!(
+            milestone_escrow.tranches[index as usize].state,
+            TrancheState::Locked | TrancheState::Approved
+        ) {
+            refund_tranche(milestone_escrow, index)?;
+        }
+    }
+    milestone_escrow.failed = true;
+
+    Ok(())
+}
+
+pub(crate) fn refund_tranche(milestone_escrow: &mut MilestoneEscrow, tranche_index: u8) -> Result<()> {
+    let amount = milestone_escrow.tranche_amount(tranche_index)?;
+
+    milestone_escrow.tranches[tranche_index as usize].state = TrancheState::Refunded;
+    milestone_escrow.refundable_lamports = milestone_escrow.refundable_lamports
+        .checked_add(amount)
+        .ok_or(IdoError::ErrMathOverflow)?;
+
+    Ok(())
+}
@@ -0,0 +1,159 @@
+use {
+    crate::{
+        instructions::IdoError,
+        state::{CampaignStatus, IdoCampaign, IDO_CAMPAIGN_VERSION},
+    },
+    anchor_lang::{prelude::*, system_program::{self, Transfer}, Discriminator},
+};
+
+/// Size of campaigns created before versioning, they were allocated with
+/// `8 + std::mem::size_of::<IdoCampaign>()` of the unversioned layout.
+pub const LEGACY_IDO_CAMPAIGN_LEN: usize = 8 + 232;
+
+/// Unversioned `IdoCampaign` layout, `status` was appended into the padding byte.
+#[derive(AnchorDeserialize)]
+pub struct LegacyIdoCampaign {
+    pub authority: Pubkey,
+    pub token_treasury: Pubkey,
+    pub sol_treasury: Pubkey,
+    pub cliff: u64,
+    pub available_tokens_after_cliff_ptc: i32,
+    pub start_sale_time: u64,
+    pub end_sale_time: u64,
+    pub vesting_end_time: u64,
+    pub price_lamports: u64,
+    pub total_claimed: u64,
+    pub total_sold: u64,
+    pub total_participants: u64,
+    pub allocation: u64,
+    pub soft_cap: u64,
+    pub hard_cap: u64,
+    pub available_allocations_per_participant: u64,
+    pub token_mint: Pubkey,
+    pub token_supply_deposited: bool,
+    pub funds_withdrawn: bool,
+    pub sale_closed: bool,
+    pub status: CampaignStatus,
+}
+
+#[derive(Accounts)]
+pub struct MigrateCampaign<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: This account is used only as a seed to derive the ido_campaign PDA
+    pub ido_campaign_owner: AccountInfo<'info>,
+
+    /// CHECK: Deserialized manually, the stored layout may predate the current one
+    #[account(
+        mut,
+        owner = crate::ID @ IdoError::ErrInvalidIdoCampaign,
+        seeds = [b"ido_campaign", ido_campaign_owner.key().as_ref()], bump,
+    )]
+    pub ido_campaign: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn migrate_campaign(ctx: Context<MigrateCampaign>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp as u64;
+    let ido_campaign_info = ctx.accounts.ido_campaign.to_account_info();
+
+    let mut ido_campaign = {
+        let data = ido_campaign_info.try_borrow_data()?;
+        require!(
+            data.len() >= 8 && &data[..8] == IdoCampaign::DISCRIMINATOR,
+            IdoError::ErrInvalidAccountData
+        );
+
+        if data.len() == LEGACY_IDO_CAMPAIGN_LEN {
+            let legacy = LegacyIdoCampaign::deserialize(&mut &data[8..])?;
+            from_legacy(legacy)?
+        } else {
+            let mut current = IdoCampaign::try_deserialize(&mut &data[..])?;
+            require!(current.version < IDO_CAMPAIGN_VERSION, IdoError::ErrAccountAlreadyMigrated);
+            current.version = IDO_CAMPAIGN_VERSION;
+            current
+        }
+    };
+    ido_campaign.sync_status(now)?;
+
+    resize_account(
+        &ido_campaign_info,
+        &ctx.accounts.payer,
+        &ctx.accounts.system_program,
+        8 + IdoCampaign::INIT_SPACE,
+    )?;
+
+    let mut data = ido_campaign_info.try_borrow_mut_data()?;
+    ido_campaign.try_serialize(&mut &mut data[..])?;
+
+    Ok(())
+}
+
+fn from_legacy(legacy: LegacyIdoCampaign) -> Result<IdoCampaign> {
+    // Campaigns created before `status` existed read it back as `Created`, every
+    // other value was written by the state machine and is kept as is.
+    let status = if legacy.status == CampaignStatus::Created {
+        CampaignStatus::from_legacy_flags(
+            legacy.token_supply_deposited,
+            legacy.funds_withdrawn,
+            legacy.sale_closed,
+            legacy.total_sold >= legacy.soft_cap,
+        )
+    } else {
+        legacy.status
+    };
+
+    // Fields added after versioning did not exist back then and start zeroed.
+    let mut ido_campaign = IdoCampaign::deserialize(&mut &[0u8; IdoCampaign::INIT_SPACE][..])?;
+    ido_campaign.version = IDO_CAMPAIGN_VERSION;
+    ido_campaign.status = status;
+    ido_campaign.authority = legacy.authority;
+    ido_campaign.token_treasury = legacy.token_treasury;
+    ido_campaign.sol_treasury = legacy.sol_treasury;
+    ido_campaign.cliff = legacy.cliff;
+    ido_campaign.available_tokens_after_cliff_ptc = legacy.available_tokens_after_cliff_ptc;
+    ido_campaign.start_sale_time = legacy.start_sale_time;
+    ido_campaign.end_sale_time = legacy.end_sale_time;
+    ido_campaign.vesting_end_time = legacy.vesting_end_time;
+    ido_campaign.price_lamports = legacy.price_lamports;
+    ido_campaign.total_claimed = legacy.total_claimed;
+    ido_campaign.total_sold = legacy.total_sold;
+    ido_campaign.total_participants = legacy.total_participants;
+    ido_campaign.allocation = legacy.allocation;
+    ido_campaign.soft_cap = legacy.soft_cap;
+    ido_campaign.hard_cap = legacy.hard_cap;
+    ido_campaign.available_allocations_per_participant = legacy.available_allocations_per_participant;
+    ido_campaign.token_mint = legacy.token_mint;
+    ido_campaign.funds_withdrawn = legacy.funds_withdrawn;
+    ido_campaign.total_committed = legacy.total_sold;
+
+    Ok(ido_campaign)
+}
+
+/// Grows (or shrinks) a program-owned account to `new_len`, topping up rent from `payer`.
+pub(crate) fn resize_account<'info>(
+    account: &AccountInfo<'info>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    new_len: usize,
+) -> Result<()> {
+    let required_lamports = Rent::get()?.minimum_balance(new_len);
+    let missing_lamports = required_lamports.saturating_sub(account.lamports());
+
+    if missing_lamports > 0 {
+        let cpi_context = CpiContext::new(
+            system_program.to_account_info(),
+            Transfer {
+                from: payer.to_account_info(),
+                to: account.clone(),
+            },
+        );
+        system_program::transfer(cpi_context, missing_lamports)?;
+    }
+
+    account.resize(new_len)?;
+
+    Ok(())
+}
@@ -0,0 +1,94 @@
+use {
+    crate::{
+        instructions::{resize_account, IdoError},
+        state::{User, USER_VERSION},
+    },
+    anchor_lang::{prelude::*, Discriminator},
+};
+
+/// Size of user accounts created before versioning, they were allocated with
+/// `8 + std::mem::size_of::<User>()` of the unversioned layout.
+pub const LEGACY_USER_LEN: usize = 8 + 96;
+
+/// Unversioned `User` layout.
+#[derive(AnchorDeserialize)]
+pub struct LegacyUser {
+    pub ido_campaign: Pubkey,
+    pub participant: Pubkey,
+    pub amount: u64,
+    pub paid_lamports: u64,
+    pub claimed: u64,
+    pub joined_at: u64,
+}
+
+#[derive(Accounts)]
+pub struct MigrateUser<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: This account is used only as a seed to derive the user PDA
+    pub participant: AccountInfo<'info>,
+
+    /// CHECK: This account is used only as a seed to derive the ido_campaign PDA
+    pub ido_campaign_owner: AccountInfo<'info>,
+
+    /// CHECK: Only its address is used, to derive the user PDA
+    #[account(
+        seeds = [b"ido_campaign", ido_campaign_owner.key().as_ref()], bump,
+    )]
+    pub ido_campaign: UncheckedAccount<'info>,
+
+    /// CHECK: Deserialized manually, the stored layout may predate the current one
+    #[account(
+        mut,
+        owner = crate::ID @ IdoError::ErrInvalidOwner,
+        seeds = [b"user", ido_campaign.key().as_ref(), participant.key().as_ref()], bump,
+    )]
+    pub user: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn migrate_user(ctx: Context<MigrateUser>) -> Result<()> {
+    let user_info = ctx.accounts.user.to_account_info();
+
+    let user = {
+        let data = user_info.try_borrow_data()?;
+        require!(
+            data.len() >= 8 && &data[..8] == User::DISCRIMINATOR,
+            IdoError::ErrInvalidAccountData
+        );
+
+        if data.len() == LEGACY_USER_LEN {
+            let legacy = LegacyUser::deserialize(&mut &data[8..])?;
+
+            // Fields added after versioning did not exist back then and start zeroed.
+            let mut user = User::deserialize(&mut &[0u8; User::INIT_SPACE][..])?;
+            user.version = USER_VERSION;
+            user.ido_campaign = legacy.ido_campaign;
+            user.participant = legacy.participant;
+            user.amount = legacy.amount;
+            user.paid_lamports = legacy.paid_lamports;
+            user.claimed = legacy.claimed;
+            user.joined_at = legacy.joined_at;
+            user
+        } else {
+            let mut current = User::try_deserialize(&mut &data[..])?;
+            require!(current.version < USER_VERSION, IdoError::ErrAccountAlreadyMigrated);
+            current.version = USER_VERSION;
+            current
+        }
+    };
+
+    resize_account(
+        &user_info,
+        &ctx.accounts.payer,
+        &ctx.accounts.system_program,
+        8 + User::INIT_SPACE,
+    )?;
+
+    let mut data = user_info.try_borrow_mut_data()?;
+    user.try_serialize(&mut &mut data[..])?;
+
+    Ok(())
+}
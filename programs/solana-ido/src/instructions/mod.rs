@@ -8,6 +8,72 @@ pub mod close_campaign;
 pub mod refund;
 pub mod close_campaign_if_soft_cap_not_reached;
 pub mod withdraw_tokens_to_owner_if_soft_cap_not_reached;
+pub mod get_claimable;
+pub mod quote_purchase;
+pub mod get_campaign_status;
+pub mod migrate_campaign;
+pub mod migrate_user;
+pub mod initialize_campaign_metadata;
+pub mod update_campaign_metadata;
+pub mod initialize_campaign_registry;
+pub mod configure_pro_rata_sale;
+pub mod refund_oversubscription;
+pub mod configure_dutch_auction;
+pub mod refund_price_difference;
+pub mod configure_bonding_curve;
+pub mod configure_batch_auction;
+pub mod commit_bid;
+pub mod reveal_bid;
+pub mod settle_batch_auction;
+pub mod refund_excess_deposit;
+pub mod initialize_launchpad_config;
+pub mod update_launchpad_config;
+pub mod stake;
+pub mod unstake;
+pub mod configure_stake_tiers;
+pub mod configure_lottery;
+pub mod register_for_lottery;
+pub mod draw_lottery;
+pub mod close_lottery_ticket;
+pub mod configure_kyc;
+pub mod configure_anti_bot;
+pub mod configure_allowlist;
+pub mod add_to_allowlist;
+pub mod remove_from_allowlist;
+pub mod add_to_blocklist;
+pub mod remove_from_blocklist;
+pub mod configure_token_gate;
+pub mod configure_milestone_escrow;
+pub mod approve_milestone;
+pub mod release_tranche;
+pub mod mark_escrow_failed;
+pub mod refund_escrow;
+pub mod configure_milestone_voting;
+pub mod create_milestone_proposal;
+pub mod vote_on_milestone;
+pub mod execute_milestone_proposal;
+pub mod configure_referral_program;
+pub mod register_referrer;
+pub mod claim_referral_reward;
+pub mod create_token_lock;
+pub mod claim_token_lock;
+pub mod revoke_token_lock;
+pub mod set_position_revocable;
+pub mod revoke_position;
+pub mod configure_airdrop;
+pub mod claim_airdrop;
+pub mod reclaim_airdrop;
+pub mod configure_claim_deadline;
+pub mod sweep_unclaimed_tokens;
+pub mod configure_unsold_token_burn;
+pub mod configure_mint_on_claim;
+pub mod release_mint_authority;
+pub mod fund_sale_supply;
+pub mod return_funder_deposit;
+pub mod add_to_gate_list;
+pub mod remove_from_gate_list;
+pub mod sync_registry_entry;
+pub mod schedule_lottery_draw;
 
 pub use create_ido_campaign::*;
 pub use errors::*;
@@ -18,4 +84,70 @@ pub use withdraw_funds::*;
 pub use close_campaign::*;
 pub use refund::*;
 pub use close_campaign_if_soft_cap_not_reached::*;
-pub use withdraw_tokens_to_owner_if_soft_cap_not_reached::*;
\ No newline at end of file
+pub use withdraw_tokens_to_owner_if_soft_cap_not_reached::*;
+pub use get_claimable::*;
+pub use quote_purchase::*;
+pub use get_campaign_status::*;
+pub use migrate_campaign::*;
+pub use migrate_user::*;
+pub use initialize_campaign_metadata::*;
+pub use update_campaign_metadata::*;
+pub use initialize_campaign_registry::*;
+pub use configure_pro_rata_sale::*;
+pub use refund_oversubscription::*;
+pub use configure_dutch_auction::*;
+pub use refund_price_difference::*;
+pub use configure_bonding_curve::*;
+pub use configure_batch_auction::*;
+pub use commit_bid::*;
+pub use reveal_bid::*;
+pub use settle_batch_auction::*;
+pub use refund_excess_deposit::*;
+pub use initialize_launchpad_config::*;
+pub use update_launchpad_config::*;
+pub use stake::*;
+pub use unstake::*;
+pub use configure_stake_tiers::*;
+pub use configure_lottery::*;
+pub use register_for_lottery::*;
+pub use draw_lottery::*;
+pub use close_lottery_ticket::*;
+pub use configure_kyc::*;
+pub use configure_anti_bot::*;
+pub use configure_allowlist::*;
+pub use add_to_allowlist::*;
+pub use remove_from_allowlist::*;
+pub use add_to_blocklist::*;
+pub use remove_from_blocklist::*;
+pub use configure_token_gate::*;
+pub use configure_milestone_escrow::*;
+pub use approve_milestone::*;
+pub use release_tranche::*;
+pub use mark_escrow_failed::*;
+pub use refund_escrow::*;
+pub use configure_milestone_voting::*;
+pub use create_milestone_proposal::*;
+pub use vote_on_milestone::*;
+pub use execute_milestone_proposal::*;
+pub use configure_referral_program::*;
+pub use register_referrer::*;
+pub use claim_referral_reward::*;
+pub use create_token_lock::*;
+pub use claim_token_lock::*;
+pub use revoke_token_lock::*;
+pub use set_position_revocable::*;
+pub use revoke_position::*;
+pub use configure_airdrop::*;
+pub use claim_airdrop::*;
+pub use reclaim_airdrop::*;
+pub use configure_claim_deadline::*;
+pub use sweep_unclaimed_tokens::*;
+pub use configure_unsold_token_burn::*;
+pub use configure_mint_on_claim::*;
+pub use release_mint_authority::*;
+pub use fund_sale_supply::*;
+pub use return_funder_deposit::*;
+pub use add_to_gate_list::*;
+pub use remove_from_gate_list::*;
+pub use sync_registry_entry::*;
+pub use schedule_lottery_draw::*;
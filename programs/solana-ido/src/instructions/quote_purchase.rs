@@ -0,0 +1,42 @@
+use {
+    crate::{instructions::{calculate_purchase, IdoError}, state::IdoCampaign},
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct QuotePurchase<'info> {
+    /// CHECK: This account is used only as a seed to derive the ido_campaign PDA
+    pub ido_campaign_owner: AccountInfo<'info>,
+
+    #[account(
+        seeds = [b"ido_campaign", ido_campaign_owner.key().as_ref()], bump,
+    )]
+    pub ido_campaign: Account<'info, IdoCampaign>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct PurchaseQuote {
+    pub amount: u64,
+    pub total_cost_lamports: u64,
+    pub remaining_tokens: u64,
+}
+
+pub fn quote_purchase(ctx: Context<QuotePurchase>, number_of_allocations: u64) -> Result<PurchaseQuote> {
+    let now = Clock::get()?.unix_timestamp as u64;
+    let ido_campaign = &ctx.accounts.ido_campaign;
+
+    require!(
+        number_of_allocations > 0 && number_of_allocations <= ido_campaign.available_allocations_per_participant,
+        IdoError::ErrInvalidNumberOfAllocations,
+    );
+
+    let (amount, total_cost_lamports) = calculate_purchase(ido_campaign, number_of_allocations, now)?;
+
+    let remaining_tokens = ido_campaign.hard_cap.saturating_sub(ido_campaign.total_sold);
+
+    Ok(PurchaseQuote {
+        amount,
+        total_cost_lamports,
+        remaining_tokens,
+    })
+}
@@ -0,0 +1,84 @@
+use {
+    crate::{
+        instructions::IdoError,
+        state::{Airdrop, IdoCampaign, SaleMode},
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::token::{self, Mint, Token, TokenAccount, TransferChecked},
+};
+
+#[derive(Accounts)]
+pub struct ReclaimAirdrop<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(mut, constraint = owner_token_account.owner == owner.key() @ IdoError::ErrInvalidOwner)]
+    pub owner_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"ido_campaign", owner.key().as_ref()], bump,
+        constraint = ido_campaign.authority == owner.key() @ IdoError::ErrUnauthorized,
+    )]
+    pub ido_campaign: Account<'info, IdoCampaign>,
+
+    #[account(
+        seeds = [b"airdrop", ido_campaign.key().as_ref()], bump,
+    )]
+    pub airdrop: Account<'info, Airdrop>,
+
+    #[account(
+        mut,
+        seeds = [b"tokens_treasury", ido_campaign.key().as_ref()], bump,
+        constraint = tokens_treasury.key() == ido_campaign.token_treasury @ IdoError::ErrInvalidIdoCampaign,
+    )]
+    pub tokens_treasury: Account<'info, TokenAccount>,
+
+    #[account(constraint = token_mint.key() == ido_campaign.token_mint @ IdoError::ErrInvalidTokenMint)]
+    pub token_mint: Account<'info, Mint>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Airdrop counterpart of `withdraw_funds`: returns the allocations nobody proved before
+/// the deadline, positions opened in time keep vesting.
+pub fn reclaim_airdrop(ctx: Context<ReclaimAirdrop>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp as u64;
+    let ido_campaign = &mut ctx.accounts.ido_campaign;
+
+    require!(ido_campaign.sale_mode == SaleMode::Airdrop, IdoError::ErrInvalidSaleMode);
+    require!(!ido_campaign.funds_withdrawn, IdoError::ErrFundsAlreadyWithdrawn);
+    require!(now >= ctx.accounts.airdrop.claim_deadline, IdoError::ErrClaimDeadlineNotReached);
+    require!(!ido_campaign.sync_status(now)?.is_closed(), IdoError::ErrSaleAlreadyClosed);
+
+    let unclaimed = ido_campaign.hard_cap
+        .checked_sub(ido_campaign.total_sold)
+        .ok_or(IdoError::ErrMathOverflow)?;
+
+    ido_campaign.funds_withdrawn = true;
+    ido_campaign.sync_status(now)?;
+
+    if unclaimed == 0 || ido_campaign.mint_on_claim {
+        return Ok(());
+    }
+
+    let owner_key = ctx.accounts.owner.key();
+    let bump_bytes = [ctx.bumps.ido_campaign];
+    let seeds: [&[u8]; 3] = [
+        b"ido_campaign",
+        owner_key.as_ref(),
+        &bump_bytes,
+    ];
+    let signer = [&seeds[..]];
+
+    let cpi_context = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        TransferChecked {
+            from: ctx.accounts.tokens_treasury.to_account_info(),
+            to: ctx.accounts.owner_token_account.to_account_info(),
+            authority: ctx.accounts.ido_campaign.to_account_info(),
+            mint: ctx.accounts.token_mint.to_account_info(),
+        },
+        &signer,
+    );
+    token::transfer_checked(cpi_context, unclaimed, ctx.accounts.token_mint.decimals)
+}
@@ -34,13 +34,16 @@ pub struct Refund<'info> {
 }
 
 pub fn refund(ctx: Context<Refund>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp as u64;
+    ctx.accounts.ido_campaign.sync_status(now)?;
+
     let ido_campaign = &ctx.accounts.ido_campaign;
     let participant = &ctx.accounts.participant;
     let user = &mut ctx.accounts.user;
     let sol_treasury = &mut ctx.accounts.sol_treasury;
 
-    require!(ido_campaign.sale_closed, IdoError::ErrSaleNotClosed);
-    require!(user.amount > 0, IdoError::ErrNothingToRefund);
+    require!(ido_campaign.status.is_closed(), IdoError::ErrSaleNotClosed);
+    require!(user.paid_lamports > 0, IdoError::ErrNothingToRefund);
     require!(user.joined_at > 0, IdoError::ErrUserNotJoined);
     require!(user.participant == participant.key(), IdoError::ErrUnauthorized);
     require!(user.ido_campaign == ido_campaign.key(), IdoError::ErrInvalidIdoCampaign);
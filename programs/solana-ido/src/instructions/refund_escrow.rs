@@ -0,0 +1,69 @@
+use {
+    crate::{
+        instructions::{pay_from_sol_treasury, IdoError},
+        state::{IdoCampaign, MilestoneEscrow, User},
+    },
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct RefundEscrow<'info> {
+    #[account(mut)]
+    pub participant: Signer<'info>,
+
+    /// CHECK: This account is used only as a seed to derive the ido_campaign PDA
+    pub ido_campaign_owner: AccountInfo<'info>,
+
+    /// Writable since settling the refund share advances the pro-rata fills.
+    #[account(
+        mut,
+        seeds = [b"ido_campaign", ido_campaign_owner.key().as_ref()], bump,
+    )]
+    pub ido_campaign: Account<'info, IdoCampaign>,
+
+    #[account(
+        mut,
+        seeds = [b"user", ido_campaign.key().as_ref(), participant.key().as_ref()], bump,
+    )]
+    pub user: Account<'info, User>,
+
+    #[account(
+        seeds = [b"milestone_escrow", ido_campaign.key().as_ref()], bump,
+    )]
+    pub milestone_escrow: Account<'info, MilestoneEscrow>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_treasury", ido_campaign.key().as_ref()], bump,
+        constraint = sol_treasury.key() == ido_campaign.sol_treasury @ IdoError::ErrInvalidSolTreasury,
+    )]
+    pub sol_treasury: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Pays the participant share of every refunded tranche it has not claimed yet.
+pub fn refund_escrow(ctx: Context<RefundEscrow>) -> Result<()> {
+    let ido_campaign = &mut ctx.accounts.ido_campaign;
+    let user = &mut ctx.accounts.user;
+    require!(user.joined_at > 0, IdoError::ErrUserNotJoined);
+
+    // Refunds are shared by tokens bought, which only final once settled.
+    user.settle(ido_campaign)?;
+    let (refund, refunded_tranches) = ctx
+        .accounts
+        .milestone_escrow
+        .unclaimed_refund(user, ido_campaign.total_sold)?;
+    require!(refund > 0, IdoError::ErrNothingToRefund);
+
+    user.escrow_refunded_tranches = refunded_tranches;
+
+    pay_from_sol_treasury(
+        &ctx.accounts.sol_treasury,
+        &ctx.accounts.participant.to_account_info(),
+        ido_campaign.key(),
+        ctx.bumps.sol_treasury,
+        &ctx.accounts.system_program,
+        refund,
+    )
+}
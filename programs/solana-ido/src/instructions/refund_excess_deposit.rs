@@ -0,0 +1,53 @@
+use {
+    crate::{
+        instructions::{refund_settled_excess, IdoError},
+        state::{IdoCampaign, SaleMode, User},
+    },
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct RefundExcessDeposit<'info> {
+    #[account(mut)]
+    pub participant: Signer<'info>,
+
+    /// CHECK: This account is used only as a seed to derive the ido_campaign PDA
+    pub ido_campaign_owner: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"ido_campaign", ido_campaign_owner.key().as_ref()], bump,
+    )]
+    pub ido_campaign: Account<'info, IdoCampaign>,
+
+    #[account(
+        mut,
+        seeds = [b"user", ido_campaign.key().as_ref(), participant.key().as_ref()], bump,
+    )]
+    pub user: Account<'info, User>,
+
+    #[account(
+        mut,
+        constraint = sol_treasury.key() == ido_campaign.sol_treasury @ IdoError::ErrInvalidSolTreasury,
+        seeds = [b"sol_treasury", ido_campaign.key().as_ref()], bump,
+    )]
+    pub sol_treasury: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn refund_excess_deposit(ctx: Context<RefundExcessDeposit>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp as u64;
+    ctx.accounts.ido_campaign.sync_status(now)?;
+
+    require!(ctx.accounts.ido_campaign.sale_mode == SaleMode::BatchAuction, IdoError::ErrInvalidSaleMode);
+
+    refund_settled_excess(
+        &mut ctx.accounts.ido_campaign,
+        &mut ctx.accounts.user,
+        &ctx.accounts.participant,
+        &ctx.accounts.sol_treasury,
+        ctx.bumps.sol_treasury,
+        &ctx.accounts.system_program,
+    )
+}
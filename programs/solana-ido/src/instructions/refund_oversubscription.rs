@@ -0,0 +1,104 @@
+use {
+    crate::{instructions::IdoError, state::{CampaignStatus, IdoCampaign, SaleMode, User}},
+    anchor_lang::{prelude::*, system_program::{self, Transfer}},
+};
+
+#[derive(Accounts)]
+pub struct RefundOversubscription<'info> {
+    #[account(mut)]
+    pub participant: Signer<'info>,
+
+    /// CHECK: This account is used only as a seed to derive the ido_campaign PDA
+    pub ido_campaign_owner: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"ido_campaign", ido_campaign_owner.key().as_ref()], bump,
+    )]
+    pub ido_campaign: Account<'info, IdoCampaign>,
+
+    #[account(
+        mut,
+        seeds = [b"user", ido_campaign.key().as_ref(), participant.key().as_ref()], bump,
+    )]
+    pub user: Account<'info, User>,
+
+    #[account(
+        mut,
+        constraint = sol_treasury.key() == ido_campaign.sol_treasury @ IdoError::ErrInvalidSolTreasury,
+        seeds = [b"sol_treasury", ido_campaign.key().as_ref()], bump,
+    )]
+    pub sol_treasury: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn refund_oversubscription(ctx: Context<RefundOversubscription>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp as u64;
+    ctx.accounts.ido_campaign.sync_status(now)?;
+
+    require!(ctx.accounts.ido_campaign.sale_mode == SaleMode::ProRata, IdoError::ErrInvalidSaleMode);
+
+    refund_settled_excess(
+        &mut ctx.accounts.ido_campaign,
+        &mut ctx.accounts.user,
+        &ctx.accounts.participant,
+        &ctx.accounts.sol_treasury,
+        ctx.bumps.sol_treasury,
+        &ctx.accounts.system_program,
+    )
+}
+
+/// Settles the user and pays back `refundable_lamports`, shared by the sale modes that
+/// only know the final purchase after the sale.
+pub(crate) fn refund_settled_excess<'info>(
+    ido_campaign: &mut Account<'info, IdoCampaign>,
+    user: &mut Account<'info, User>,
+    participant: &Signer<'info>,
+    sol_treasury: &SystemAccount<'info>,
+    sol_treasury_bump: u8,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    check_settled_status(ido_campaign.status)?;
+    require!(user.joined_at > 0, IdoError::ErrUserNotJoined);
+    require!(user.participant == participant.key(), IdoError::ErrUnauthorized);
+    require!(user.ido_campaign == ido_campaign.key(), IdoError::ErrInvalidIdoCampaign);
+
+    user.settle(ido_campaign)?;
+
+    let amount_to_refund_lamports = user.refundable_lamports;
+    require!(amount_to_refund_lamports > 0, IdoError::ErrNothingToRefund);
+    require!(sol_treasury.lamports() >= amount_to_refund_lamports, IdoError::ErrNotEnoughFundsInSolTreasury);
+
+    let ido_campaign_key = ido_campaign.key();
+    let bump_bytes = [sol_treasury_bump];
+    let seeds: [&[u8]; 3] = [
+        b"sol_treasury",
+        ido_campaign_key.as_ref(),
+        &bump_bytes,
+    ];
+    let signer = [&seeds[..]];
+
+    let cpi_context = CpiContext::new_with_signer(
+        system_program.to_account_info(),
+        Transfer {
+            from: sol_treasury.to_account_info(),
+            to: participant.to_account_info(),
+        },
+        &signer,
+    );
+    system_program::transfer(cpi_context, amount_to_refund_lamports)?;
+
+    user.refundable_lamports = 0;
+
+    Ok(())
+}
+
+fn check_settled_status(status: CampaignStatus) -> Result<()> {
+    match status {
+        CampaignStatus::Succeeded | CampaignStatus::Finalized => Ok(()),
+        // Failed and cancelled sales return the whole payment through refund.
+        CampaignStatus::Failed | CampaignStatus::Cancelled => err!(IdoError::ErrSaleAlreadyClosed),
+        _ => err!(IdoError::ErrSaleNotSettled),
+    }
+}
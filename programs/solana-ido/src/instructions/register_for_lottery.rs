@@ -0,0 +1,76 @@
+use {
+    crate::{
+        instructions::IdoError,
+        state::{CampaignStatus, IdoCampaign, Lottery, LotteryTicket, LOTTERY_VERSION, MAX_LOTTERY_REGISTRANTS},
+    },
+    anchor_lang::{prelude::*, system_program::{self, Transfer}},
+};
+
+#[derive(Accounts)]
+pub struct RegisterForLottery<'info> {
+    #[account(mut)]
+    pub participant: Signer<'info>,
+
+    /// CHECK: This account is used only as a seed to derive the ido_campaign PDA
+    pub ido_campaign_owner: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"ido_campaign", ido_campaign_owner.key().as_ref()], bump,
+    )]
+    pub ido_campaign: Account<'info, IdoCampaign>,
+
+    #[account(
+        mut,
+        seeds = [b"lottery", ido_campaign.key().as_ref()], bump,
+    )]
+    pub lottery: Account<'info, Lottery>,
+
+    #[account(
+        init,
+        payer = participant,
+        space = 8 + LotteryTicket::INIT_SPACE,
+        seeds = [b"lottery_ticket", ido_campaign.key().as_ref(), participant.key().as_ref()], bump,
+    )]
+    pub lottery_ticket: Account<'info, LotteryTicket>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn register_for_lottery(ctx: Context<RegisterForLottery>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp as u64;
+    ctx.accounts.ido_campaign.sync_status(now)?;
+
+    let ido_campaign = &ctx.accounts.ido_campaign;
+    require!(!ido_campaign.status.is_closed(), IdoError::ErrSaleAlreadyClosed);
+    require!(ido_campaign.status != CampaignStatus::Created, IdoError::ErrTokenSupplyNotDeposited);
+    require!(ido_campaign.status == CampaignStatus::Funded, IdoError::ErrLotteryRegistrationClosed);
+    require!(
+        ctx.accounts.lottery.registrant_count < MAX_LOTTERY_REGISTRANTS,
+        IdoError::ErrLotteryFull
+    );
+
+    // The deposit sits on the ticket itself and comes back when it is closed.
+    let deposit = ctx.accounts.lottery.registration_deposit_lamports;
+    if deposit > 0 {
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.participant.to_account_info(),
+                to: ctx.accounts.lottery_ticket.to_account_info(),
+            },
+        );
+        system_program::transfer(cpi_context, deposit)?;
+    }
+
+    let lottery = &mut ctx.accounts.lottery;
+    let lottery_ticket = &mut ctx.accounts.lottery_ticket;
+    lottery_ticket.version = LOTTERY_VERSION;
+    lottery_ticket.ido_campaign = ctx.accounts.ido_campaign.key();
+    lottery_ticket.participant = ctx.accounts.participant.key();
+    lottery_ticket.index = lottery.registrant_count;
+
+    lottery.registrant_count += 1;
+
+    Ok(())
+}
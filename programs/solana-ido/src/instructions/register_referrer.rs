@@ -0,0 +1,39 @@
+use {
+    crate::{instructions::IdoError, state::{IdoCampaign, Referral, REFERRAL_VERSION}},
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct RegisterReferrer<'info> {
+    #[account(mut)]
+    pub referrer: Signer<'info>,
+
+    /// CHECK: This account is used only as a seed to derive the ido_campaign PDA
+    pub ido_campaign_owner: AccountInfo<'info>,
+
+    #[account(
+        seeds = [b"ido_campaign", ido_campaign_owner.key().as_ref()], bump,
+    )]
+    pub ido_campaign: Account<'info, IdoCampaign>,
+
+    #[account(
+        init,
+        payer = referrer,
+        space = 8 + Referral::INIT_SPACE,
+        seeds = [b"referral", ido_campaign.key().as_ref(), referrer.key().as_ref()], bump,
+    )]
+    pub referral: Account<'info, Referral>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn register_referrer(ctx: Context<RegisterReferrer>) -> Result<()> {
+    require!(ctx.accounts.ido_campaign.referral_bps > 0, IdoError::ErrReferralsDisabled);
+
+    let referral = &mut ctx.accounts.referral;
+    referral.version = REFERRAL_VERSION;
+    referral.ido_campaign = ctx.accounts.ido_campaign.key();
+    referral.referrer = ctx.accounts.referrer.key();
+
+    Ok(())
+}
@@ -0,0 +1,66 @@
+use {
+    crate::{
+        instructions::IdoError,
+        state::{CampaignStatus, IdoCampaign},
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::token::{self, spl_token::instruction::AuthorityType, Mint, SetAuthority, Token},
+};
+
+#[derive(Accounts)]
+pub struct ReleaseMintAuthority<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"ido_campaign", owner.key().as_ref()], bump,
+        constraint = ido_campaign.authority == owner.key() @ IdoError::ErrUnauthorized,
+    )]
+    pub ido_campaign: Account<'info, IdoCampaign>,
+
+    #[account(
+        mut,
+        constraint = token_mint.key() == ido_campaign.token_mint @ IdoError::ErrInvalidTokenMint,
+    )]
+    pub token_mint: Account<'info, Mint>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Once nothing is left to claim the mint authority goes back to the authority, or is
+/// revoked for good to fix the supply at what was sold.
+pub fn release_mint_authority(ctx: Context<ReleaseMintAuthority>, revoke: bool) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp as u64;
+    let ido_campaign = &mut ctx.accounts.ido_campaign;
+
+    require!(ido_campaign.mint_on_claim, IdoError::ErrMintOnClaimDisabled);
+    let status = ido_campaign.sync_status(now)?;
+    require!(
+        status == CampaignStatus::Finalized || status.is_closed(),
+        IdoError::ErrInvalidStatusTransition
+    );
+
+    let owner_key = ctx.accounts.owner.key();
+    let bump_bytes = [ctx.bumps.ido_campaign];
+    let seeds: [&[u8]; 3] = [
+        b"ido_campaign",
+        owner_key.as_ref(),
+        &bump_bytes,
+    ];
+    let signer = [&seeds[..]];
+
+    let cpi_context = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        SetAuthority {
+            current_authority: ctx.accounts.ido_campaign.to_account_info(),
+            account_or_mint: ctx.accounts.token_mint.to_account_info(),
+        },
+        &signer,
+    );
+    let new_authority = if revoke { None } else { Some(owner_key) };
+    token::set_authority(cpi_context, AuthorityType::MintTokens, new_authority)?;
+
+    ctx.accounts.ido_campaign.mint_on_claim = false;
+
+    Ok(())
+}
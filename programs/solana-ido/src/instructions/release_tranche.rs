@@ -0,0 +1,102 @@
+use {
+    crate::{
+        instructions::IdoError,
+        state::{IdoCampaign, MilestoneEscrow, TrancheState},
+    },
+    anchor_lang::{prelude::*, system_program::{self, Transfer}},
+};
+
+#[derive(Accounts)]
+pub struct ReleaseTranche<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [b"ido_campaign", owner.key().as_ref()], bump,
+    )]
+    pub ido_campaign: Account<'info, IdoCampaign>,
+
+    #[account(
+        mut,
+        seeds = [b"milestone_escrow", ido_campaign.key().as_ref()], bump,
+    )]
+    pub milestone_escrow: Account<'info, MilestoneEscrow>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_treasury", ido_campaign.key().as_ref()], bump,
+        constraint = sol_treasury.key() == ido_campaign.sol_treasury @ IdoError::ErrInvalidSolTreasury,
+    )]
+    pub sol_treasury: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn release_tranche(ctx: Context<ReleaseTranche>, tranche_index: u8) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp as u64;
+    require!(ctx.accounts.ido_campaign.funds_withdrawn, IdoError::ErrFundsNotWithdrawn);
+
+    let milestone_escrow = &ctx.accounts.milestone_escrow;
+    require!(!milestone_escrow.failed, IdoError::ErrEscrowFailed);
+    require!(
+        matches!(
+            milestone_escrow.tranche(tranche_index)?.state,
+            TrancheState::Locked | TrancheState::Approved
+        ),
+        IdoError::ErrTrancheAlreadySettled
+    );
+    require!(milestone_escrow.is_unlocked(tranche_index, now)?, IdoError::ErrTrancheLocked);
+
+    let amount = mark_tranche_released(&mut ctx.accounts.milestone_escrow, tranche_index)?;
+    pay_from_sol_treasury(
+        &ctx.accounts.sol_treasury,
+        &ctx.accounts.owner.to_account_info(),
+        ctx.accounts.ido_campaign.key(),
+        ctx.bumps.sol_treasury,
+        &ctx.accounts.system_program,
+        amount,
+    )
+}
+
+/// Returns the lamports of the tranche the owner has to be paid.
+pub(crate) fn mark_tranche_released(milestone_escrow: &mut MilestoneEscrow, tranche_index: u8) -> Result<u64> {
+    let amount = milestone_escrow.tranche_amount(tranche_index)?;
+
+    milestone_escrow.tranches[tranche_index as usize].state = TrancheState::Released;
+    milestone_escrow.released_lamports = milestone_escrow.released_lamports
+        .checked_add(amount)
+        .ok_or(IdoError::ErrMathOverflow)?;
+
+    Ok(amount)
+}
+
+pub(crate) fn pay_from_sol_treasury<'info>(
+    sol_treasury: &SystemAccount<'info>,
+    to: &AccountInfo<'info>,
+    ido_campaign_key: Pubkey,
+    sol_treasury_bump: u8,
+    system_program: &Program<'info, System>,
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+
+    let bump_bytes = [sol_treasury_bump];
+    let seeds: [&[u8]; 3] = [
+        b"sol_treasury",
+        ido_campaign_key.as_ref(),
+        &bump_bytes,
+    ];
+    let signer = [&seeds[..]];
+
+    let cpi_context = CpiContext::new_with_signer(
+        system_program.to_account_info(),
+        Transfer {
+            from: sol_treasury.to_account_info(),
+            to: to.clone(),
+        },
+        &signer,
+    );
+    system_program::transfer(cpi_context, amount)
+}
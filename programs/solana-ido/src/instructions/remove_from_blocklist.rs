@@ -0,0 +1,32 @@
+use {
+    crate::{
+        instructions::IdoError,
+        state::{BlocklistEntry, LaunchpadConfig},
+    },
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct RemoveFromBlocklist<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"launchpad_config"], bump,
+        constraint = launchpad_config.admin == admin.key() @ IdoError::ErrUnauthorized,
+    )]
+    pub launchpad_config: Account<'info, LaunchpadConfig>,
+}
+
+/// Remaining accounts are the blocklist entries to close, their rent goes back to the admin.
+/// A legacy transaction the admin pays for fits 30 of them.
+pub fn remove_from_blocklist<'info>(ctx: Context<'_, '_, 'info, 'info, RemoveFromBlocklist<'info>>) -> Result<()> {
+    require!(!ctx.remaining_accounts.is_empty(), IdoError::ErrInvalidAccessListBatch);
+
+    for entry_info in ctx.remaining_accounts {
+        let entry = Account::<BlocklistEntry>::try_from(entry_info)?;
+        entry.close(ctx.accounts.admin.to_account_info())?;
+    }
+
+    Ok(())
+}
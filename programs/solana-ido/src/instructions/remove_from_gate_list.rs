@@ -0,0 +1,35 @@
+use {
+    crate::{
+        instructions::{check_allowlist_editable, IdoError},
+        state::{GateListEntry, IdoCampaign},
+    },
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct RemoveFromGateList<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [b"ido_campaign", owner.key().as_ref()], bump,
+    )]
+    pub ido_campaign: Account<'info, IdoCampaign>,
+}
+
+/// Remaining accounts are the gate list entries to close, their rent goes back to the owner.
+/// A legacy transaction fits 28 of them, 30 when the owner also pays the fee.
+pub fn remove_from_gate_list<'info>(ctx: Context<'_, '_, 'info, 'info, RemoveFromGateList<'info>>) -> Result<()> {
+    let ido_campaign = &ctx.accounts.ido_campaign;
+    check_allowlist_editable(ido_campaign, ctx.accounts.owner.key())?;
+    require!(!ctx.remaining_accounts.is_empty(), IdoError::ErrInvalidAccessListBatch);
+
+    for entry_info in ctx.remaining_accounts {
+        let entry = Account::<GateListEntry>::try_from(entry_info)?;
+        require_keys_eq!(entry.ido_campaign, ido_campaign.key(), IdoError::ErrInvalidAccessListBatch);
+
+        entry.close(ctx.accounts.owner.to_account_info())?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,83 @@
+use {
+    crate::{
+        instructions::IdoError,
+        state::{IdoCampaign, SupplyDeposit},
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::token::{self, Mint, Token, TokenAccount, TransferChecked},
+};
+
+#[derive(Accounts)]
+pub struct ReturnFunderDeposit<'info> {
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = funder_token_account.owner == funder.key() @ IdoError::ErrInvalidOwner,
+    )]
+    pub funder_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: This account is used only as a seed to derive the ido_campaign PDA
+    pub ido_campaign_owner: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"ido_campaign", ido_campaign_owner.key().as_ref()], bump,
+    )]
+    pub ido_campaign: Account<'info, IdoCampaign>,
+
+    #[account(
+        mut,
+        seeds = [b"tokens_treasury", ido_campaign.key().as_ref()], bump,
+        constraint = tokens_treasury.key() == ido_campaign.token_treasury @ IdoError::ErrInvalidIdoCampaign,
+    )]
+    pub tokens_treasury: Account<'info, TokenAccount>,
+
+    #[account(constraint = token_mint.key() == ido_campaign.token_mint @ IdoError::ErrInvalidMintAccount)]
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        close = funder,
+        seeds = [b"supply_deposit", ido_campaign.key().as_ref(), funder.key().as_ref()], bump,
+    )]
+    pub supply_deposit: Account<'info, SupplyDeposit>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Gives a funder its deposit back once the campaign failed or was cancelled.
+pub fn return_funder_deposit(ctx: Context<ReturnFunderDeposit>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp as u64;
+    let ido_campaign = &mut ctx.accounts.ido_campaign;
+
+    require!(ido_campaign.sync_status(now)?.is_closed(), IdoError::ErrSaleNotClosed);
+
+    let amount = ctx.accounts.supply_deposit.amount;
+    ido_campaign.funder_deposits = ido_campaign.funder_deposits
+        .checked_sub(amount)
+        .ok_or(IdoError::ErrMathOverflow)?;
+    ido_campaign.total_deposited = ido_campaign.total_deposited.saturating_sub(amount);
+
+    let owner_key = ctx.accounts.ido_campaign_owner.key();
+    let bump_bytes = [ctx.bumps.ido_campaign];
+    let seeds: [&[u8]; 3] = [
+        b"ido_campaign",
+        owner_key.as_ref(),
+        &bump_bytes,
+    ];
+    let signer = [&seeds[..]];
+
+    let cpi_context = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        TransferChecked {
+            from: ctx.accounts.tokens_treasury.to_account_info(),
+            to: ctx.accounts.funder_token_account.to_account_info(),
+            authority: ctx.accounts.ido_campaign.to_account_info(),
+            mint: ctx.accounts.token_mint.to_account_info(),
+        },
+        &signer,
+    );
+    token::transfer_checked(cpi_context, amount, ctx.accounts.token_mint.decimals)
+}
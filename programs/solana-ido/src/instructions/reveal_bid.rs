@@ -0,0 +1,104 @@
+use {
+    crate::{
+        instructions::IdoError,
+        state::{BidBook, CampaignStatus, IdoCampaign, SaleMode, User, MAX_BID_PRICE_LEVELS},
+    },
+    anchor_lang::{prelude::*, solana_program::hash::hashv},
+};
+
+#[derive(Accounts)]
+pub struct RevealBid<'info> {
+    pub participant: Signer<'info>,
+
+    /// CHECK: This account is used only as a seed to derive the ido_campaign PDA
+    pub ido_campaign_owner: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"ido_campaign", ido_campaign_owner.key().as_ref()], bump,
+    )]
+    pub ido_campaign: Account<'info, IdoCampaign>,
+
+    #[account(
+        mut,
+        seeds = [b"bid_book", ido_campaign.key().as_ref()], bump,
+    )]
+    pub bid_book: Account<'info, BidBook>,
+
+    #[account(
+        mut,
+        seeds = [b"user", ido_campaign.key().as_ref(), participant.key().as_ref()], bump,
+    )]
+    pub user: Account<'info, User>,
+}
+
+pub fn reveal_bid(ctx: Context<RevealBid>, price_lamports: u64, quantity: u64, salt: [u8; 32]) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp as u64;
+    ctx.accounts.ido_campaign.sync_status(now)?;
+
+    let ido_campaign = &ctx.accounts.ido_campaign;
+    let participant = &ctx.accounts.participant;
+    let user = &mut ctx.accounts.user;
+
+    require!(ido_campaign.sale_mode == SaleMode::BatchAuction, IdoError::ErrInvalidSaleMode);
+    require!(!ido_campaign.status.is_closed(), IdoError::ErrSaleAlreadyClosed);
+    require!(
+        ido_campaign.status == CampaignStatus::Ended && now <= ido_campaign.reveal_end_time,
+        IdoError::ErrInvalidRevealPeriod
+    );
+    require!(user.joined_at > 0, IdoError::ErrUserNotJoined);
+    require!(!user.bid_revealed, IdoError::ErrBidAlreadyRevealed);
+
+    let commitment = hashv(&[
+        &price_lamports.to_le_bytes(),
+        &quantity.to_le_bytes(),
+        &salt,
+        participant.key().as_ref(),
+    ]);
+    require!(commitment.to_bytes() == user.bid_commitment, IdoError::ErrInvalidBidCommitment);
+
+    let level = price_level(ido_campaign, price_lamports)?;
+    check_bid(ido_campaign, user, price_lamports, quantity)?;
+
+    user.bid_price_lamports = price_lamports;
+    user.bid_quantity = quantity;
+    user.bid_revealed = true;
+
+    let bid_book = &mut ctx.accounts.bid_book;
+    bid_book.demand[level] = bid_book.demand[level]
+        .checked_add(quantity)
+        .ok_or(IdoError::ErrMathOverflow)?;
+
+    let ido_campaign = &mut ctx.accounts.ido_campaign;
+    ido_campaign.total_committed = ido_campaign.total_committed
+        .checked_add(quantity)
+        .ok_or(IdoError::ErrMathOverflow)?;
+
+    Ok(())
+}
+
+fn price_level(ido_campaign: &IdoCampaign, price_lamports: u64) -> Result<usize> {
+    let above_reserve = price_lamports
+        .checked_sub(ido_campaign.price_lamports)
+        .ok_or(IdoError::ErrInvalidBidPrice)?;
+    require!(above_reserve % ido_campaign.bid_tick_lamports == 0, IdoError::ErrInvalidBidPrice);
+
+    let level = (above_reserve / ido_campaign.bid_tick_lamports) as usize;
+    require!(level < MAX_BID_PRICE_LEVELS, IdoError::ErrInvalidBidPrice);
+
+    Ok(level)
+}
+
+fn check_bid(ido_campaign: &IdoCampaign, user: &User, price_lamports: u64, quantity: u64) -> Result<()> {
+    let max_quantity = ido_campaign.available_allocations_per_participant
+        .checked_mul(ido_campaign.allocation)
+        .ok_or(IdoError::ErrMathOverflow)?;
+    require!(quantity > 0 && quantity <= max_quantity, IdoError::ErrInvalidNumberOfAllocations);
+
+    let bid_cost = price_lamports
+        .checked_mul(quantity)
+        .ok_or(IdoError::ErrMathOverflow)?;
+    require!(user.paid_lamports >= bid_cost, IdoError::ErrInsufficientFunds);
+
+    Ok(())
+}
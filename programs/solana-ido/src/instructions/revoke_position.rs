@@ -0,0 +1,144 @@
+use {
+    crate::{
+        instructions::{unlocked_amount, IdoError},
+        state::{CampaignStatus, IdoCampaign, MilestoneEscrow, User},
+    },
+    anchor_lang::{
+        prelude::*,
+        system_program::{self, Transfer},
+    },
+    anchor_spl::token::{self, Mint, Token, TokenAccount, TransferChecked},
+};
+
+#[derive(Accounts)]
+pub struct RevokePosition<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(mut, constraint = owner_token_account.owner == owner.key() @ IdoError::ErrInvalidOwner)]
+    pub owner_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"ido_campaign", owner.key().as_ref()], bump,
+        constraint = ido_campaign.authority == owner.key() @ IdoError::ErrUnauthorized,
+    )]
+    pub ido_campaign: Account<'info, IdoCampaign>,
+
+    #[account(
+        mut,
+        seeds = [b"tokens_treasury", ido_campaign.key().as_ref()], bump,
+        constraint = tokens_treasury.key() == ido_campaign.token_treasury @ IdoError::ErrInvalidIdoCampaign,
+    )]
+    pub tokens_treasury: Account<'info, TokenAccount>,
+
+    #[account(constraint = token_mint.key() == ido_campaign.token_mint @ IdoError::ErrInvalidTokenMint)]
+    pub token_mint: Account<'info, Mint>,
+
+    /// Gets back what it paid for the revoked tokens.
+    #[account(mut)]
+    pub participant: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"user", ido_campaign.key().as_ref(), participant.key().as_ref()], bump,
+    )]
+    pub user: Account<'info, User>,
+
+    /// Required when the campaign escrows its proceeds, revoked tokens stop sharing in its refunds.
+    #[account(
+        mut,
+        seeds = [b"milestone_escrow", ido_campaign.key().as_ref()], bump,
+    )]
+    pub milestone_escrow: Option<Account<'info, MilestoneEscrow>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Sends the part of the position not vested yet to the authority, the vested part is
+/// claimable by the participant right away. The authority buys the revoked tokens back at
+/// the price the participant paid for them.
+pub fn revoke_position(ctx: Context<RevokePosition>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp as u64;
+    let ido_campaign = &mut ctx.accounts.ido_campaign;
+    let user = &mut ctx.accounts.user;
+
+    require!(
+        ido_campaign.sync_status(now)? == CampaignStatus::Succeeded,
+        IdoError::ErrInvalidStatusTransition
+    );
+    require!(user.revocable, IdoError::ErrPositionNotRevocable);
+    require!(!user.revoked, IdoError::ErrPositionRevoked);
+    user.settle(ido_campaign)?;
+
+    let vested = unlocked_amount(
+        user.amount,
+        ido_campaign.cliff,
+        ido_campaign.vesting_end_time,
+        ido_campaign.available_tokens_after_cliff_ptc,
+        now,
+    )?;
+    let unvested = user.amount
+        .checked_sub(vested)
+        .ok_or(IdoError::ErrMathOverflow)?;
+    // Settled already, so `paid_lamports` is what the position cost without refundable excess.
+    let refund = (user.paid_lamports as u128 * unvested as u128)
+        .checked_div(user.amount as u128)
+        .unwrap_or(0) as u64;
+
+    user.amount = vested;
+    user.paid_lamports -= refund;
+    user.revoked = true;
+    // Revoked tokens leave the treasury like claimed ones, so the campaign still finalizes.
+    ido_campaign.total_claimed = ido_campaign.total_claimed
+        .checked_add(unvested)
+        .ok_or(IdoError::ErrMathOverflow)?;
+    ido_campaign.sync_status(now)?;
+
+    require!(
+        !ido_campaign.milestone_escrow_enabled || ctx.accounts.milestone_escrow.is_some(),
+        IdoError::ErrMilestoneEscrowRequired
+    );
+    if let Some(milestone_escrow) = ctx.accounts.milestone_escrow.as_mut() {
+        milestone_escrow.revoked_amount = milestone_escrow.revoked_amount
+            .checked_add(unvested)
+            .ok_or(IdoError::ErrMathOverflow)?;
+    }
+
+    if refund > 0 {
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.owner.to_account_info(),
+                to: ctx.accounts.participant.to_account_info(),
+            },
+        );
+        system_program::transfer(cpi_context, refund)?;
+    }
+
+    if unvested == 0 || ctx.accounts.ido_campaign.mint_on_claim {
+        return Ok(());
+    }
+
+    let owner_key = ctx.accounts.owner.key();
+    let bump_bytes = [ctx.bumps.ido_campaign];
+    let seeds: [&[u8]; 3] = [
+        b"ido_campaign",
+        owner_key.as_ref(),
+        &bump_bytes,
+    ];
+    let signer = [&seeds[..]];
+
+    let cpi_context = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        TransferChecked {
+            from: ctx.accounts.tokens_treasury.to_account_info(),
+            to: ctx.accounts.owner_token_account.to_account_info(),
+            authority: ctx.accounts.ido_campaign.to_account_info(),
+            mint: ctx.accounts.token_mint.to_account_info(),
+        },
+        &signer,
+    );
+    token::transfer_checked(cpi_context, unvested, ctx.accounts.token_mint.decimals)
+}
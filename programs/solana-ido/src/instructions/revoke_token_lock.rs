@@ -0,0 +1,78 @@
+use {
+    crate::{
+        instructions::{transfer_from_token_lock_vault, unlocked_amount, IdoError},
+        state::{IdoCampaign, TokenLock},
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::token::{Mint, Token, TokenAccount},
+};
+
+#[derive(Accounts)]
+pub struct RevokeTokenLock<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(mut, constraint = owner_token_account.owner == owner.key() @ IdoError::ErrInvalidOwner)]
+    pub owner_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"ido_campaign", owner.key().as_ref()], bump,
+        constraint = ido_campaign.authority == owner.key() @ IdoError::ErrUnauthorized,
+    )]
+    pub ido_campaign: Account<'info, IdoCampaign>,
+
+    /// CHECK: This account is used only as a seed to derive the token_lock PDA
+    pub beneficiary: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"token_lock", ido_campaign.key().as_ref(), beneficiary.key().as_ref()], bump,
+    )]
+    pub token_lock: Account<'info, TokenLock>,
+
+    #[account(
+        mut,
+        seeds = [b"token_lock_vault", token_lock.key().as_ref()], bump,
+    )]
+    pub token_lock_vault: Account<'info, TokenAccount>,
+
+    #[account(constraint = token_mint.key() == ido_campaign.token_mint @ IdoError::ErrInvalidTokenMint)]
+    pub token_mint: Account<'info, Mint>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Returns the unvested part to the authority, what vested so far stays claimable.
+pub fn revoke_token_lock(ctx: Context<RevokeTokenLock>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp as u64;
+    let token_lock = &mut ctx.accounts.token_lock;
+    require!(token_lock.revocable, IdoError::ErrTokenLockNotRevocable);
+    require!(!token_lock.revoked, IdoError::ErrTokenLockRevoked);
+
+    let vested = unlocked_amount(
+        token_lock.amount,
+        token_lock.cliff,
+        token_lock.vesting_end_time,
+        token_lock.available_tokens_after_cliff_ptc,
+        now,
+    )?;
+    let unvested = token_lock.amount
+        .checked_sub(vested)
+        .ok_or(IdoError::ErrMathOverflow)?;
+
+    token_lock.amount = vested;
+    token_lock.revoked = true;
+
+    if unvested == 0 {
+        return Ok(());
+    }
+
+    transfer_from_token_lock_vault(
+        &ctx.accounts.token_lock,
+        ctx.bumps.token_lock,
+        &ctx.accounts.token_lock_vault,
+        &ctx.accounts.owner_token_account,
+        &ctx.accounts.token_mint,
+        &ctx.accounts.token_program,
+        unvested,
+    )
+}
@@ -0,0 +1,54 @@
+use {
+    crate::{
+        instructions::IdoError,
+        state::{CampaignStatus, IdoCampaign, Lottery, LOTTERY_DRAW_DELAY_SLOTS},
+    },
+    anchor_lang::{prelude::*, solana_program::slot_hashes::MAX_ENTRIES},
+};
+
+#[derive(Accounts)]
+pub struct ScheduleLotteryDraw<'info> {
+    /// CHECK: This account is used only as a seed to derive the ido_campaign PDA
+    pub ido_campaign_owner: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"ido_campaign", ido_campaign_owner.key().as_ref()], bump,
+    )]
+    pub ido_campaign: Account<'info, IdoCampaign>,
+
+    #[account(
+        mut,
+        seeds = [b"lottery", ido_campaign.key().as_ref()], bump,
+    )]
+    pub lottery: Account<'info, Lottery>,
+}
+
+/// Picks the future slot whose hash seeds `draw_lottery`. A draw slot that fell out of
+/// the `SlotHashes` window before anyone drew can be scheduled again.
+pub fn schedule_lottery_draw(ctx: Context<ScheduleLotteryDraw>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp as u64;
+    let slot = Clock::get()?.slot;
+    ctx.accounts.ido_campaign.sync_status(now)?;
+
+    let ido_campaign = &ctx.accounts.ido_campaign;
+    require!(!ido_campaign.status.is_closed(), IdoError::ErrSaleAlreadyClosed);
+    require!(ido_campaign.status != CampaignStatus::Created, IdoError::ErrTokenSupplyNotDeposited);
+    // Registration closes at start_sale_time, the registrants are final from then on.
+    require!(ido_campaign.status != CampaignStatus::Funded, IdoError::ErrInvalidSalePeriod);
+
+    let lottery = &mut ctx.accounts.lottery;
+    require!(!lottery.drawn, IdoError::ErrLotteryAlreadyDrawn);
+    require!(
+        lottery.draw_slot == 0 || draw_slot_expired(lottery.draw_slot, slot),
+        IdoError::ErrLotteryDrawScheduled
+    );
+
+    lottery.draw_slot = slot + LOTTERY_DRAW_DELAY_SLOTS;
+
+    Ok(())
+}
+
+pub(crate) fn draw_slot_expired(draw_slot: u64, slot: u64) -> bool {
+    slot >= draw_slot + MAX_ENTRIES as u64
+}
@@ -0,0 +1,41 @@
+use {
+    crate::{instructions::IdoError, state::{CampaignStatus, IdoCampaign, User}},
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct SetPositionRevocable<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"ido_campaign", owner.key().as_ref()], bump,
+        constraint = ido_campaign.authority == owner.key() @ IdoError::ErrUnauthorized,
+    )]
+    pub ido_campaign: Account<'info, IdoCampaign>,
+
+    /// Signs along with the authority, a paid position only becomes revocable with the
+    /// consent of its participant.
+    pub participant: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"user", ido_campaign.key().as_ref(), participant.key().as_ref()], bump,
+    )]
+    pub user: Account<'info, User>,
+}
+
+/// Advisor or OTC positions are flagged while the sale is live, so the terms are
+/// known before the outcome of the sale is.
+pub fn set_position_revocable(ctx: Context<SetPositionRevocable>, revocable: bool) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp as u64;
+    require!(
+        ctx.accounts.ido_campaign.sync_status(now)? == CampaignStatus::Live,
+        IdoError::ErrInvalidSalePeriod
+    );
+    require!(!ctx.accounts.user.revoked, IdoError::ErrPositionRevoked);
+
+    ctx.accounts.user.revocable = revocable;
+
+    Ok(())
+}
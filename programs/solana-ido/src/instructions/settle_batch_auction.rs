@@ -0,0 +1,77 @@
+use {
+    crate::{
+        instructions::IdoError,
+        state::{BidBook, CampaignStatus, IdoCampaign, SaleMode},
+    },
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct SettleBatchAuction<'info> {
+    /// CHECK: This account is used only as a seed to derive the ido_campaign PDA
+    pub ido_campaign_owner: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"ido_campaign", ido_campaign_owner.key().as_ref()], bump,
+    )]
+    pub ido_campaign: Account<'info, IdoCampaign>,
+
+    #[account(
+        seeds = [b"bid_book", ido_campaign.key().as_ref()], bump,
+    )]
+    pub bid_book: Account<'info, BidBook>,
+}
+
+pub fn settle_batch_auction(ctx: Context<SettleBatchAuction>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp as u64;
+    let ido_campaign = &mut ctx.accounts.ido_campaign;
+
+    ido_campaign.sync_status(now)?;
+    require!(ido_campaign.sale_mode == SaleMode::BatchAuction, IdoError::ErrInvalidSaleMode);
+    require!(!ido_campaign.status.is_closed(), IdoError::ErrSaleAlreadyClosed);
+    require!(ido_campaign.status != CampaignStatus::Created, IdoError::ErrTokenSupplyNotDeposited);
+    require!(!ido_campaign.auction_settled, IdoError::ErrAuctionAlreadySettled);
+    require!(now > ido_campaign.reveal_end_time, IdoError::ErrInvalidRevealPeriod);
+
+    find_clearing_price(ido_campaign, &ctx.accounts.bid_book.demand)?;
+    ido_campaign.auction_settled = true;
+
+    ido_campaign.sync_status(now)?;
+
+    Ok(())
+}
+
+/// Fills the book from the highest level down until `hard_cap` is reached, the last level
+/// touched sets the uniform clearing price.
+fn find_clearing_price(ido_campaign: &mut IdoCampaign, demand: &[u64]) -> Result<()> {
+    let mut remaining = ido_campaign.hard_cap;
+    let mut clearing_level = 0;
+    let mut marginal_bid_filled = 0;
+    let mut marginal_bid_demand = 0;
+
+    for (level, &level_demand) in demand.iter().enumerate().rev() {
+        if level_demand == 0 {
+            continue;
+        }
+
+        clearing_level = level;
+        marginal_bid_demand = level_demand;
+        marginal_bid_filled = level_demand.min(remaining);
+        remaining -= marginal_bid_filled;
+
+        if remaining == 0 {
+            break;
+        }
+    }
+
+    ido_campaign.clearing_price_lamports = ido_campaign.bid_tick_lamports
+        .checked_mul(clearing_level as u64)
+        .and_then(|increase| increase.checked_add(ido_campaign.price_lamports))
+        .ok_or(IdoError::ErrMathOverflow)?;
+    ido_campaign.marginal_bid_filled = marginal_bid_filled;
+    ido_campaign.marginal_bid_demand = marginal_bid_demand;
+    ido_campaign.total_sold = ido_campaign.hard_cap - remaining;
+
+    Ok(())
+}
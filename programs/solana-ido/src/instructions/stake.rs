@@ -0,0 +1,77 @@
+use {
+    crate::{
+        instructions::IdoError,
+        state::{LaunchpadConfig, StakeAccount, STAKE_ACCOUNT_VERSION},
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::token::{self, Mint, Token, TokenAccount, TransferChecked},
+};
+
+#[derive(Accounts)]
+pub struct Stake<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [b"launchpad_config"], bump,
+    )]
+    pub launchpad_config: Account<'info, LaunchpadConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"stake_vault"], bump,
+    )]
+    pub stake_vault: Account<'info, TokenAccount>,
+
+    #[account(address = launchpad_config.stake_mint @ IdoError::ErrInvalidStakeMint)]
+    pub stake_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = owner_token_account.owner == owner.key() @ IdoError::ErrInvalidOwner,
+        constraint = owner_token_account.mint == stake_mint.key() @ IdoError::ErrInvalidStakeMint,
+    )]
+    pub owner_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + StakeAccount::INIT_SPACE,
+        seeds = [b"stake", owner.key().as_ref()], bump,
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn stake(ctx: Context<Stake>, amount: u64) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp as u64;
+
+    require!(amount > 0, IdoError::ErrInsufficientStake);
+    require!(ctx.accounts.owner_token_account.amount >= amount, IdoError::ErrInsufficientFunds);
+
+    let cpi_context = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        TransferChecked {
+            from: ctx.accounts.owner_token_account.to_account_info(),
+            to: ctx.accounts.stake_vault.to_account_info(),
+            authority: ctx.accounts.owner.to_account_info(),
+            mint: ctx.accounts.stake_mint.to_account_info(),
+        },
+    );
+    token::transfer_checked(cpi_context, amount, ctx.accounts.stake_mint.decimals)?;
+
+    let stake_account = &mut ctx.accounts.stake_account;
+    stake_account.version = STAKE_ACCOUNT_VERSION;
+    stake_account.owner = ctx.accounts.owner.key();
+    stake_account.amount = stake_account.amount
+        .checked_add(amount)
+        .ok_or(IdoError::ErrMathOverflow)?;
+    // Every deposit restarts the lock, so a tier can't be borrowed for a single sale.
+    stake_account.locked_until = now
+        .checked_add(ctx.accounts.launchpad_config.stake_lock_period)
+        .ok_or(IdoError::ErrMathOverflow)?;
+
+    Ok(())
+}
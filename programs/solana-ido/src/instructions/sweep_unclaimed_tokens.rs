@@ -0,0 +1,89 @@
+use {
+    crate::{
+        instructions::IdoError,
+        state::{CampaignStatus, IdoCampaign, RegistryEntry},
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::token::{self, Mint, Token, TokenAccount, TransferChecked},
+};
+
+#[derive(Accounts)]
+pub struct SweepUnclaimedTokens<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(mut, constraint = owner_token_account.owner == owner.key() @ IdoError::ErrInvalidOwner)]
+    pub owner_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"ido_campaign", owner.key().as_ref()], bump,
+        constraint = ido_campaign.authority == owner.key() @ IdoError::ErrUnauthorized,
+    )]
+    pub ido_campaign: Account<'info, IdoCampaign>,
+
+    #[account(
+        mut,
+        seeds = [b"tokens_treasury", ido_campaign.key().as_ref()], bump,
+        constraint = tokens_treasury.key() == ido_campaign.token_treasury @ IdoError::ErrInvalidIdoCampaign,
+    )]
+    pub tokens_treasury: Account<'info, TokenAccount>,
+
+    #[account(constraint = token_mint.key() == ido_campaign.token_mint @ IdoError::ErrInvalidTokenMint)]
+    pub token_mint: Account<'info, Mint>,
+
+    /// Gets the new status mirrored, missing for campaigns created before the registry.
+    #[account(
+        mut,
+        constraint = registry_entry.ido_campaign == ido_campaign.key() @ IdoError::ErrInvalidIdoCampaign,
+    )]
+    pub registry_entry: Option<Account<'info, RegistryEntry>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Takes the sold tokens nobody claimed before the claim deadline and finalizes the campaign.
+/// Unsold tokens went back with `withdraw_funds` already, so only the unclaimed part is left.
+pub fn sweep_unclaimed_tokens(ctx: Context<SweepUnclaimedTokens>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp as u64;
+    let ido_campaign = &mut ctx.accounts.ido_campaign;
+
+    require!(ido_campaign.claim_deadline_passed(now), IdoError::ErrClaimDeadlineNotReached);
+    require!(
+        ido_campaign.sync_status(now)? == CampaignStatus::Succeeded,
+        IdoError::ErrInvalidStatusTransition
+    );
+    require!(ido_campaign.funds_withdrawn, IdoError::ErrFundsNotWithdrawn);
+
+    let unclaimed = ido_campaign.total_sold
+        .checked_sub(ido_campaign.total_claimed)
+        .ok_or(IdoError::ErrMathOverflow)?;
+    ido_campaign.transition(CampaignStatus::Finalized)?;
+    if let Some(registry_entry) = ctx.accounts.registry_entry.as_mut() {
+        registry_entry.record_status(ido_campaign);
+    }
+
+    if unclaimed == 0 || ido_campaign.mint_on_claim {
+        return Ok(());
+    }
+
+    let owner_key = ctx.accounts.owner.key();
+    let bump_bytes = [ctx.bumps.ido_campaign];
+    let seeds: [&[u8]; 3] = [
+        b"ido_campaign",
+        owner_key.as_ref(),
+        &bump_bytes,
+    ];
+    let signer = [&seeds[..]];
+
+    let cpi_context = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        TransferChecked {
+            from: ctx.accounts.tokens_treasury.to_account_info(),
+            to: ctx.accounts.owner_token_account.to_account_info(),
+            authority: ctx.accounts.ido_campaign.to_account_info(),
+            mint: ctx.accounts.token_mint.to_account_info(),
+        },
+        &signer,
+    );
+    token::transfer_checked(cpi_context, unclaimed, ctx.accounts.token_mint.decimals)
+}
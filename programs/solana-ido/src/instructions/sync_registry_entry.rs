@@ -0,0 +1,35 @@
+use {
+    crate::{
+        instructions::IdoError,
+        state::{IdoCampaign, RegistryEntry},
+    },
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct SyncRegistryEntry<'info> {
+    /// CHECK: This account is used only as a seed to derive the ido_campaign PDA
+    pub ido_campaign_owner: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"ido_campaign", ido_campaign_owner.key().as_ref()], bump,
+    )]
+    pub ido_campaign: Account<'info, IdoCampaign>,
+
+    #[account(
+        mut,
+        constraint = registry_entry.ido_campaign == ido_campaign.key() @ IdoError::ErrInvalidIdoCampaign,
+    )]
+    pub registry_entry: Account<'info, RegistryEntry>,
+}
+
+/// Anyone can bring the registry entry up to date, for the status changes made by
+/// the clock or by instructions that don't take the entry.
+pub fn sync_registry_entry(ctx: Context<SyncRegistryEntry>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp as u64;
+    ctx.accounts.ido_campaign.sync_status(now)?;
+    ctx.accounts.registry_entry.record_status(&ctx.accounts.ido_campaign);
+
+    Ok(())
+}
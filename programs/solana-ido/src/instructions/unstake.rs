@@ -0,0 +1,75 @@
+use {
+    crate::{
+        instructions::IdoError,
+        state::{LaunchpadConfig, StakeAccount},
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::token::{self, Mint, Token, TokenAccount, TransferChecked},
+};
+
+#[derive(Accounts)]
+pub struct Unstake<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [b"launchpad_config"], bump,
+    )]
+    pub launchpad_config: Account<'info, LaunchpadConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"stake_vault"], bump,
+    )]
+    pub stake_vault: Account<'info, TokenAccount>,
+
+    #[account(address = launchpad_config.stake_mint @ IdoError::ErrInvalidStakeMint)]
+    pub stake_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = owner_token_account.owner == owner.key() @ IdoError::ErrInvalidOwner,
+        constraint = owner_token_account.mint == stake_mint.key() @ IdoError::ErrInvalidStakeMint,
+    )]
+    pub owner_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"stake", owner.key().as_ref()], bump,
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn unstake(ctx: Context<Unstake>, amount: u64) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp as u64;
+    let stake_account = &mut ctx.accounts.stake_account;
+
+    require!(now >= stake_account.locked_until, IdoError::ErrStakeLocked);
+    require!(amount > 0 && amount <= stake_account.amount, IdoError::ErrInsufficientStake);
+
+    stake_account.amount -= amount;
+
+    let bump = ctx.bumps.launchpad_config;
+    let bump_bytes = [bump];
+    let seeds: [&[u8]; 2] = [
+        b"launchpad_config",
+        &bump_bytes,
+    ];
+    let signer = [&seeds[..]];
+
+    let cpi_context = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        TransferChecked {
+            from: ctx.accounts.stake_vault.to_account_info(),
+            to: ctx.accounts.owner_token_account.to_account_info(),
+            authority: ctx.accounts.launchpad_config.to_account_info(),
+            mint: ctx.accounts.stake_mint.to_account_info(),
+        },
+        &signer,
+    );
+    token::transfer_checked(cpi_context, amount, ctx.accounts.stake_mint.decimals)?;
+
+    Ok(())
+}
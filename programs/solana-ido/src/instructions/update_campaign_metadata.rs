@@ -0,0 +1,42 @@
+use {
+    crate::{
+        instructions::{check_metadata_editable, IdoError},
+        state::{CampaignMetadata, IdoCampaign},
+    },
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct UpdateCampaignMetadata<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"ido_campaign", owner.key().as_ref()], bump,
+        constraint = ido_campaign.authority == owner.key() @ IdoError::ErrUnauthorized,
+    )]
+    pub ido_campaign: Account<'info, IdoCampaign>,
+
+    #[account(
+        mut,
+        seeds = [b"campaign_metadata", ido_campaign.key().as_ref()], bump,
+        constraint = campaign_metadata.ido_campaign == ido_campaign.key() @ IdoError::ErrInvalidIdoCampaign,
+    )]
+    pub campaign_metadata: Account<'info, CampaignMetadata>,
+}
+
+pub fn update_campaign_metadata(
+    ctx: Context<UpdateCampaignMetadata>,
+    name: String,
+    symbol: String,
+    uri: String,
+    description: String,
+    social_links: Vec<String>,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp as u64;
+    check_metadata_editable(&mut ctx.accounts.ido_campaign, now)?;
+
+    ctx.accounts.campaign_metadata.set(name, symbol, uri, description, social_links, now)?;
+
+    Ok(())
+}
@@ -0,0 +1,29 @@
+use {
+    crate::{instructions::IdoError, state::LaunchpadConfig},
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct UpdateLaunchpadConfig<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"launchpad_config"], bump,
+        constraint = launchpad_config.admin == admin.key() @ IdoError::ErrUnauthorized,
+    )]
+    pub launchpad_config: Account<'info, LaunchpadConfig>,
+}
+
+/// Existing stakes keep their `locked_until`, the new period applies from the next deposit.
+pub fn update_launchpad_config(
+    ctx: Context<UpdateLaunchpadConfig>,
+    new_admin: Pubkey,
+    stake_lock_period: u64,
+) -> Result<()> {
+    let launchpad_config = &mut ctx.accounts.launchpad_config;
+    launchpad_config.admin = new_admin;
+    launchpad_config.stake_lock_period = stake_lock_period;
+
+    Ok(())
+}
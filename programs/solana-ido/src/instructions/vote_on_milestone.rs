@@ -0,0 +1,80 @@
+use {
+    crate::{
+        instructions::IdoError,
+        state::{IdoCampaign, MilestoneProposal, MilestoneVote, User, MILESTONE_ESCROW_VERSION},
+    },
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct VoteOnMilestone<'info> {
+    #[account(mut)]
+    pub participant: Signer<'info>,
+
+    /// CHECK: This account is used only as a seed to derive the ido_campaign PDA
+    pub ido_campaign_owner: AccountInfo<'info>,
+
+    /// Writable since settling the vote weight advances the pro-rata fills.
+    #[account(
+        mut,
+        seeds = [b"ido_campaign", ido_campaign_owner.key().as_ref()], bump,
+    )]
+    pub ido_campaign: Account<'info, IdoCampaign>,
+
+    #[account(
+        mut,
+        seeds = [b"user", ido_campaign.key().as_ref(), participant.key().as_ref()], bump,
+    )]
+    pub user: Account<'info, User>,
+
+    #[account(
+        mut,
+        constraint = milestone_proposal.ido_campaign == ido_campaign.key() @ IdoError::ErrInvalidIdoCampaign,
+    )]
+    pub milestone_proposal: Account<'info, MilestoneProposal>,
+
+    #[account(
+        init,
+        payer = participant,
+        space = 8 + MilestoneVote::INIT_SPACE,
+        seeds = [b"milestone_vote", milestone_proposal.key().as_ref(), participant.key().as_ref()], bump,
+    )]
+    pub milestone_vote: Account<'info, MilestoneVote>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn vote_on_milestone(ctx: Context<VoteOnMilestone>, approve: bool) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp as u64;
+
+    let milestone_proposal = &mut ctx.accounts.milestone_proposal;
+    require!(
+        !milestone_proposal.executed && now <= milestone_proposal.voting_end,
+        IdoError::ErrVotingClosed
+    );
+
+    // Weighted by the tokens bought, final once the purchase is settled.
+    let user = &mut ctx.accounts.user;
+    user.settle(&mut ctx.accounts.ido_campaign)?;
+    let weight = user.amount;
+    require!(weight > 0, IdoError::ErrNoVotingWeight);
+
+    if approve {
+        milestone_proposal.approve_weight = milestone_proposal.approve_weight
+            .checked_add(weight)
+            .ok_or(IdoError::ErrMathOverflow)?;
+    } else {
+        milestone_proposal.reject_weight = milestone_proposal.reject_weight
+            .checked_add(weight)
+            .ok_or(IdoError::ErrMathOverflow)?;
+    }
+
+    let milestone_vote = &mut ctx.accounts.milestone_vote;
+    milestone_vote.version = MILESTONE_ESCROW_VERSION;
+    milestone_vote.proposal = milestone_proposal.key();
+    milestone_vote.participant = ctx.accounts.participant.key();
+    milestone_vote.approve = approve;
+    milestone_vote.weight = weight;
+
+    Ok(())
+}
@@ -1,8 +1,8 @@
 use {
-    crate::{instructions::IdoError, state::IdoCampaign}, 
+    crate::{instructions::IdoError, state::{CampaignStatus, IdoCampaign, MilestoneEscrow, RegistryEntry, SaleMode}}, 
     anchor_lang::{prelude::*, system_program::{self, Transfer}},
     anchor_spl::{
-        token::{self, Mint, Token, TokenAccount, TransferChecked},
+        token::{self, Burn, Mint, Token, TokenAccount, TransferChecked},
     },
 };
 
@@ -43,13 +43,32 @@ pub struct WithdrawFunds<'info> {
     )]
     pub sol_treasury: SystemAccount<'info>,
 
+    /// Writable so unsold tokens can be burned.
+    #[account(mut)]
     pub token_mint: Account<'info, Mint>,
+
+    /// Required when the campaign escrows its proceeds.
+    #[account(
+        mut,
+        constraint = milestone_escrow.ido_campaign == ido_campaign.key() @ IdoError::ErrInvalidIdoCampaign,
+    )]
+    pub milestone_escrow: Option<Account<'info, MilestoneEscrow>>,
+
+    /// Gets the new status mirrored, missing for campaigns created before the registry.
+    #[account(
+        mut,
+        constraint = registry_entry.ido_campaign == ido_campaign.key() @ IdoError::ErrInvalidIdoCampaign,
+    )]
+    pub registry_entry: Option<Account<'info, RegistryEntry>>,
     
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
 }
 
 pub fn withdraw_funds(ctx: Context<WithdrawFunds>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp as u64;
+    ctx.accounts.ido_campaign.sync_status(now)?;
+
     check_ido_campaign(&ctx.accounts.ido_campaign, ctx.accounts.owner.key())?;
 
     check_withdraw_token_accounts(
@@ -58,20 +77,57 @@ pub fn withdraw_funds(ctx: Context<WithdrawFunds>) -> Result<()> {
         ctx.accounts.owner_token_account.mint,
     )?;
     require!(ctx.accounts.token_mint.decimals == 0, IdoError::ErrInvalidTokenDecimals);
+    // Unclaimed airdrops only go back after the claim deadline, through reclaim_airdrop.
+    require!(
+        ctx.accounts.ido_campaign.sale_mode != SaleMode::Airdrop,
+        IdoError::ErrInvalidSaleMode
+    );
 
-    withdraw_all_sol_to_owners(&ctx)?;
+    require!(
+        !ctx.accounts.ido_campaign.milestone_escrow_enabled || ctx.accounts.milestone_escrow.is_some(),
+        IdoError::ErrMilestoneEscrowRequired
+    );
+    let escrowed_lamports = withdraw_all_sol_to_owners(&ctx)?;
+    if let Some(milestone_escrow) = ctx.accounts.milestone_escrow.as_mut() {
+        milestone_escrow.escrowed_lamports = escrowed_lamports;
+    }
 
-    withdraw_unsold_tokens_to_owner(&ctx)?;
+    let unsold_tokens_burned = withdraw_unsold_tokens_to_owner(&ctx)?;
     
+    ctx.accounts.ido_campaign.unsold_tokens_burned = unsold_tokens_burned;
     ctx.accounts.ido_campaign.funds_withdrawn = true;
+    ctx.accounts.ido_campaign.sync_status(now)?;
+    if let Some(registry_entry) = ctx.accounts.registry_entry.as_mut() {
+        registry_entry.record_status(&ctx.accounts.ido_campaign);
+    }
 
     return Ok(());
 }
 
-fn withdraw_all_sol_to_owners(ctx: &Context<WithdrawFunds>) -> Result<()> {
-    let amount = ctx.accounts.sol_treasury.lamports();
+/// Returns the owner share kept in the treasury when the campaign escrows its proceeds.
+fn withdraw_all_sol_to_owners(ctx: &Context<WithdrawFunds>) -> Result<u64> {
+    let ido_campaign = &ctx.accounts.ido_campaign;
+
+    let amount = match ido_campaign.sale_mode {
+        // The unfilled part of every commitment stays in the treasury for refund_oversubscription.
+        SaleMode::ProRata => ido_campaign.total_sold
+            .checked_mul(ido_campaign.price_lamports)
+            .ok_or(IdoError::ErrMathOverflow)?,
+        // Whatever was paid above the clearing price stays in the treasury for
+        // refund_price_difference and refund_excess_deposit.
+        _ if ido_campaign.sells_at_clearing_price() => ido_campaign.total_sold
+            .checked_mul(ido_campaign.clearing_price_lamports)
+            .ok_or(IdoError::ErrMathOverflow)?,
+        // The escrow keeps the rent reserve funded by configure_milestone_escrow in the treasury.
+        _ if ido_campaign.milestone_escrow_enabled => ctx.accounts.sol_treasury
+            .lamports()
+            .saturating_sub(Rent::get()?.minimum_balance(0)),
+        _ => ctx.accounts.sol_treasury.lamports(),
+    };
+    // Referral rewards not claimed yet stay in the treasury for claim_referral_reward.
+    let amount = amount.saturating_sub(ido_campaign.referral_rewards_lamports);
     if amount == 0 {
-        return Ok(());
+        return Ok(0);
     }
 
     let amount_to_launchpad_owner = amount / 100 * 5;
@@ -87,15 +143,20 @@ fn withdraw_all_sol_to_owners(ctx: &Context<WithdrawFunds>) -> Result<()> {
     ];
     let signer = [&seeds[..]];
 
-    let cpi_context = CpiContext::new_with_signer(
-        ctx.accounts.system_program.to_account_info(),
-        Transfer {
-            from: ctx.accounts.sol_treasury.to_account_info(),
-            to: ctx.accounts.owner.to_account_info(),
-        },
-        &signer,
-    );
-    system_program::transfer(cpi_context, amount_to_owner)?;
+    let escrowed_lamports = if ido_campaign.milestone_escrow_enabled {
+        amount_to_owner
+    } else {
+        let cpi_context = CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.sol_treasury.to_account_info(),
+                to: ctx.accounts.owner.to_account_info(),
+            },
+            &signer,
+        );
+        system_program::transfer(cpi_context, amount_to_owner)?;
+        0
+    };
 
     let cpi_context = CpiContext::new_with_signer(
         ctx.accounts.system_program.to_account_info(),
@@ -107,18 +168,20 @@ fn withdraw_all_sol_to_owners(ctx: &Context<WithdrawFunds>) -> Result<()> {
     );
     system_program::transfer(cpi_context, amount_to_launchpad_owner)?;
     
-    Ok(())
+    Ok(escrowed_lamports)
 }
 
-fn withdraw_unsold_tokens_to_owner(ctx: &Context<WithdrawFunds>) -> Result<()> {
+/// Returns how many unsold tokens were burned instead, when the campaign promised to burn them.
+fn withdraw_unsold_tokens_to_owner(ctx: &Context<WithdrawFunds>) -> Result<u64> {
     let ido_campaign = &ctx.accounts.ido_campaign;
 
     let unsold_tokens = ido_campaign
         .hard_cap
         .checked_sub(ido_campaign.total_sold)
         .ok_or(IdoError::ErrMathOverflow)?;
-    if unsold_tokens == 0 {
-        return Ok(());
+    // Unsold tokens of mint-on-claim campaigns were never minted.
+    if unsold_tokens == 0 || ido_campaign.mint_on_claim {
+        return Ok(0);
     }
 
     require!(
@@ -136,6 +199,21 @@ fn withdraw_unsold_tokens_to_owner(ctx: &Context<WithdrawFunds>) -> Result<()> {
     ];
     let signer = [&seeds[..]];
 
+    if ido_campaign.burn_unsold_tokens {
+        let cpi_context = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.token_mint.to_account_info(),
+                from: ctx.accounts.tokens_treasury.to_account_info(),
+                authority: ctx.accounts.ido_campaign.to_account_info(),
+            },
+            &signer,
+        );
+        token::burn(cpi_context, unsold_tokens)?;
+
+        return Ok(unsold_tokens);
+    }
+
     let cpi_context = CpiContext::new_with_signer(
         ctx.accounts.token_program.to_account_info(),
         TransferChecked {
@@ -148,7 +226,7 @@ fn withdraw_unsold_tokens_to_owner(ctx: &Context<WithdrawFunds>) -> Result<()> {
     );
     token::transfer_checked(cpi_context, unsold_tokens, ctx.accounts.token_mint.decimals)?;
 
-    Ok(())
+    Ok(0)
 }
 
 fn check_withdraw_token_accounts(
@@ -168,13 +246,14 @@ fn check_withdraw_token_accounts(
 }
 
 fn check_ido_campaign(ido_campaign: &IdoCampaign, owner: Pubkey) -> Result<()> {
-    let now = Clock::get()?.unix_timestamp as u64;
-
     require!(!ido_campaign.funds_withdrawn, IdoError::ErrFundsAlreadyWithdrawn);
     require!(ido_campaign.authority == owner, IdoError::ErrUnauthorized);
-    require!(now >= ido_campaign.end_sale_time, IdoError::ErrInvalidEndSaleTime);
-    require!(ido_campaign.token_supply_deposited, IdoError::ErrTokenSupplyNotDeposited);
-    require!(ido_campaign.total_sold >= ido_campaign.soft_cap, IdoError::ErrSoftCapNotReached);
 
-    Ok(())
+    match ido_campaign.status {
+        CampaignStatus::Succeeded => Ok(()),
+        CampaignStatus::Created => err!(IdoError::ErrTokenSupplyNotDeposited),
+        CampaignStatus::Funded | CampaignStatus::Live => err!(IdoError::ErrInvalidEndSaleTime),
+        CampaignStatus::Cancelled => err!(IdoError::ErrSaleAlreadyClosed),
+        _ => err!(IdoError::ErrSoftCapNotReached),
+    }
 }
\ No newline at end of file
@@ -1,5 +1,5 @@
 use {
-    crate::{instructions::IdoError, state::IdoCampaign}, 
+    crate::{instructions::IdoError, state::{CampaignStatus, IdoCampaign}}, 
     anchor_lang::prelude::*,
     anchor_spl::{
         token::{self, Mint, Token, TokenAccount, TransferChecked},
@@ -45,12 +45,16 @@ pub struct WithdrawTokensToOwnerIfSoftCapNotReached<'info> {
 pub fn withdraw_tokens_to_owner_if_soft_cap_not_reached(ctx: Context<WithdrawTokensToOwnerIfSoftCapNotReached>) -> Result<()> {
     let ido_campaign = &mut ctx.accounts.ido_campaign;
 
-    require!(ido_campaign.sale_closed, IdoError::ErrSaleNotClosed);
-    require!(!ido_campaign.funds_withdrawn, IdoError::ErrFundsAlreadyWithdrawn);
+    require!(ido_campaign.status.is_closed(), IdoError::ErrSaleNotClosed);
+    require!(
+        !ido_campaign.funds_withdrawn && ido_campaign.status != CampaignStatus::Cancelled,
+        IdoError::ErrFundsAlreadyWithdrawn
+    );
     require!(ido_campaign.total_sold < ido_campaign.soft_cap, IdoError::ErrSoftCapReached);
-    require!(ido_campaign.token_supply_deposited, IdoError::ErrTokenSupplyNotDeposited);
     require!(ctx.accounts.token_mint.decimals == 0, IdoError::ErrInvalidTokenDecimals);
-    require!(ctx.accounts.tokens_treasury.amount > 0, IdoError::ErrNothingToWithdraw);
+    // Third-party funders take their deposits back with return_funder_deposit.
+    let owner_tokens = ctx.accounts.tokens_treasury.amount.saturating_sub(ido_campaign.funder_deposits);
+    require!(owner_tokens > 0, IdoError::ErrNothingToWithdraw);
 
     let owner_key = ctx.accounts.owner.key();
     let bump = ctx.bumps.ido_campaign;
@@ -72,7 +76,7 @@ pub fn withdraw_tokens_to_owner_if_soft_cap_not_reached(ctx: Context<WithdrawTok
         },
         &signer,
     );
-    token::transfer_checked(cpi_context, ctx.accounts.tokens_treasury.amount, ctx.accounts.token_mint.decimals)?;
+    token::transfer_checked(cpi_context, owner_tokens, ctx.accounts.token_mint.decimals)?;
 
     ido_campaign.funds_withdrawn = true;
 
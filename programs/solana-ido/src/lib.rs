@@ -7,6 +7,7 @@ pub mod state;
 
 use {
     instructions::*,
+    state::{AllowlistAddition, BondingCurve, CampaignMetadataArgs, PriceDecay, ReferralPayout, StakeTier, TrancheTerms},
 };
 
 declare_id!("DLThX59oXgmtrkGJbyjTjkwC4qBp4H36QfGJvfFCsiue");
@@ -15,6 +16,10 @@ declare_id!("DLThX59oXgmtrkGJbyjTjkwC4qBp4H36QfGJvfFCsiue");
 pub mod solana_ido {
     use super::*;
 
+    pub fn initialize_campaign_registry(ctx: Context<InitializeCampaignRegistry>) -> Result<()> {
+        crate::instructions::initialize_campaign_registry::initialize_campaign_registry(ctx)
+    }
+
     pub fn initialize_sale(ctx: Context<CreateIdoCampaign>,     
         start_time: u64, 
         end_time: u64, 
@@ -26,6 +31,7 @@ pub mod solana_ido {
         hard_cap: u64,
         available_tokens_after_cliff_ptc: i32,
         available_allocations_per_participant: u64,
+        metadata: CampaignMetadataArgs,
     ) -> Result<()> {
         crate::instructions::create_ido_campaign::initialize_sale(
             ctx,
@@ -39,6 +45,7 @@ pub mod solana_ido {
             hard_cap,
             available_tokens_after_cliff_ptc,
             available_allocations_per_participant,
+            metadata,
         )
     }
     pub fn deposit_tokens_to_sale(ctx: Context<DepositTokensToSale>) -> Result<()> {
@@ -76,4 +83,404 @@ pub mod solana_ido {
     ) -> Result<()> {
         crate::instructions::withdraw_tokens_to_owner_if_soft_cap_not_reached::withdraw_tokens_to_owner_if_soft_cap_not_reached(ctx)
     }
+
+    pub fn get_claimable(ctx: Context<GetClaimable>) -> Result<ClaimableAmount> {
+        crate::instructions::get_claimable::get_claimable(ctx)
+    }
+
+    pub fn quote_purchase(ctx: Context<QuotePurchase>, number_of_allocations: u64) -> Result<PurchaseQuote> {
+        crate::instructions::quote_purchase::quote_purchase(ctx, number_of_allocations)
+    }
+
+    pub fn get_campaign_status(ctx: Context<GetCampaignStatus>) -> Result<CampaignStatusView> {
+        crate::instructions::get_campaign_status::get_campaign_status(ctx)
+    }
+
+    pub fn migrate_campaign(ctx: Context<MigrateCampaign>) -> Result<()> {
+        crate::instructions::migrate_campaign::migrate_campaign(ctx)
+    }
+
+    pub fn migrate_user(ctx: Context<MigrateUser>) -> Result<()> {
+        crate::instructions::migrate_user::migrate_user(ctx)
+    }
+
+    pub fn initialize_campaign_metadata(
+        ctx: Context<InitializeCampaignMetadata>,
+        name: String,
+        symbol: String,
+        uri: String,
+        description: String,
+        social_links: Vec<String>,
+    ) -> Result<()> {
+        crate::instructions::initialize_campaign_metadata::initialize_campaign_metadata(
+            ctx,
+            name,
+            symbol,
+            uri,
+            description,
+            social_links,
+        )
+    }
+
+    pub fn update_campaign_metadata(
+        ctx: Context<UpdateCampaignMetadata>,
+        name: String,
+        symbol: String,
+        uri: String,
+        description: String,
+        social_links: Vec<String>,
+    ) -> Result<()> {
+        crate::instructions::update_campaign_metadata::update_campaign_metadata(
+            ctx,
+            name,
+            symbol,
+            uri,
+            description,
+            social_links,
+        )
+    }
+
+    pub fn configure_pro_rata_sale(ctx: Context<ConfigureProRataSale>) -> Result<()> {
+        crate::instructions::configure_pro_rata_sale::configure_pro_rata_sale(ctx)
+    }
+
+    pub fn refund_oversubscription(ctx: Context<RefundOversubscription>) -> Result<()> {
+        crate::instructions::refund_oversubscription::refund_oversubscription(ctx)
+    }
+
+    pub fn configure_dutch_auction(
+        ctx: Context<ConfigureDutchAuction>,
+        floor_price_lamports: u64,
+        price_decay: PriceDecay,
+        settle_at_clearing_price: bool,
+    ) -> Result<()> {
+        crate::instructions::configure_dutch_auction::configure_dutch_auction(
+            ctx,
+            floor_price_lamports,
+            price_decay,
+            settle_at_clearing_price,
+        )
+    }
+
+    pub fn refund_price_difference(ctx: Context<RefundPriceDifference>) -> Result<()> {
+        crate::instructions::refund_price_difference::refund_price_difference(ctx)
+    }
+
+    pub fn configure_bonding_curve(ctx: Context<ConfigureBondingCurve>, bonding_curve: BondingCurve) -> Result<()> {
+        crate::instructions::configure_bonding_curve::configure_bonding_curve(ctx, bonding_curve)
+    }
+
+    pub fn configure_batch_auction(
+        ctx: Context<ConfigureBatchAuction>,
+        bid_tick_lamports: u64,
+        reveal_end_time: u64,
+    ) -> Result<()> {
+        crate::instructions::configure_batch_auction::configure_batch_auction(ctx, bid_tick_lamports, reveal_end_time)
+    }
+
+    pub fn commit_bid(ctx: Context<CommitBid>, bid_commitment: [u8; 32], deposit_lamports: u64) -> Result<()> {
+        crate::instructions::commit_bid::commit_bid(ctx, bid_commitment, deposit_lamports)
+    }
+
+    pub fn reveal_bid(ctx: Context<RevealBid>, price_lamports: u64, quantity: u64, salt: [u8; 32]) -> Result<()> {
+        crate::instructions::reveal_bid::reveal_bid(ctx, price_lamports, quantity, salt)
+    }
+
+    pub fn settle_batch_auction(ctx: Context<SettleBatchAuction>) -> Result<()> {
+        crate::instructions::settle_batch_auction::settle_batch_auction(ctx)
+    }
+
+    pub fn refund_excess_deposit(ctx: Context<RefundExcessDeposit>) -> Result<()> {
+        crate::instructions::refund_excess_deposit::refund_excess_deposit(ctx)
+    }
+
+    pub fn initialize_launchpad_config(ctx: Context<InitializeLaunchpadConfig>, stake_lock_period: u64) -> Result<()> {
+        crate::instructions::initialize_launchpad_config::initialize_launchpad_config(ctx, stake_lock_period)
+    }
+
+    pub fn update_launchpad_config(
+        ctx: Context<UpdateLaunchpadConfig>,
+        new_admin: Pubkey,
+        stake_lock_period: u64,
+    ) -> Result<()> {
+        crate::instructions::update_launchpad_config::update_launchpad_config(ctx, new_admin, stake_lock_period)
+    }
+
+    pub fn stake(ctx: Context<Stake>, amount: u64) -> Result<()> {
+        crate::instructions::stake::stake(ctx, amount)
+    }
+
+    pub fn unstake(ctx: Context<Unstake>, amount: u64) -> Result<()> {
+        crate::instructions::unstake::unstake(ctx, amount)
+    }
+
+    pub fn configure_stake_tiers(ctx: Context<ConfigureStakeTiers>, tiers: Vec<StakeTier>) -> Result<()> {
+        crate::instructions::configure_stake_tiers::configure_stake_tiers(ctx, tiers)
+    }
+
+    pub fn configure_lottery(
+        ctx: Context<ConfigureLottery>,
+        registration_deposit_lamports: u64,
+        winners_count: u64,
+        guaranteed_window_end: u64,
+    ) -> Result<()> {
+        crate::instructions::configure_lottery::configure_lottery(
+            ctx,
+            registration_deposit_lamports,
+            winners_count,
+            guaranteed_window_end,
+        )
+    }
+
+    pub fn register_for_lottery(ctx: Context<RegisterForLottery>) -> Result<()> {
+        crate::instructions::register_for_lottery::register_for_lottery(ctx)
+    }
+
+    pub fn draw_lottery(ctx: Context<DrawLottery>) -> Result<()> {
+        crate::instructions::draw_lottery::draw_lottery(ctx)
+    }
+
+    pub fn close_lottery_ticket(ctx: Context<CloseLotteryTicket>) -> Result<()> {
+        crate::instructions::close_lottery_ticket::close_lottery_ticket(ctx)
+    }
+
+    pub fn configure_kyc(ctx: Context<ConfigureKyc>, kyc_signer: Pubkey) -> Result<()> {
+        crate::instructions::configure_kyc::configure_kyc(ctx, kyc_signer)
+    }
+
+    pub fn configure_anti_bot(
+        ctx: Context<ConfigureAntiBot>,
+        reject_foreign_instructions: bool,
+        max_joins_per_slot: u64,
+        ramp_up_duration: u64,
+        ramp_up_start_allocations: u64,
+    ) -> Result<()> {
+        crate::instructions::configure_anti_bot::configure_anti_bot(
+            ctx,
+            reject_foreign_instructions,
+            max_joins_per_slot,
+            ramp_up_duration,
+            ramp_up_start_allocations,
+        )
+    }
+
+    pub fn configure_allowlist(ctx: Context<ConfigureAllowlist>, enabled: bool) -> Result<()> {
+        crate::instructions::configure_allowlist::configure_allowlist(ctx, enabled)
+    }
+
+    pub fn add_to_allowlist<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AddToAllowlist<'info>>,
+        additions: Vec<AllowlistAddition>,
+    ) -> Result<()> {
+        crate::instructions::add_to_allowlist::add_to_allowlist(ctx, additions)
+    }
+
+    pub fn remove_from_allowlist<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RemoveFromAllowlist<'info>>,
+    ) -> Result<()> {
+        crate::instructions::remove_from_allowlist::remove_from_allowlist(ctx)
+    }
+
+    pub fn add_to_blocklist<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AddToBlocklist<'info>>,
+        wallets: Vec<Pubkey>,
+    ) -> Result<()> {
+        crate::instructions::add_to_blocklist::add_to_blocklist(ctx, wallets)
+    }
+
+    pub fn remove_from_blocklist<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RemoveFromBlocklist<'info>>,
+    ) -> Result<()> {
+        crate::instructions::remove_from_blocklist::remove_from_blocklist(ctx)
+    }
+
+    pub fn configure_token_gate(
+        ctx: Context<ConfigureTokenGate>,
+        gate_mint: Pubkey,
+        gate_mint_list: bool,
+        gate_min_balance: u64,
+        gate_tokens_per_allocation: u64,
+    ) -> Result<()> {
+        crate::instructions::configure_token_gate::configure_token_gate(
+            ctx,
+            gate_mint,
+            gate_mint_list,
+            gate_min_balance,
+            gate_tokens_per_allocation,
+        )
+    }
+
+    pub fn configure_milestone_escrow(
+        ctx: Context<ConfigureMilestoneEscrow>,
+        tranches: Vec<TrancheTerms>,
+    ) -> Result<()> {
+        crate::instructions::configure_milestone_escrow::configure_milestone_escrow(ctx, tranches)
+    }
+
+    pub fn approve_milestone(ctx: Context<ApproveMilestone>, tranche_index: u8) -> Result<()> {
+        crate::instructions::approve_milestone::approve_milestone(ctx, tranche_index)
+    }
+
+    pub fn release_tranche(ctx: Context<ReleaseTranche>, tranche_index: u8) -> Result<()> {
+        crate::instructions::release_tranche::release_tranche(ctx, tranche_index)
+    }
+
+    pub fn mark_escrow_failed(ctx: Context<MarkEscrowFailed>) -> Result<()> {
+        crate::instructions::mark_escrow_failed::mark_escrow_failed(ctx)
+    }
+
+    pub fn refund_escrow(ctx: Context<RefundEscrow>) -> Result<()> {
+        crate::instructions::refund_escrow::refund_escrow(ctx)
+    }
+
+    pub fn configure_milestone_voting(
+        ctx: Context<ConfigureMilestoneVoting>,
+        quorum_bps: u16,
+        approval_threshold_bps: u16,
+    ) -> Result<()> {
+        crate::instructions::configure_milestone_voting::configure_milestone_voting(
+            ctx,
+            quorum_bps,
+            approval_threshold_bps,
+        )
+    }
+
+    pub fn create_milestone_proposal(
+        ctx: Context<CreateMilestoneProposal>,
+        tranche_index: u8,
+        voting_end: u64,
+    ) -> Result<()> {
+        crate::instructions::create_milestone_proposal::create_milestone_proposal(ctx, tranche_index, voting_end)
+    }
+
+    pub fn vote_on_milestone(ctx: Context<VoteOnMilestone>, approve: bool) -> Result<()> {
+        crate::instructions::vote_on_milestone::vote_on_milestone(ctx, approve)
+    }
+
+    pub fn execute_milestone_proposal(ctx: Context<ExecuteMilestoneProposal>) -> Result<()> {
+        crate::instructions::execute_milestone_proposal::execute_milestone_proposal(ctx)
+    }
+
+    pub fn configure_referral_program(
+        ctx: Context<ConfigureReferralProgram>,
+        referral_bps: u16,
+        referral_payout: ReferralPayout,
+        referral_budget_lamports: u64,
+    ) -> Result<()> {
+        crate::instructions::configure_referral_program::configure_referral_program(
+            ctx,
+            referral_bps,
+            referral_payout,
+            referral_budget_lamports,
+        )
+    }
+
+    pub fn register_referrer(ctx: Context<RegisterReferrer>) -> Result<()> {
+        crate::instructions::register_referrer::register_referrer(ctx)
+    }
+
+    pub fn claim_referral_reward(ctx: Context<ClaimReferralReward>) -> Result<()> {
+        crate::instructions::claim_referral_reward::claim_referral_reward(ctx)
+    }
+
+    pub fn create_token_lock(
+        ctx: Context<CreateTokenLock>,
+        name: String,
+        amount: u64,
+        cliff: u64,
+        vesting_end_time: u64,
+        available_tokens_after_cliff_ptc: i32,
+        revocable: bool,
+    ) -> Result<()> {
+        crate::instructions::create_token_lock::create_token_lock(
+            ctx,
+            name,
+            amount,
+            cliff,
+            vesting_end_time,
+            available_tokens_after_cliff_ptc,
+            revocable,
+        )
+    }
+
+    pub fn claim_token_lock(ctx: Context<ClaimTokenLock>) -> Result<()> {
+        crate::instructions::claim_token_lock::claim_token_lock(ctx)
+    }
+
+    pub fn revoke_token_lock(ctx: Context<RevokeTokenLock>) -> Result<()> {
+        crate::instructions::revoke_token_lock::revoke_token_lock(ctx)
+    }
+
+    pub fn set_position_revocable(ctx: Context<SetPositionRevocable>, revocable: bool) -> Result<()> {
+        crate::instructions::set_position_revocable::set_position_revocable(ctx, revocable)
+    }
+
+    pub fn revoke_position(ctx: Context<RevokePosition>) -> Result<()> {
+        crate::instructions::revoke_position::revoke_position(ctx)
+    }
+
+    pub fn configure_airdrop(
+        ctx: Context<ConfigureAirdrop>,
+        merkle_root: [u8; 32],
+        claim_deadline: u64,
+    ) -> Result<()> {
+        crate::instructions::configure_airdrop::configure_airdrop(ctx, merkle_root, claim_deadline)
+    }
+
+    pub fn claim_airdrop(ctx: Context<ClaimAirdrop>, amount: u64, proof: Vec<[u8; 32]>) -> Result<()> {
+        crate::instructions::claim_airdrop::claim_airdrop(ctx, amount, proof)
+    }
+
+    pub fn reclaim_airdrop(ctx: Context<ReclaimAirdrop>) -> Result<()> {
+        crate::instructions::reclaim_airdrop::reclaim_airdrop(ctx)
+    }
+
+    pub fn configure_claim_deadline(ctx: Context<ConfigureClaimDeadline>, claim_deadline: u64) -> Result<()> {
+        crate::instructions::configure_claim_deadline::configure_claim_deadline(ctx, claim_deadline)
+    }
+
+    pub fn sweep_unclaimed_tokens(ctx: Context<SweepUnclaimedTokens>) -> Result<()> {
+        crate::instructions::sweep_unclaimed_tokens::sweep_unclaimed_tokens(ctx)
+    }
+
+    pub fn configure_unsold_token_burn(ctx: Context<ConfigureUnsoldTokenBurn>, burn_unsold_tokens: bool) -> Result<()> {
+        crate::instructions::configure_unsold_token_burn::configure_unsold_token_burn(ctx, burn_unsold_tokens)
+    }
+
+    pub fn configure_mint_on_claim(ctx: Context<ConfigureMintOnClaim>) -> Result<()> {
+        crate::instructions::configure_mint_on_claim::configure_mint_on_claim(ctx)
+    }
+
+    pub fn release_mint_authority(ctx: Context<ReleaseMintAuthority>, revoke: bool) -> Result<()> {
+        crate::instructions::release_mint_authority::release_mint_authority(ctx, revoke)
+    }
+
+    pub fn fund_sale_supply(ctx: Context<FundSaleSupply>, amount: u64) -> Result<()> {
+        crate::instructions::fund_sale_supply::fund_sale_supply(ctx, amount)
+    }
+
+    pub fn return_funder_deposit(ctx: Context<ReturnFunderDeposit>) -> Result<()> {
+        crate::instructions::return_funder_deposit::return_funder_deposit(ctx)
+    }
+
+    pub fn add_to_gate_list<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AddToGateList<'info>>,
+        mints: Vec<Pubkey>,
+    ) -> Result<()> {
+        crate::instructions::add_to_gate_list::add_to_gate_list(ctx, mints)
+    }
+
+    pub fn remove_from_gate_list<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RemoveFromGateList<'info>>,
+    ) -> Result<()> {
+        crate::instructions::remove_from_gate_list::remove_from_gate_list(ctx)
+    }
+
+    pub fn sync_registry_entry(ctx: Context<SyncRegistryEntry>) -> Result<()> {
+        crate::instructions::sync_registry_entry::sync_registry_entry(ctx)
+    }
+
+    pub fn schedule_lottery_draw(ctx: Context<ScheduleLotteryDraw>) -> Result<()> {
+        crate::instructions::schedule_lottery_draw::schedule_lottery_draw(ctx)
+    }
 }
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+
+pub const ACCESS_LIST_VERSION: u8 = 1;
+
+/// Lets `participant` join a campaign with an allowlist, at
+/// `[b"allowlist", ido_campaign, participant]`.
+#[account]
+#[derive(InitSpace)]
+pub struct AllowlistEntry {
+    pub version: u8,
+    pub ido_campaign: Pubkey,
+    pub participant: Pubkey,
+    /// Caps the allocations the participant can buy, 0 keeps the campaign limit.
+    pub max_allocations: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct AllowlistAddition {
+    pub participant: Pubkey,
+    pub max_allocations: u64,
+}
+
+/// Bars `wallet` from joining any campaign, at `[b"blocklist", wallet]`.
+#[account]
+#[derive(InitSpace)]
+pub struct BlocklistEntry {
+    pub version: u8,
+    pub wallet: Pubkey,
+}
+
+/// Lets holders of `mint` join a campaign gated by a mint list, at
+/// `[b"gate_list", ido_campaign, mint]`.
+#[account]
+#[derive(InitSpace)]
+pub struct GateListEntry {
+    pub version: u8,
+    pub ido_campaign: Pubkey,
+    pub mint: Pubkey,
+}
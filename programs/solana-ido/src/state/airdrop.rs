@@ -0,0 +1,32 @@
+use anchor_lang::{prelude::*, solana_program::hash::hashv};
+
+pub const AIRDROP_VERSION: u8 = 1;
+
+const LEAF_PREFIX: &[u8] = &[0];
+const NODE_PREFIX: &[u8] = &[1];
+
+/// Eligibility of a `SaleMode::Airdrop` campaign at `[b"airdrop", ido_campaign]`.
+#[account]
+#[derive(InitSpace)]
+pub struct Airdrop {
+    pub version: u8,
+    pub ido_campaign: Pubkey,
+    /// Root of the tree with a `sha256(0 || participant || amount_le)` leaf per recipient,
+    /// inner nodes hash their sorted children as `sha256(1 || left || right)`.
+    pub merkle_root: [u8; 32],
+    /// Airdrops not claimed by then go back to the authority with `reclaim_airdrop`.
+    pub claim_deadline: u64,
+}
+
+impl Airdrop {
+    pub fn verify(&self, participant: &Pubkey, amount: u64, proof: &[[u8; 32]]) -> bool {
+        let leaf = hashv(&[LEAF_PREFIX, participant.as_ref(), &amount.to_le_bytes()]).to_bytes();
+
+        let root = proof.iter().fold(leaf, |node, sibling| {
+            let (left, right) = if node <= *sibling { (node, *sibling) } else { (*sibling, node) };
+            hashv(&[NODE_PREFIX, &left, &right]).to_bytes()
+        });
+
+        root == self.merkle_root
+    }
+}
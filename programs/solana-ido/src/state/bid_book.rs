@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+
+pub const BID_BOOK_VERSION: u8 = 1;
+
+/// Number of price levels a batch auction bid can be placed at, level `i` is
+/// `price_lamports + i * bid_tick_lamports`.
+pub const MAX_BID_PRICE_LEVELS: usize = 64;
+
+/// Revealed demand of a `SaleMode::BatchAuction` campaign aggregated by price level,
+/// so settlement walks a fixed number of levels instead of every bid.
+#[account]
+#[derive(InitSpace)]
+pub struct BidBook {
+    pub version: u8,
+    pub ido_campaign: Pubkey,
+    pub demand: [u64; MAX_BID_PRICE_LEVELS],
+}
@@ -0,0 +1,97 @@
+use {
+    crate::instructions::IdoError,
+    anchor_lang::prelude::*,
+};
+
+/// Upper bound of `ExponentialSteps` steps up to `hard_cap`, keeps the integration loop cheap.
+pub const MAX_BONDING_CURVE_STEPS: u64 = 64;
+
+const BPS_DENOMINATOR: u128 = 10_000;
+
+/// Price per token as a function of the tokens already sold, `price_lamports` is the
+/// price of the very first token. Clients quote with the same math as `cost`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum BondingCurve {
+    /// `price(x) = price_lamports + slope_lamports * x`.
+    Linear { slope_lamports: u64 },
+    /// Price is flat within a step and grows by `growth_bps` (rounded down) every `step_tokens` sold.
+    ExponentialSteps { step_tokens: u64, growth_bps: u16 },
+}
+
+impl BondingCurve {
+    /// Price of the next token once `sold` tokens are gone.
+    pub fn price_at(&self, base_price: u64, sold: u64) -> Result<u64> {
+        let price = match *self {
+            BondingCurve::Linear { slope_lamports } => (slope_lamports as u128)
+                .checked_mul(sold as u128)
+                .and_then(|increase| increase.checked_add(base_price as u128))
+                .ok_or(IdoError::ErrMathOverflow)?,
+            BondingCurve::ExponentialSteps { step_tokens, growth_bps } => {
+                let mut price = base_price as u128;
+                for _ in 0..sold / step_tokens {
+                    price = grow(price, growth_bps)?;
+                }
+                price
+            }
+        };
+
+        u64::try_from(price).map_err(|_| error!(IdoError::ErrMathOverflow))
+    }
+
+    /// Exact sum of the prices of tokens `[sold, sold + amount)`.
+    pub fn cost(&self, base_price: u64, sold: u64, amount: u64) -> Result<u64> {
+        let end = sold.checked_add(amount).ok_or(IdoError::ErrMathOverflow)?;
+
+        let cost = match *self {
+            BondingCurve::Linear { slope_lamports } => {
+                // amount * base + slope * (amount * sold + amount * (amount - 1) / 2)
+                let amount = amount as u128;
+                let triangle = amount * amount.saturating_sub(1) / 2;
+                let offset = amount
+                    .checked_mul(sold as u128)
+                    .and_then(|offset| offset.checked_add(triangle))
+                    .and_then(|offset| offset.checked_mul(slope_lamports as u128))
+                    .ok_or(IdoError::ErrMathOverflow)?;
+
+                amount
+                    .checked_mul(base_price as u128)
+                    .and_then(|base| base.checked_add(offset))
+                    .ok_or(IdoError::ErrMathOverflow)?
+            }
+            BondingCurve::ExponentialSteps { step_tokens, growth_bps } => {
+                // The curve is only defined up to its last step.
+                require!(
+                    end <= step_tokens.saturating_mul(MAX_BONDING_CURVE_STEPS),
+                    IdoError::ErrThisAllocationIsNotAvailable
+                );
+
+                let mut cost: u128 = 0;
+                let mut price = base_price as u128;
+                let mut step_start: u64 = 0;
+                while step_start < end {
+                    let step_end = step_start.saturating_add(step_tokens);
+                    if step_end > sold {
+                        let tokens = end.min(step_end) - sold.max(step_start);
+                        cost = price
+                            .checked_mul(tokens as u128)
+                            .and_then(|step_cost| step_cost.checked_add(cost))
+                            .ok_or(IdoError::ErrMathOverflow)?;
+                    }
+                    price = grow(price, growth_bps)?;
+                    step_start = step_end;
+                }
+                cost
+            }
+        };
+
+        u64::try_from(cost).map_err(|_| error!(IdoError::ErrMathOverflow))
+    }
+}
+
+fn grow(price: u128, growth_bps: u16) -> Result<u128> {
+    let grown = price
+        .checked_mul(BPS_DENOMINATOR + growth_bps as u128)
+        .ok_or(IdoError::ErrMathOverflow)?;
+
+    Ok(grown / BPS_DENOMINATOR)
+}
@@ -1,8 +1,15 @@
-use anchor_lang::prelude::*;
+use {
+    crate::{instructions::IdoError, state::{BondingCurve, ReferralPayout}},
+    anchor_lang::prelude::*,
+};
+
+pub const IDO_CAMPAIGN_VERSION: u8 = 1;
 
 #[account]
 #[derive(InitSpace)]
 pub struct IdoCampaign {
+    pub version: u8,
+    pub status: CampaignStatus,
     pub authority: Pubkey,
     pub token_treasury: Pubkey,
     pub sol_treasury: Pubkey,
@@ -20,7 +27,314 @@ pub struct IdoCampaign {
     pub hard_cap: u64,
     pub available_allocations_per_participant: u64,
     pub token_mint: Pubkey,
-    pub token_supply_deposited: bool,
     pub funds_withdrawn: bool,
-    pub sale_closed: bool,
-}
\ No newline at end of file
+    pub sale_mode: SaleMode,
+    /// Tokens requested by all participants, may exceed `hard_cap` in `SaleMode::ProRata`.
+    pub total_committed: u64,
+    /// Lowest price `SaleMode::DutchAuction` decays to, `price_lamports` is the start price.
+    pub floor_price_lamports: u64,
+    pub price_decay: PriceDecay,
+    /// Every Dutch auction buyer pays `clearing_price_lamports` in the end and gets the difference back.
+    pub settle_at_clearing_price: bool,
+    /// Price of the latest Dutch auction purchase, the lowest one paid since the price only decays.
+    /// In `SaleMode::BatchAuction` the uniform price every filled bid pays, known once settled.
+    pub clearing_price_lamports: u64,
+    /// Price per token over `total_sold` in `SaleMode::BondingCurve`.
+    pub bonding_curve: BondingCurve,
+    /// Distance between the price levels batch auction bids can be placed at.
+    pub bid_tick_lamports: u64,
+    /// Bids are revealed between `end_sale_time` and `reveal_end_time`.
+    pub reveal_end_time: u64,
+    pub auction_settled: bool,
+    /// Tokens left for the bids at the clearing price, shared pro-rata to their demand.
+    pub marginal_bid_filled: u64,
+    pub marginal_bid_demand: u64,
+    /// Per-participant allocations come from the campaign `StakeTiers` instead of
+    /// `available_allocations_per_participant`.
+    pub stake_tiered: bool,
+    /// Only `Lottery` winners can join until its guaranteed window ends.
+    pub lottery_enabled: bool,
+    /// Signs the `KycTicket` every participant has to present to join,
+    /// `Pubkey::default()` when the sale requires no KYC.
+    pub kyc_signer: Pubkey,
+    /// `join_ido` has to be a top level instruction in a transaction that only
+    /// calls this program, the Ed25519 program and the compute budget program.
+    pub reject_foreign_instructions: bool,
+    /// Joins a wallet can make across campaigns within one slot, 0 when unlimited.
+    pub max_joins_per_slot: u64,
+    /// Per-participant allocations grow linearly from `ramp_up_start_allocations` to
+    /// `available_allocations_per_participant` over the first `ramp_up_duration` seconds.
+    pub ramp_up_duration: u64,
+    pub ramp_up_start_allocations: u64,
+    /// Only participants with an `AllowlistEntry` can join.
+    pub allowlist_enabled: bool,
+    /// Participants have to hold at least `gate_min_balance` of this mint,
+    /// `Pubkey::default()` when the sale is not token gated.
+    pub gate_mint: Pubkey,
+    pub gate_min_balance: u64,
+    /// Every this many gate tokens held unlock one allocation, 0 keeps the per-wallet limit.
+    pub gate_tokens_per_allocation: u64,
+    /// `withdraw_funds` locks the owner share in the `MilestoneEscrow` instead of paying it out.
+    pub milestone_escrow_enabled: bool,
+    /// Share of a referred purchase paid to its referrer, 0 when the sale has no referral program.
+    pub referral_bps: u16,
+    pub referral_payout: ReferralPayout,
+    /// Total rewards the campaign pays to all referrers.
+    pub referral_budget_lamports: u64,
+    /// Rewards accrued and not claimed yet. Claims start after the sale, so while it runs
+    /// this is everything counted against the budget.
+    pub referral_rewards_lamports: u64,
+    /// Tokens nobody claimed by then can be swept by the authority, 0 when claims never expire.
+    pub claim_deadline: u64,
+    /// `withdraw_funds` burns the unsold tokens instead of returning them to the authority.
+    pub burn_unsold_tokens: bool,
+    pub unsold_tokens_burned: u64,
+    /// The campaign holds the mint authority and mints every claim, nothing is deposited
+    /// and unsold tokens are never minted.
+    pub mint_on_claim: bool,
+    /// Sale supply in the treasury so far, the campaign is funded once it covers `hard_cap`.
+    pub total_deposited: u64,
+    /// Part of `total_deposited` from third-party funders, owed back to them if the campaign
+    /// fails and never swept to the authority.
+    pub funder_deposits: u64,
+    /// Demand already settled out of the pool filled pro-rata, the commitments of a
+    /// `SaleMode::ProRata` sale or the batch auction bids at the clearing price.
+    pub settled_demand: u64,
+    /// Participants have to hold a mint with a `GateListEntry` instead of `gate_mint`,
+    /// for collections that are a list of mints.
+    pub gate_mint_list: bool,
+    /// Zeroed space for fields added without a realloc, new fields are carved from here.
+    pub reserved: [u8; 6],
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum SaleMode {
+    /// First come first served at `price_lamports` until `hard_cap` is sold.
+    FixedPrice,
+    /// Commitments above `hard_cap` are accepted and filled pro-rata once the sale ends,
+    /// the unfilled part of every payment is refundable.
+    ProRata,
+    /// Price decays from `price_lamports` to `floor_price_lamports` over the sale window,
+    /// first come first served until `hard_cap` is sold.
+    DutchAuction,
+    /// Price grows with `total_sold` along `bonding_curve`, every purchase pays the exact
+    /// integral over the range it buys, first come first served until `hard_cap` is sold.
+    BondingCurve,
+    /// Sealed bids are committed during the sale, revealed after it and filled from the
+    /// highest price down to `hard_cap`, every filled bid pays the uniform clearing price.
+    BatchAuction,
+    /// Nothing is sold, `price_lamports` is 0 and recipients of the `Airdrop` merkle tree
+    /// claim their vested tokens with `claim_airdrop` once `end_sale_time` passed.
+    Airdrop,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum PriceDecay {
+    /// Price drops every second.
+    Linear,
+    /// Price drops once every `interval` seconds.
+    Stepped { interval: u64 },
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum CampaignStatus {
+    /// Initialized, sale supply not deposited yet.
+    Created,
+    /// Sale supply deposited, waiting for `start_sale_time`.
+    Funded,
+    /// Between `start_sale_time` and `end_sale_time`, purchases accepted.
+    Live,
+    /// Past `end_sale_time`, outcome not settled yet.
+    Ended,
+    /// Soft cap reached, participants can claim and the owner can withdraw funds.
+    Succeeded,
+    /// Soft cap not reached, participants can refund.
+    Failed,
+    /// Closed by the authority before the sale ended, participants can refund.
+    Cancelled,
+    /// Funds withdrawn and every sold token claimed or swept after the claim deadline,
+    /// nothing left to do.
+    Finalized,
+}
+
+impl CampaignStatus {
+    pub fn can_transition_to(self, next: CampaignStatus) -> bool {
+        use CampaignStatus::*;
+
+        matches!(
+            (self, next),
+            (Created, Funded)
+                | (Created, Failed)
+                | (Funded, Live)
+                | (Funded, Cancelled)
+                | (Live, Ended)
+                | (Live, Cancelled)
+                | (Ended, Succeeded)
+                | (Ended, Failed)
+                | (Succeeded, Finalized)
+        )
+    }
+
+    pub fn is_closed(self) -> bool {
+        matches!(self, CampaignStatus::Failed | CampaignStatus::Cancelled)
+    }
+
+    /// Maps the boolean flags used before `status` existed onto a status. Campaigns that
+    /// were closed with the soft cap missed are reported as failed, which refunds exactly
+    /// like a cancellation does.
+    pub fn from_legacy_flags(
+        token_supply_deposited: bool,
+        funds_withdrawn: bool,
+        sale_closed: bool,
+        soft_cap_reached: bool,
+    ) -> CampaignStatus {
+        if sale_closed {
+            return if soft_cap_reached {
+                CampaignStatus::Cancelled
+            } else {
+                CampaignStatus::Failed
+            };
+        }
+
+        if funds_withdrawn {
+            return CampaignStatus::Succeeded;
+        }
+
+        if token_supply_deposited {
+            return CampaignStatus::Funded;
+        }
+
+        CampaignStatus::Created
+    }
+}
+
+impl IdoCampaign {
+    /// Single entry point for every status change, rejects edges the lifecycle does not allow.
+    pub fn transition(&mut self, next: CampaignStatus) -> Result<()> {
+        require!(
+            self.status.can_transition_to(next),
+            IdoError::ErrInvalidStatusTransition
+        );
+
+        self.status = next;
+
+        Ok(())
+    }
+
+    /// Price of the next token at `now`, changes over time in `SaleMode::DutchAuction`
+    /// and with `total_sold` in `SaleMode::BondingCurve`.
+    pub fn current_price(&self, now: u64) -> Result<u64> {
+        match self.sale_mode {
+            SaleMode::DutchAuction => self.dutch_auction_price(now),
+            SaleMode::BondingCurve => self.bonding_curve.price_at(self.price_lamports, self.total_sold),
+            _ => Ok(self.price_lamports),
+        }
+    }
+
+    fn dutch_auction_price(&self, now: u64) -> Result<u64> {
+        let duration = self
+            .end_sale_time
+            .checked_sub(self.start_sale_time)
+            .ok_or(IdoError::ErrMathOverflow)?;
+        let elapsed = now.saturating_sub(self.start_sale_time).min(duration);
+        let elapsed = match self.price_decay {
+            PriceDecay::Linear => elapsed,
+            PriceDecay::Stepped { interval } => elapsed / interval * interval,
+        };
+
+        let price_range = self
+            .price_lamports
+            .checked_sub(self.floor_price_lamports)
+            .ok_or(IdoError::ErrMathOverflow)?;
+        let decay = (price_range as u128)
+            .checked_mul(elapsed as u128)
+            .ok_or(IdoError::ErrMathOverflow)?
+            / duration as u128;
+
+        Ok(self.price_lamports - decay as u64)
+    }
+
+    /// Allocations cap of the ramp-up schedule at `now`, unbounded when there is none.
+    pub fn ramp_up_allocations(&self, now: u64) -> u64 {
+        if self.ramp_up_duration == 0 {
+            return u64::MAX;
+        }
+
+        let elapsed = now.saturating_sub(self.start_sale_time).min(self.ramp_up_duration);
+        let growth = self
+            .available_allocations_per_participant
+            .saturating_sub(self.ramp_up_start_allocations);
+
+        self.ramp_up_start_allocations + (growth as u128 * elapsed as u128 / self.ramp_up_duration as u128) as u64
+    }
+
+    /// Supply still missing before the campaign is funded.
+    pub fn remaining_supply(&self) -> u64 {
+        self.hard_cap.saturating_sub(self.total_deposited)
+    }
+
+    pub fn claim_deadline_passed(&self, now: u64) -> bool {
+        self.claim_deadline > 0 && now >= self.claim_deadline
+    }
+
+    /// Fill of `demand` when `filled` tokens are shared over `total_demand`, handed out in the
+    /// order participants settle. Every fill is the running total rounded down minus what was
+    /// handed out before, so the fills of the whole pool add up to exactly `filled`.
+    pub fn settle_shared_fill(&mut self, demand: u64, filled: u64, total_demand: u64) -> Result<u64> {
+        let filled_up_to = |settled_demand: u64| {
+            (settled_demand as u128 * filled as u128 / total_demand as u128) as u64
+        };
+
+        let filled_before = filled_up_to(self.settled_demand);
+        self.settled_demand = self.settled_demand
+            .checked_add(demand)
+            .ok_or(IdoError::ErrMathOverflow)?;
+        require!(self.settled_demand <= total_demand, IdoError::ErrMathOverflow);
+
+        Ok(filled_up_to(self.settled_demand) - filled_before)
+    }
+
+    /// Every filled purchase ends up paying `clearing_price_lamports`.
+    pub fn sells_at_clearing_price(&self) -> bool {
+        match self.sale_mode {
+            SaleMode::DutchAuction => self.settle_at_clearing_price,
+            SaleMode::BatchAuction => true,
+            _ => false,
+        }
+    }
+
+    /// Reward for a referred purchase of `purchase_lamports`, cut down to what is left of the budget.
+    pub fn referral_reward(&self, purchase_lamports: u64) -> u64 {
+        let reward = (purchase_lamports as u128 * self.referral_bps as u128 / 10_000) as u64;
+        let budget_left = self
+            .referral_budget_lamports
+            .saturating_sub(self.referral_rewards_lamports);
+
+        reward.min(budget_left)
+    }
+
+    /// Applies every transition that only depends on the clock and the sale totals.
+    pub fn sync_status(&mut self, now: u64) -> Result<CampaignStatus> {
+        while let Some(next) = self.next_automatic_status(now) {
+            self.transition(next)?;
+        }
+
+        Ok(self.status)
+    }
+
+    fn next_automatic_status(&self, now: u64) -> Option<CampaignStatus> {
+        match self.status {
+            CampaignStatus::Funded if now >= self.start_sale_time => Some(CampaignStatus::Live),
+            // join_ido accepts purchases at end_sale_time itself, the sale only ends after it.
+            CampaignStatus::Live if now > self.end_sale_time => Some(CampaignStatus::Ended),
+            CampaignStatus::Ended if self.total_sold >= self.soft_cap || self.sale_mode == SaleMode::Airdrop => {
+                Some(CampaignStatus::Succeeded)
+            }
+            // Settled fills add up to total_sold exactly, see settle_shared_fill.
+            CampaignStatus::Succeeded if self.funds_withdrawn && self.total_claimed >= self.total_sold => {
+                Some(CampaignStatus::Finalized)
+            }
+            _ => None,
+        }
+    }
+}
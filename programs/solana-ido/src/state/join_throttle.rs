@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+pub const JOIN_THROTTLE_VERSION: u8 = 1;
+
+/// Joins a wallet made in its latest slot across every campaign, one per wallet at
+/// `[b"join_throttle", participant]`, created by its first throttled `join_ido`.
+#[account]
+#[derive(InitSpace)]
+pub struct JoinThrottle {
+    pub version: u8,
+    pub participant: Pubkey,
+    pub slot: u64,
+    pub joins_in_slot: u64,
+}
+
+impl JoinThrottle {
+    /// Counts a join at `slot` and returns how many the wallet made in it so far.
+    pub fn record_join(&mut self, slot: u64) -> u64 {
+        if self.slot != slot {
+            self.slot = slot;
+            self.joins_in_slot = 0;
+        }
+        self.joins_in_slot += 1;
+
+        self.joins_in_slot
+    }
+}
@@ -0,0 +1,68 @@
+use {
+    crate::instructions::IdoError,
+    anchor_lang::{
+        prelude::*,
+        solana_program::{ed25519_program, instruction::Instruction},
+    },
+};
+
+/// Size of one `Ed25519SignatureOffsets` entry in the Ed25519 program instruction data.
+const SIGNATURE_OFFSETS_LEN: usize = 14;
+/// Offsets pointing at `u16::MAX` refer to the Ed25519 instruction's own data.
+const CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+/// Message the campaign KYC signer signs off-chain for every approved participant.
+/// Serialized as campaign, participant, max allocations and expiry, integers little endian.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct KycTicket {
+    pub ido_campaign: Pubkey,
+    pub participant: Pubkey,
+    pub max_allocations: u64,
+    pub expires_at: u64,
+}
+
+impl KycTicket {
+    pub const LEN: usize = 32 + 32 + 8 + 8;
+
+    /// Reads the ticket out of an Ed25519 program instruction carrying exactly one
+    /// signature by `kyc_signer`. The runtime already verified the signature, so only
+    /// the signer and the signed bytes need checking here.
+    pub fn from_ed25519_instruction(instruction: &Instruction, kyc_signer: &Pubkey) -> Result<KycTicket> {
+        require_keys_eq!(instruction.program_id, ed25519_program::ID, IdoError::ErrInvalidKycTicket);
+
+        let data = instruction.data.as_slice();
+        require!(
+            data.len() >= 2 + SIGNATURE_OFFSETS_LEN && data[0] == 1,
+            IdoError::ErrInvalidKycTicket
+        );
+
+        let offsets = &data[2..2 + SIGNATURE_OFFSETS_LEN];
+        let read_u16 = |at: usize| u16::from_le_bytes([offsets[at], offsets[at + 1]]);
+        let signature_instruction_index = read_u16(2);
+        let public_key_offset = read_u16(4) as usize;
+        let public_key_instruction_index = read_u16(6);
+        let message_data_offset = read_u16(8) as usize;
+        let message_data_size = read_u16(10) as usize;
+        let message_instruction_index = read_u16(12);
+
+        // Offsets into other instructions would let the checked bytes differ from the signed ones.
+        require!(
+            signature_instruction_index == CURRENT_INSTRUCTION
+                && public_key_instruction_index == CURRENT_INSTRUCTION
+                && message_instruction_index == CURRENT_INSTRUCTION,
+            IdoError::ErrInvalidKycTicket
+        );
+
+        let public_key = data
+            .get(public_key_offset..public_key_offset + 32)
+            .ok_or(IdoError::ErrInvalidKycTicket)?;
+        require!(public_key == kyc_signer.as_ref(), IdoError::ErrInvalidKycTicket);
+
+        require!(message_data_size == KycTicket::LEN, IdoError::ErrInvalidKycTicket);
+        let mut message = data
+            .get(message_data_offset..message_data_offset + message_data_size)
+            .ok_or(IdoError::ErrInvalidKycTicket)?;
+
+        Ok(KycTicket::deserialize(&mut message)?)
+    }
+}
@@ -0,0 +1,20 @@
+use anchor_lang::prelude::*;
+
+pub const LAUNCHPAD_CONFIG_VERSION: u8 = 1;
+
+/// Launchpad-wide settings at `[b"launchpad_config"]`, created by the program upgrade
+/// authority which becomes the first `admin`.
+#[account]
+#[derive(InitSpace)]
+pub struct LaunchpadConfig {
+    pub version: u8,
+    pub admin: Pubkey,
+    /// Launchpad token staked for allocation tiers.
+    pub stake_mint: Pubkey,
+    /// Program-owned token account at `[b"stake_vault"]` holding every stake.
+    pub stake_vault: Pubkey,
+    /// Seconds a stake stays locked after the latest deposit.
+    pub stake_lock_period: u64,
+    /// Zeroed space for fields added without a realloc, new fields are carved from here.
+    pub reserved: [u8; 64],
+}
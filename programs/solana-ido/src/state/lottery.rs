@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+
+pub const LOTTERY_VERSION: u8 = 1;
+
+/// Registrations per campaign, bounded so the draw fits in a single transaction.
+pub const MAX_LOTTERY_REGISTRANTS: u64 = 1024;
+
+/// Slots between scheduling the draw and the slot whose hash seeds it, so nobody knows
+/// the seed while the registrants can still change.
+pub const LOTTERY_DRAW_DELAY_SLOTS: u64 = 8;
+
+/// Registration and draw state of a campaign at `[b"lottery", ido_campaign]`.
+#[account]
+#[derive(InitSpace)]
+pub struct Lottery {
+    pub version: u8,
+    pub ido_campaign: Pubkey,
+    /// Lamports every registrant locks in its ticket until the draw is over.
+    pub registration_deposit_lamports: u64,
+    pub winners_count: u64,
+    /// Until then only winners can join, afterwards the sale is open to everyone.
+    pub guaranteed_window_end: u64,
+    pub registrant_count: u64,
+    pub drawn: bool,
+    /// Set by `schedule_lottery_draw` once registration closed, 0 until then.
+    pub draw_slot: u64,
+    pub seed: [u8; 32],
+    /// Bit `i` is set when the ticket with index `i` won.
+    pub winners: [u8; (MAX_LOTTERY_REGISTRANTS / 8) as usize],
+}
+
+impl Lottery {
+    pub fn is_winner(&self, index: u64) -> bool {
+        index < MAX_LOTTERY_REGISTRANTS && self.winners[(index / 8) as usize] & (1 << (index % 8)) != 0
+    }
+
+    pub fn set_winner(&mut self, index: u64) {
+        self.winners[(index / 8) as usize] |= 1 << (index % 8);
+    }
+}
+
+/// One per registrant at `[b"lottery_ticket", ido_campaign, participant]`, holds the deposit.
+#[account]
+#[derive(InitSpace)]
+pub struct LotteryTicket {
+    pub version: u8,
+    pub ido_campaign: Pubkey,
+    pub participant: Pubkey,
+    pub index: u64,
+}
@@ -0,0 +1,72 @@
+use {
+    crate::instructions::IdoError,
+    anchor_lang::prelude::*,
+};
+
+pub const CAMPAIGN_METADATA_VERSION: u8 = 1;
+
+pub const MAX_METADATA_NAME_LEN: usize = 32;
+pub const MAX_METADATA_SYMBOL_LEN: usize = 10;
+pub const MAX_METADATA_URI_LEN: usize = 200;
+pub const MAX_METADATA_DESCRIPTION_LEN: usize = 256;
+pub const MAX_METADATA_SOCIAL_LINKS: usize = 4;
+pub const MAX_METADATA_SOCIAL_LINK_LEN: usize = 100;
+
+#[account]
+#[derive(InitSpace)]
+pub struct CampaignMetadata {
+    pub version: u8,
+    pub ido_campaign: Pubkey,
+    #[max_len(MAX_METADATA_NAME_LEN)]
+    pub name: String,
+    #[max_len(MAX_METADATA_SYMBOL_LEN)]
+    pub symbol: String,
+    #[max_len(MAX_METADATA_URI_LEN)]
+    pub uri: String,
+    #[max_len(MAX_METADATA_DESCRIPTION_LEN)]
+    pub description: String,
+    #[max_len(MAX_METADATA_SOCIAL_LINKS, MAX_METADATA_SOCIAL_LINK_LEN)]
+    pub social_links: Vec<String>,
+    pub updated_at: u64,
+}
+
+/// Project info passed to `initialize_sale`, every campaign is created with its metadata.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CampaignMetadataArgs {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub description: String,
+    pub social_links: Vec<String>,
+}
+
+impl CampaignMetadata {
+    pub fn set(
+        &mut self,
+        name: String,
+        symbol: String,
+        uri: String,
+        description: String,
+        social_links: Vec<String>,
+        now: u64,
+    ) -> Result<()> {
+        require!(!name.is_empty() && name.len() <= MAX_METADATA_NAME_LEN, IdoError::ErrInvalidMetadata);
+        require!(symbol.len() <= MAX_METADATA_SYMBOL_LEN, IdoError::ErrInvalidMetadata);
+        require!(uri.len() <= MAX_METADATA_URI_LEN, IdoError::ErrInvalidMetadata);
+        require!(description.len() <= MAX_METADATA_DESCRIPTION_LEN, IdoError::ErrInvalidMetadata);
+        require!(social_links.len() <= MAX_METADATA_SOCIAL_LINKS, IdoError::ErrInvalidMetadata);
+        require!(
+            social_links.iter().all(|link| link.len() <= MAX_METADATA_SOCIAL_LINK_LEN),
+            IdoError::ErrInvalidMetadata
+        );
+
+        self.name = name;
+        self.symbol = symbol;
+        self.uri = uri;
+        self.description = description;
+        self.social_links = social_links;
+        self.updated_at = now;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,166 @@
+use {
+    crate::{instructions::IdoError, state::User},
+    anchor_lang::prelude::*,
+};
+
+pub const MILESTONE_ESCROW_VERSION: u8 = 1;
+/// Tranches per campaign, `User.escrow_refunded_tranches` keeps one bit per tranche.
+pub const MAX_ESCROW_TRANCHES: usize = 8;
+
+const BPS_DENOMINATOR: u128 = 10_000;
+
+/// Owner share of the proceeds of a campaign at `[b"milestone_escrow", ido_campaign]`,
+/// locked by `withdraw_funds` and released to the owner tranche by tranche.
+#[account]
+#[derive(InitSpace)]
+pub struct MilestoneEscrow {
+    pub version: u8,
+    pub ido_campaign: Pubkey,
+    pub escrowed_lamports: u64,
+    pub released_lamports: u64,
+    /// Escrow of refunded tranches, participants claim it pro-rata to the tokens they bought.
+    pub refundable_lamports: u64,
+    pub failed: bool,
+    #[max_len(MAX_ESCROW_TRANCHES)]
+    pub tranches: Vec<Tranche>,
+    /// Milestone tranches are released or refunded by a `MilestoneProposal` vote
+    /// instead of the launchpad admin approval.
+    pub voting_enabled: bool,
+    /// Share of `total_sold` that has to vote for a proposal to count.
+    pub quorum_bps: u16,
+    /// Share of the cast weight that has to approve for the tranche to be released.
+    pub approval_threshold_bps: u16,
+    /// Tokens taken back by `revoke_position`, they no longer share in the refunds.
+    pub revoked_amount: u64,
+}
+
+/// Vote on releasing a milestone tranche at `[b"milestone_proposal", ido_campaign, tranche_index]`,
+/// weighted by the tokens every participant bought.
+#[account]
+#[derive(InitSpace)]
+pub struct MilestoneProposal {
+    pub version: u8,
+    pub ido_campaign: Pubkey,
+    pub tranche_index: u8,
+    pub voting_start: u64,
+    pub voting_end: u64,
+    pub approve_weight: u64,
+    pub reject_weight: u64,
+    pub executed: bool,
+}
+
+/// Marks that `participant` voted on a proposal, at `[b"milestone_vote", proposal, participant]`.
+#[account]
+#[derive(InitSpace)]
+pub struct MilestoneVote {
+    pub version: u8,
+    pub proposal: Pubkey,
+    pub participant: Pubkey,
+    pub approve: bool,
+    pub weight: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum TrancheUnlock {
+    /// Releasable once `unlock_time` has passed.
+    Time { unlock_time: u64 },
+    /// Releasable once the milestone is approved.
+    Milestone,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum TrancheState {
+    Locked,
+    Approved,
+    Released,
+    Refunded,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub struct TrancheTerms {
+    pub unlock: TrancheUnlock,
+    /// Share of the escrow, the terms of a campaign add up to 10000.
+    pub release_bps: u16,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub struct Tranche {
+    pub terms: TrancheTerms,
+    pub state: TrancheState,
+}
+
+impl MilestoneProposal {
+    /// Quorum of `total_sold` reached and enough of the cast weight approved.
+    pub fn passed(&self, total_sold: u64, quorum_bps: u16, approval_threshold_bps: u16) -> bool {
+        let cast = self.approve_weight as u128 + self.reject_weight as u128;
+        let quorum = total_sold as u128 * quorum_bps as u128;
+        let threshold = cast * approval_threshold_bps as u128;
+
+        cast > 0 && cast * BPS_DENOMINATOR >= quorum && self.approve_weight as u128 * BPS_DENOMINATOR >= threshold
+    }
+}
+
+impl MilestoneEscrow {
+    pub fn tranche(&self, index: u8) -> Result<&Tranche> {
+        self.tranches
+            .get(index as usize)
+            .ok_or_else(|| error!(IdoError::ErrInvalidTranche))
+    }
+
+    /// Lamports of the tranche at `index`, the last one takes the rounding remainder so
+    /// the tranches add up to `escrowed_lamports`.
+    pub fn tranche_amount(&self, index: u8) -> Result<u64> {
+        self.tranche(index)?;
+
+        let share = |index: usize| -> u64 {
+            (self.escrowed_lamports as u128 * self.tranches[index].terms.release_bps as u128
+                / BPS_DENOMINATOR) as u64
+        };
+
+        let last = self.tranches.len() - 1;
+        if index as usize != last {
+            return Ok(share(index as usize));
+        }
+
+        let others: u64 = (0..last).map(share).sum();
+        Ok(self.escrowed_lamports - others)
+    }
+
+    pub fn is_unlocked(&self, index: u8, now: u64) -> Result<bool> {
+        let tranche = self.tranche(index)?;
+
+        Ok(match tranche.state {
+            TrancheState::Approved => true,
+            TrancheState::Locked => match tranche.terms.unlock {
+                TrancheUnlock::Time { unlock_time } => now >= unlock_time,
+                TrancheUnlock::Milestone => false,
+            },
+            _ => false,
+        })
+    }
+
+    /// Escrow `user` has not claimed yet from refunded tranches, with the bitmap of every
+    /// refunded tranche. Shares round down so the claims never exceed the refunded escrow.
+    pub fn unclaimed_refund(&self, user: &User, total_sold: u64) -> Result<(u64, u8)> {
+        let shares = total_sold
+            .checked_sub(self.revoked_amount)
+            .ok_or(IdoError::ErrMathOverflow)?;
+        let mut refund: u64 = 0;
+        let mut refunded_tranches = user.escrow_refunded_tranches;
+
+        for index in 0..self.tranches.len() as u8 {
+            let bit = 1 << index;
+            if self.tranches[index as usize].state != TrancheState::Refunded || refunded_tranches & bit != 0 {
+                continue;
+            }
+
+            let share = self.tranche_amount(index)? as u128 * user.amount as u128 / shares as u128;
+            refund = refund
+                .checked_add(share as u64)
+                .ok_or(IdoError::ErrMathOverflow)?;
+            refunded_tranches |= bit;
+        }
+
+        Ok((refund, refunded_tranches))
+    }
+}
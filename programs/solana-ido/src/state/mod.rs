@@ -1,5 +1,35 @@
 pub mod ido;
 pub mod user;
+pub mod metadata;
+pub mod registry;
+pub mod bonding_curve;
+pub mod bid_book;
+pub mod launchpad;
+pub mod stake;
+pub mod lottery;
+pub mod kyc;
+pub mod join_throttle;
+pub mod access_list;
+pub mod milestone_escrow;
+pub mod referral;
+pub mod token_lock;
+pub mod airdrop;
+pub mod supply_deposit;
 
 pub use ido::*;
-pub use user::*;
\ No newline at end of file
+pub use user::*;
+pub use metadata::*;
+pub use registry::*;
+pub use bonding_curve::*;
+pub use bid_book::*;
+pub use launchpad::*;
+pub use stake::*;
+pub use lottery::*;
+pub use kyc::*;
+pub use join_throttle::*;
+pub use access_list::*;
+pub use milestone_escrow::*;
+pub use referral::*;
+pub use token_lock::*;
+pub use airdrop::*;
+pub use supply_deposit::*;
\ No newline at end of file
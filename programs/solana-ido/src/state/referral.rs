@@ -0,0 +1,52 @@
+use {
+    crate::instructions::IdoError,
+    anchor_lang::prelude::*,
+};
+
+pub const REFERRAL_VERSION: u8 = 1;
+/// Referral rewards can't exceed the purchase they are taken from.
+pub const MAX_REFERRAL_BPS: u16 = 10_000;
+
+/// Referred purchases are refunded in full if the campaign fails, referrers are only
+/// paid once it succeeds.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum ReferralPayout {
+    /// Rewards stay in the treasury until the sale succeeds and can be collected by
+    /// `claim_referral_reward` from then on, before the owner withdraws. Called
+    /// `Immediate` before rewards stopped being paid out of `join_ido`, the encoding
+    /// is unchanged.
+    OnSuccess,
+    /// Rewards stay in the treasury when `withdraw_funds` pays the owner and are
+    /// collected by `claim_referral_reward` afterwards.
+    AtWithdraw,
+}
+
+/// Purchases a referrer brought into a campaign at `[b"referral", ido_campaign, referrer]`.
+#[account]
+#[derive(InitSpace)]
+pub struct Referral {
+    pub version: u8,
+    pub ido_campaign: Pubkey,
+    pub referrer: Pubkey,
+    pub referred_participants: u64,
+    /// Lamports paid by the referred participants.
+    pub referred_lamports: u64,
+    pub reward_lamports: u64,
+    pub claimed_lamports: u64,
+}
+
+impl Referral {
+    pub fn record(&mut self, purchase_lamports: u64, reward_lamports: u64) -> Result<()> {
+        self.referred_participants = self.referred_participants
+            .checked_add(1)
+            .ok_or(IdoError::ErrMathOverflow)?;
+        self.referred_lamports = self.referred_lamports
+            .checked_add(purchase_lamports)
+            .ok_or(IdoError::ErrMathOverflow)?;
+        self.reward_lamports = self.reward_lamports
+            .checked_add(reward_lamports)
+            .ok_or(IdoError::ErrMathOverflow)?;
+
+        Ok(())
+    }
+}
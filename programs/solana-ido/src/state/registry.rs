@@ -0,0 +1,39 @@
+use {
+    crate::state::{CampaignStatus, IdoCampaign},
+    anchor_lang::prelude::*,
+};
+
+pub const CAMPAIGN_REGISTRY_VERSION: u8 = 1;
+
+/// Launchpad-wide counter, every campaign gets the next index on creation. Campaigns
+/// created before the registry existed are not in it.
+#[account]
+#[derive(InitSpace)]
+pub struct CampaignRegistry {
+    pub version: u8,
+    pub campaign_count: u64,
+}
+
+/// One per campaign at `[b"registry_entry", index]`, lets clients walk campaigns
+/// from 0 to `campaign_count` without scanning program accounts, or filter the
+/// entries by `authority` or `status` with `memcmp`.
+#[account]
+#[derive(InitSpace)]
+pub struct RegistryEntry {
+    pub version: u8,
+    pub index: u64,
+    pub ido_campaign: Pubkey,
+    pub authority: Pubkey,
+    pub token_mint: Pubkey,
+    pub created_at: u64,
+    /// Mirror of the campaign status, written by the instructions that move the campaign
+    /// to a new status when they are given the entry. Transitions driven by the clock
+    /// or by claims are picked up by `sync_registry_entry`.
+    pub status: CampaignStatus,
+}
+
+impl RegistryEntry {
+    pub fn record_status(&mut self, ido_campaign: &IdoCampaign) {
+        self.status = ido_campaign.status;
+    }
+}
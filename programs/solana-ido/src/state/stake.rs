@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+pub const STAKE_ACCOUNT_VERSION: u8 = 1;
+pub const STAKE_TIERS_VERSION: u8 = 1;
+pub const MAX_STAKE_TIERS: usize = 8;
+
+/// Launchpad tokens staked by `owner`, one per wallet at `[b"stake", owner]`.
+#[account]
+#[derive(InitSpace)]
+pub struct StakeAccount {
+    pub version: u8,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub locked_until: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub struct StakeTier {
+    pub min_stake: u64,
+    pub max_allocations: u64,
+}
+
+/// Per campaign table at `[b"stake_tiers", ido_campaign]` replacing the flat
+/// `available_allocations_per_participant`, sorted by `min_stake` ascending.
+#[account]
+#[derive(InitSpace)]
+pub struct StakeTiers {
+    pub version: u8,
+    pub ido_campaign: Pubkey,
+    #[max_len(MAX_STAKE_TIERS)]
+    pub tiers: Vec<StakeTier>,
+}
+
+impl StakeTiers {
+    /// Allocations of the highest tier `staked` qualifies for, 0 below the first tier.
+    pub fn max_allocations(&self, staked: u64) -> u64 {
+        self.tiers
+            .iter()
+            .rev()
+            .find(|tier| staked >= tier.min_stake)
+            .map_or(0, |tier| tier.max_allocations)
+    }
+}
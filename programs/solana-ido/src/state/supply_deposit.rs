@@ -0,0 +1,14 @@
+use anchor_lang::prelude::*;
+
+pub const SUPPLY_DEPOSIT_VERSION: u8 = 1;
+
+/// Sale supply a third-party funder deposited into a campaign at
+/// `[b"supply_deposit", ido_campaign, funder]`, returned to it if the campaign fails.
+#[account]
+#[derive(InitSpace)]
+pub struct SupplyDeposit {
+    pub version: u8,
+    pub ido_campaign: Pubkey,
+    pub funder: Pubkey,
+    pub amount: u64,
+}
@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+pub const TOKEN_LOCK_VERSION: u8 = 1;
+pub const MAX_TOKEN_LOCK_NAME_LEN: usize = 32;
+
+/// Team or advisor tokens of a campaign locked for one beneficiary at
+/// `[b"token_lock", ido_campaign, beneficiary]`, held by its vault at
+/// `[b"token_lock_vault", token_lock]` and vested like a sale position.
+#[account]
+#[derive(InitSpace)]
+pub struct TokenLock {
+    pub version: u8,
+    pub ido_campaign: Pubkey,
+    pub beneficiary: Pubkey,
+    #[max_len(MAX_TOKEN_LOCK_NAME_LEN)]
+    pub name: String,
+    /// Tokens the beneficiary gets in the end, cut down to the vested part when revoked,
+    /// which is then claimable right away.
+    pub amount: u64,
+    pub claimed: u64,
+    pub cliff: u64,
+    pub vesting_end_time: u64,
+    pub available_tokens_after_cliff_ptc: i32,
+    /// The authority can take back the unvested part.
+    pub revocable: bool,
+    pub revoked: bool,
+}
@@ -1,12 +1,127 @@
-use anchor_lang::prelude::*;
+use {
+    crate::{
+        instructions::IdoError,
+        state::{IdoCampaign, SaleMode},
+    },
+    anchor_lang::prelude::*,
+};
+
+pub const USER_VERSION: u8 = 1;
 
 #[account]
 #[derive(InitSpace)]
 pub struct User {
+    pub version: u8,
     pub ido_campaign: Pubkey,
     pub participant: Pubkey,
     pub amount: u64,
     pub paid_lamports: u64,
     pub claimed: u64,
     pub joined_at: u64,
-}
\ No newline at end of file
+    pub settled: bool,
+    /// Part of `paid_lamports` returned after the sale, known once settled.
+    pub refundable_lamports: u64,
+    /// Hash of the sealed batch auction bid, `paid_lamports` holds its deposit.
+    pub bid_commitment: [u8; 32],
+    pub bid_price_lamports: u64,
+    pub bid_quantity: u64,
+    pub bid_revealed: bool,
+    /// Bit `i` is set once the refund of milestone escrow tranche `i` was claimed.
+    pub escrow_refunded_tranches: u8,
+    /// The authority can take back the unvested part of the position with `revoke_position`.
+    pub revocable: bool,
+    pub revoked: bool,
+    /// Zeroed space for fields added without a realloc, new fields are carved from here.
+    pub reserved: [u8; 3],
+}
+
+impl User {
+    /// Fixes the final purchase once the sale succeeded, for sale modes that only know
+    /// the filled amount or the final price after `end_sale_time`.
+    pub fn settle(&mut self, ido_campaign: &mut IdoCampaign) -> Result<()> {
+        if self.settled {
+            return Ok(());
+        }
+
+        match ido_campaign.sale_mode {
+            SaleMode::ProRata => self.settle_pro_rata(ido_campaign)?,
+            SaleMode::DutchAuction if ido_campaign.settle_at_clearing_price => {
+                self.settle_at_clearing_price(ido_campaign)?
+            }
+            SaleMode::BatchAuction => self.settle_bid(ido_campaign)?,
+            _ => {}
+        }
+
+        self.settled = true;
+
+        Ok(())
+    }
+
+    /// Scales the commitment down to the filled part and only charges the filled tokens, the
+    /// rest of the payment is refundable. The fills of all participants add up to `total_sold`,
+    /// which is what `withdraw_funds` pays out.
+    fn settle_pro_rata(&mut self, ido_campaign: &mut IdoCampaign) -> Result<()> {
+        let committed = ido_campaign.total_committed;
+        let filled = ido_campaign.total_sold;
+
+        if committed > filled {
+            let filled_amount = ido_campaign.settle_shared_fill(self.amount, filled, committed)?;
+            let final_cost = filled_amount
+                .checked_mul(ido_campaign.price_lamports)
+                .ok_or(IdoError::ErrMathOverflow)?;
+
+            self.amount = filled_amount;
+            self.refundable_lamports = self.paid_lamports
+                .checked_sub(final_cost)
+                .ok_or(IdoError::ErrMathOverflow)?;
+            self.paid_lamports = final_cost;
+        }
+
+        Ok(())
+    }
+
+    fn settle_at_clearing_price(&mut self, ido_campaign: &IdoCampaign) -> Result<()> {
+        let final_cost = self.amount
+            .checked_mul(ido_campaign.clearing_price_lamports)
+            .ok_or(IdoError::ErrMathOverflow)?;
+
+        self.refundable_lamports = self.paid_lamports
+            .checked_sub(final_cost)
+            .ok_or(IdoError::ErrMathOverflow)?;
+        self.paid_lamports = final_cost;
+
+        Ok(())
+    }
+
+    /// Bids above the clearing price are filled in full, bids at it share what is left. Only
+    /// the filled tokens are charged at the clearing price, the rest of the deposit is
+    /// refundable, and the fills of all bids add up to `total_sold`.
+    fn settle_bid(&mut self, ido_campaign: &mut IdoCampaign) -> Result<()> {
+        let clearing_price = ido_campaign.clearing_price_lamports;
+
+        let filled = if !self.bid_revealed || self.bid_price_lamports < clearing_price {
+            0
+        } else if self.bid_price_lamports > clearing_price
+            || ido_campaign.marginal_bid_filled == ido_campaign.marginal_bid_demand
+        {
+            self.bid_quantity
+        } else {
+            ido_campaign.settle_shared_fill(
+                self.bid_quantity,
+                ido_campaign.marginal_bid_filled,
+                ido_campaign.marginal_bid_demand,
+            )?
+        };
+        let final_cost = filled
+            .checked_mul(clearing_price)
+            .ok_or(IdoError::ErrMathOverflow)?;
+
+        self.amount = filled;
+        self.refundable_lamports = self.paid_lamports
+            .checked_sub(final_cost)
+            .ok_or(IdoError::ErrMathOverflow)?;
+        self.paid_lamports = final_cost;
+
+        Ok(())
+    }
+}
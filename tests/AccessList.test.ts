@@ -0,0 +1,218 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { SolanaIdo } from "../target/types/solana_ido";
+import * as helpers from "../tests/helpers";
+import { expect } from "chai";
+import BN from "bn.js";
+import { Keypair, PublicKey } from "@solana/web3.js";
+
+describe("allowlist and blocklist tests", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.solanaIdo as Program<SolanaIdo>;
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+
+  before(async () => {
+    await helpers.ensureCampaignRegistry(program);
+  });
+
+  const writable = (pubkey: PublicKey) => ({ pubkey, isSigner: false, isWritable: true });
+
+  const setup = async (owner: Keypair, startSaleTime: BN, endSaleTime: BN, allowlist: boolean) => {
+    const { mint } = await helpers.createMintAndMintToOwner(provider, owner.publicKey);
+
+    return helpers.setupCampaign({
+      program,
+      provider,
+      owner,
+      mint,
+      startSaleTime,
+      endSaleTime,
+      cliff: endSaleTime.addn(2),
+      vestingEndTime: endSaleTime.addn(100),
+      priceLamports: helpers.priceLamports,
+      allocation: helpers.allocation,
+      softCap: helpers.softCap,
+      hardCap: helpers.hardCap,
+      availableTokensAfterCliffPtc: helpers.availableTokensAfterCliffPtc,
+      availableAllocationsPerParticipant: helpers.availableAllocationsPerParticipant,
+      configure: allowlist
+        ? async () => {
+            await program.methods
+              .configureAllowlist(true)
+              .accounts({ owner: owner.publicKey })
+              .signers([owner])
+              .rpc();
+          }
+        : undefined,
+    });
+  };
+
+  const join = (owner: Keypair, participant: Keypair, allocations: number, allowlistEntry: PublicKey | null) =>
+    program.methods
+      .joinIdo(new BN(allocations))
+      .accounts({
+        participant: participant.publicKey,
+        idoCampaignOwner: owner.publicKey,
+        stakeAccount: null,
+        stakeTiers: null,
+        lottery: null,
+        lotteryTicket: null,
+        instructionsSysvar: null,
+        joinThrottle: null,
+        allowlistEntry,
+      })
+      .signers([participant])
+      .rpc();
+
+  it("only allowlisted participants join, up to their custom cap", async () => {
+    const owner = Keypair.generate();
+    const [capped, uncapped, removed, stranger] = [...Array(4)].map(() => Keypair.generate());
+    const now = Math.floor(Date.now() / 1000);
+    const startSaleTime = new BN(now + 6);
+    const { idoCampaignPda } = await setup(owner, startSaleTime, new BN(now + 40), true);
+
+    const entryOf = (participant: Keypair) =>
+      helpers.findAllowlistEntryPda(program.programId, idoCampaignPda, participant.publicKey);
+    const listed = [capped, uncapped, removed];
+
+    await program.methods
+      .addToAllowlist([
+        { participant: capped.publicKey, maxAllocations: new BN(1) },
+        { participant: uncapped.publicKey, maxAllocations: new BN(0) },
+        { participant: removed.publicKey, maxAllocations: new BN(0) },
+      ])
+      .accounts({ owner: owner.publicKey })
+      .remainingAccounts(listed.map((participant) => writable(entryOf(participant))))
+      .signers([owner])
+      .rpc();
+
+    await program.methods
+      .removeFromAllowlist()
+      .accounts({ owner: owner.publicKey })
+      .remainingAccounts([writable(entryOf(removed))])
+      .signers([owner])
+      .rpc();
+    expect(await program.account.allowlistEntry.fetchNullable(entryOf(removed))).to.equal(null);
+
+    for (const participant of [...listed, stranger]) {
+      await helpers.airdropSol(provider, participant.publicKey, 10);
+    }
+    await helpers.waitUntil(startSaleTime.toNumber());
+
+    for (const participant of [removed, stranger]) {
+      try {
+        await join(owner, participant, 1, null);
+        expect.fail("Expected joinIdo to throw");
+      } catch (error: any) {
+        helpers.expectIdlError(program, error, { msg: "Participant not allowlisted" });
+      }
+    }
+
+    try {
+      await join(owner, capped, 2, entryOf(capped));
+      expect.fail("Expected joinIdo to throw");
+    } catch (error: any) {
+      helpers.expectIdlError(program, error, { msg: "Invalid number of allocations" });
+    }
+
+    await join(owner, capped, 1, entryOf(capped));
+    await join(owner, uncapped, 5, entryOf(uncapped));
+  });
+
+  it("blocked wallets can't join until the launchpad admin unblocks them", async () => {
+    await helpers.ensureLaunchpadConfig(program, provider);
+
+    const owner = Keypair.generate();
+    const blocked = Keypair.generate();
+    const now = Math.floor(Date.now() / 1000);
+    const startSaleTime = new BN(now + 4);
+    await setup(owner, startSaleTime, new BN(now + 40), false);
+
+    const blocklistEntry = helpers.findBlocklistEntryPda(program.programId, blocked.publicKey);
+    await program.methods
+      .addToBlocklist([blocked.publicKey])
+      .remainingAccounts([writable(blocklistEntry)])
+      .rpc();
+
+    try {
+      await program.methods
+        .addToBlocklist([owner.publicKey])
+        .accounts({ admin: owner.publicKey })
+        .remainingAccounts([writable(helpers.findBlocklistEntryPda(program.programId, owner.publicKey))])
+        .signers([owner])
+        .rpc();
+      expect.fail("Expected addToBlocklist to throw");
+    } catch (error: any) {
+      helpers.expectIdlError(program, error, { msg: "Unauthorized" });
+    }
+
+    await helpers.airdropSol(provider, blocked.publicKey, 10);
+    await helpers.waitUntil(startSaleTime.toNumber());
+
+    try {
+      await join(owner, blocked, 1, null);
+      expect.fail("Expected joinIdo to throw");
+    } catch (error: any) {
+      helpers.expectIdlError(program, error, { msg: "Participant is blocked" });
+    }
+
+    await program.methods
+      .removeFromBlocklist()
+      .remainingAccounts([writable(blocklistEntry)])
+      .rpc();
+
+    await join(owner, blocked, 1, null);
+  });
+
+  it("fits the largest batches a legacy transaction carries", async () => {
+    await helpers.ensureLaunchpadConfig(program, provider);
+
+    const maxAllowlistAdditions = 12;
+    const maxListRemovals = 28;
+    const maxBlocklistAdditions = 15;
+    const maxBlocklistRemovals = 30;
+
+    const owner = Keypair.generate();
+    const now = Math.floor(Date.now() / 1000);
+    const { idoCampaignPda } = await setup(owner, new BN(now + 30), new BN(now + 60), true);
+
+    const participants = [...Array(maxListRemovals)].map(() => Keypair.generate().publicKey);
+    const entries = participants.map((participant) =>
+      helpers.findAllowlistEntryPda(program.programId, idoCampaignPda, participant)
+    );
+    for (let start = 0; start < participants.length; start += maxAllowlistAdditions) {
+      const batch = participants.slice(start, start + maxAllowlistAdditions);
+      await program.methods
+        .addToAllowlist(batch.map((participant) => ({ participant, maxAllocations: new BN(0) })))
+        .accounts({ owner: owner.publicKey })
+        .remainingAccounts(entries.slice(start, start + maxAllowlistAdditions).map(writable))
+        .signers([owner])
+        .rpc();
+    }
+
+    await program.methods
+      .removeFromAllowlist()
+      .accounts({ owner: owner.publicKey })
+      .remainingAccounts(entries.map(writable))
+      .signers([owner])
+      .rpc();
+    expect(await program.account.allowlistEntry.fetchMultiple(entries)).to.deep.equal(entries.map(() => null));
+
+    const wallets = [...Array(maxBlocklistRemovals)].map(() => Keypair.generate().publicKey);
+    const blocklistEntries = wallets.map((wallet) => helpers.findBlocklistEntryPda(program.programId, wallet));
+    for (let start = 0; start < wallets.length; start += maxBlocklistAdditions) {
+      await program.methods
+        .addToBlocklist(wallets.slice(start, start + maxBlocklistAdditions))
+        .remainingAccounts(blocklistEntries.slice(start, start + maxBlocklistAdditions).map(writable))
+        .rpc();
+    }
+
+    await program.methods
+      .removeFromBlocklist()
+      .remainingAccounts(blocklistEntries.map(writable))
+      .rpc();
+    expect(await program.account.blocklistEntry.fetchMultiple(blocklistEntries)).to.deep.equal(
+      blocklistEntries.map(() => null)
+    );
+  });
+});
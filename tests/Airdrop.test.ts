@@ -0,0 +1,146 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { SolanaIdo } from "../target/types/solana_ido";
+import * as helpers from "../tests/helpers";
+import { expect } from "chai";
+import BN from "bn.js";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { getAccount, getAssociatedTokenAddressSync } from "@solana/spl-token";
+import { createHash } from "crypto";
+
+const sha256 = (...parts: Buffer[]): Buffer => createHash("sha256").update(Buffer.concat(parts)).digest();
+
+const airdropLeaf = (recipient: PublicKey, amount: number): Buffer =>
+  sha256(Buffer.from([0]), recipient.toBuffer(), new BN(amount).toArrayLike(Buffer, "le", 8));
+
+const airdropNode = (a: Buffer, b: Buffer): Buffer =>
+  Buffer.compare(a, b) <= 0 ? sha256(Buffer.from([1]), a, b) : sha256(Buffer.from([1]), b, a);
+
+describe("airdrop tests", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.solanaIdo as Program<SolanaIdo>;
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+
+  before(async () => {
+    await helpers.ensureCampaignRegistry(program);
+  });
+
+  it("recipients claim vested airdrops and the rest returns after the deadline", async () => {
+    const owner = Keypair.generate();
+    const [recipient, absentee] = [Keypair.generate(), Keypair.generate()];
+    const { mint } = await helpers.createMintAndMintToOwner(provider, owner.publicKey);
+
+    const recipientLeaf = airdropLeaf(recipient.publicKey, 300);
+    const absenteeLeaf = airdropLeaf(absentee.publicKey, 200);
+    const merkleRoot = airdropNode(recipientLeaf, absenteeLeaf);
+
+    const now = Math.floor(Date.now() / 1000);
+    const startSaleTime = new BN(now + 4);
+    const endSaleTime = new BN(now + 8);
+    const cliff = new BN(now + 12);
+    const claimDeadline = new BN(now + 16);
+    const vestingEndTime = new BN(now + 24);
+
+    const { ownerAta } = await helpers.setupCampaign({
+      program,
+      provider,
+      owner,
+      mint,
+      startSaleTime,
+      endSaleTime,
+      cliff,
+      vestingEndTime,
+      priceLamports: helpers.priceLamports,
+      allocation: helpers.allocation,
+      softCap: helpers.softCap,
+      hardCap: helpers.hardCap,
+      availableTokensAfterCliffPtc: helpers.availableTokensAfterCliffPtc,
+      availableAllocationsPerParticipant: helpers.availableAllocationsPerParticipant,
+      configure: async () => {
+        await program.methods
+          .configureAirdrop([...merkleRoot], claimDeadline)
+          .accounts({ owner: owner.publicKey })
+          .signers([owner])
+          .rpc();
+      },
+    });
+
+    for (const participant of [recipient, absentee]) {
+      await helpers.airdropSol(provider, participant.publicKey, 1);
+    }
+
+    await helpers.waitUntil(startSaleTime.toNumber());
+    try {
+      await program.methods
+        .joinIdo(new BN(1))
+        .accounts({ participant: recipient.publicKey, idoCampaignOwner: owner.publicKey })
+        .signers([recipient])
+        .rpc();
+      expect.fail("Expected joinIdo to throw");
+    } catch (error: any) {
+      helpers.expectIdlError(program, error, { msg: "Invalid price" });
+    }
+
+    const claimAirdrop = (participant: Keypair, amount: number, proof: Buffer[]) =>
+      program.methods
+        .claimAirdrop(
+          new BN(amount),
+          proof.map((node) => [...node])
+        )
+        .accounts({
+          participant: participant.publicKey,
+          idoCampaignOwner: owner.publicKey,
+          tokenMint: mint,
+        })
+        .signers([participant])
+        .rpc();
+
+    await helpers.waitUntil(endSaleTime.toNumber());
+    try {
+      await claimAirdrop(recipient, 400, [absenteeLeaf]);
+      expect.fail("Expected claimAirdrop to throw");
+    } catch (error: any) {
+      helpers.expectIdlError(program, error, { msg: "Invalid merkle proof" });
+    }
+
+    // Before the cliff the claim only opens the position.
+    await claimAirdrop(recipient, 300, [absenteeLeaf]);
+    const [idoCampaignPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("ido_campaign"), owner.publicKey.toBuffer()],
+      program.programId
+    );
+    const [userPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user"), idoCampaignPda.toBuffer(), recipient.publicKey.toBuffer()],
+      program.programId
+    );
+    const user = await program.account.user.fetch(userPda);
+    expect(user.amount.toNumber()).to.equal(300);
+    expect(user.claimed.toNumber()).to.equal(0);
+
+    await helpers.waitUntil(claimDeadline.toNumber());
+    try {
+      await claimAirdrop(absentee, 200, [recipientLeaf]);
+      expect.fail("Expected claimAirdrop to throw");
+    } catch (error: any) {
+      helpers.expectIdlError(program, error, { msg: "Claim deadline passed" });
+    }
+
+    await program.methods
+      .reclaimAirdrop()
+      .accounts({ owner: owner.publicKey, ownerTokenAccount: ownerAta, tokenMint: mint })
+      .signers([owner])
+      .rpc();
+    expect(Number((await getAccount(provider.connection, ownerAta)).amount)).to.equal(
+      helpers.hardCap.toNumber() - 300
+    );
+
+    await helpers.waitUntil(vestingEndTime.toNumber());
+    await claimAirdrop(recipient, 300, [absenteeLeaf]);
+
+    const recipientAta = getAssociatedTokenAddressSync(mint, recipient.publicKey, false);
+    expect(Number((await getAccount(provider.connection, recipientAta)).amount)).to.equal(300);
+
+    const campaign = await program.account.idoCampaign.fetch(idoCampaignPda);
+    expect(campaign.status).to.deep.equal({ finalized: {} });
+  });
+});
@@ -0,0 +1,163 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { SolanaIdo } from "../target/types/solana_ido";
+import * as helpers from "../tests/helpers";
+import { expect } from "chai";
+import BN from "bn.js";
+import {
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+  Transaction,
+} from "@solana/web3.js";
+
+describe("anti-bot tests", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.solanaIdo as Program<SolanaIdo>;
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+
+  before(async () => {
+    await helpers.ensureCampaignRegistry(program);
+  });
+
+  const setupProtectedCampaign = async (
+    startSaleTime: BN,
+    endSaleTime: BN,
+    antiBot: [boolean, BN, BN, BN]
+  ) => {
+    const owner = Keypair.generate();
+    const { mint } = await helpers.createMintAndMintToOwner(provider, owner.publicKey);
+
+    const { idoCampaignPda } = await helpers.setupCampaign({
+      program,
+      provider,
+      owner,
+      mint,
+      startSaleTime,
+      endSaleTime,
+      cliff: endSaleTime.addn(2),
+      vestingEndTime: endSaleTime.addn(100),
+      priceLamports: helpers.priceLamports,
+      allocation: helpers.allocation,
+      softCap: helpers.softCap,
+      hardCap: helpers.hardCap,
+      availableTokensAfterCliffPtc: helpers.availableTokensAfterCliffPtc,
+      availableAllocationsPerParticipant: helpers.availableAllocationsPerParticipant,
+      configure: async () => {
+        await program.methods
+          .configureAntiBot(...antiBot)
+          .accounts({ owner: owner.publicKey })
+          .signers([owner])
+          .rpc();
+      },
+    });
+
+    return { owner, idoCampaignPda };
+  };
+
+  const joinIx = (participant: Keypair, owner: Keypair, allocations: number, joinThrottle: PublicKey | null) =>
+    program.methods
+      .joinIdo(new BN(allocations))
+      .accounts({
+        participant: participant.publicKey,
+        idoCampaignOwner: owner.publicKey,
+        stakeAccount: null,
+        stakeTiers: null,
+        lottery: null,
+        lotteryTicket: null,
+        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+        joinThrottle,
+      })
+      .instruction();
+
+  it("rejects foreign instructions and caps allocations during the ramp-up", async () => {
+    const participant = Keypair.generate();
+    const now = Math.floor(Date.now() / 1000);
+    const startSaleTime = new BN(now + 4);
+    const { owner } = await setupProtectedCampaign(startSaleTime, new BN(now + 60), [
+      true,
+      new BN(0),
+      new BN(40),
+      new BN(1),
+    ]);
+
+    await helpers.airdropSol(provider, participant.publicKey, 10);
+    await helpers.waitUntil(startSaleTime.toNumber());
+
+    const send = async (tx: Transaction) =>
+      provider.sendAndConfirm(tx, [participant]);
+
+    try {
+      await send(
+        new Transaction().add(
+          SystemProgram.transfer({
+            fromPubkey: participant.publicKey,
+            toPubkey: owner.publicKey,
+            lamports: 1,
+          }),
+          await joinIx(participant, owner, 1, null)
+        )
+      );
+      expect.fail("Expected joinIdo to throw");
+    } catch (error: any) {
+      helpers.expectIdlError(program, error, { msg: "Transaction contains foreign instructions" });
+    }
+
+    // Right after the start only the ramp-up start allocation is available.
+    try {
+      await send(new Transaction().add(await joinIx(participant, owner, 5, null)));
+      expect.fail("Expected joinIdo to throw");
+    } catch (error: any) {
+      helpers.expectIdlError(program, error, { msg: "Invalid number of allocations" });
+    }
+
+    await send(new Transaction().add(await joinIx(participant, owner, 1, null)));
+  });
+
+  it("limits joins per wallet within a slot", async () => {
+    const participant = Keypair.generate();
+    const now = Math.floor(Date.now() / 1000);
+    const startSaleTime = new BN(now + 8);
+    const endSaleTime = new BN(now + 60);
+    const antiBot: [boolean, BN, BN, BN] = [false, new BN(1), new BN(0), new BN(0)];
+    const first = await setupProtectedCampaign(startSaleTime, endSaleTime, antiBot);
+    const second = await setupProtectedCampaign(startSaleTime, endSaleTime, antiBot);
+
+    await helpers.airdropSol(provider, participant.publicKey, 10);
+    await helpers.waitUntil(startSaleTime.toNumber());
+
+    try {
+      await provider.sendAndConfirm(
+        new Transaction().add(await joinIx(participant, first.owner, 1, null)),
+        [participant]
+      );
+      expect.fail("Expected joinIdo to throw");
+    } catch (error: any) {
+      helpers.expectIdlError(program, error, { msg: "Join throttle required" });
+    }
+
+    // The first throttled join creates the wallet's throttle.
+    const joinThrottle = helpers.findJoinThrottlePda(program.programId, participant.publicKey);
+
+    try {
+      await provider.sendAndConfirm(
+        new Transaction().add(
+          await joinIx(participant, first.owner, 1, joinThrottle),
+          await joinIx(participant, second.owner, 1, joinThrottle)
+        ),
+        [participant]
+      );
+      expect.fail("Expected joinIdo to throw");
+    } catch (error: any) {
+      helpers.expectIdlError(program, error, { msg: "Too many joins in this slot" });
+    }
+
+    await provider.sendAndConfirm(
+      new Transaction().add(await joinIx(participant, first.owner, 1, joinThrottle)),
+      [participant]
+    );
+    const throttle = await program.account.joinThrottle.fetch(joinThrottle);
+    expect(throttle.joinsInSlot.toNumber()).to.equal(1);
+  });
+});
@@ -0,0 +1,329 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { SolanaIdo } from "../target/types/solana_ido";
+import * as helpers from "../tests/helpers";
+import { expect } from "chai";
+import BN from "bn.js";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { randomBytes } from "crypto";
+
+describe("batch auction tests", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.solanaIdo as Program<SolanaIdo>;
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+
+  before(async () => {
+    await helpers.ensureCampaignRegistry(program);
+  });
+
+  const bidTickLamports = new BN(10_000);
+
+  type Bid = { bidder: Keypair; price: BN; quantity: BN; salt: Buffer };
+
+  it("settles every filled bid at the uniform clearing price", async () => {
+    const owner = Keypair.generate();
+    const { mint } = await helpers.createMintAndMintToOwner(provider, owner.publicKey);
+
+    const now = Math.floor(Date.now() / 1000);
+    const startSaleTime = new BN(now + 2);
+    const endSaleTime = new BN(now + 10);
+    const revealEndTime = new BN(now + 18);
+
+    const { idoCampaignPda } = await helpers.setupCampaign({
+      program,
+      provider,
+      owner,
+      mint,
+      startSaleTime,
+      endSaleTime,
+      cliff: new BN(now + 20),
+      vestingEndTime: new BN(now + 100),
+      priceLamports: helpers.priceLamports,
+      allocation: helpers.allocation,
+      softCap: helpers.softCap,
+      hardCap: helpers.hardCap,
+      availableTokensAfterCliffPtc: helpers.availableTokensAfterCliffPtc,
+      availableAllocationsPerParticipant: helpers.availableAllocationsPerParticipant,
+      configure: async () => {
+        await program.methods
+          .configureBatchAuction(bidTickLamports, revealEndTime)
+          .accounts({ owner: owner.publicKey })
+          .signers([owner])
+          .rpc();
+      },
+    });
+
+    // 1300 tokens demanded against a hard cap of 1000, the 120_000 level is the marginal one.
+    const bids: Bid[] = [
+      { bidder: Keypair.generate(), price: new BN(130_000), quantity: new BN(500), salt: randomBytes(32) },
+      { bidder: Keypair.generate(), price: new BN(120_000), quantity: new BN(500), salt: randomBytes(32) },
+      { bidder: Keypair.generate(), price: new BN(120_000), quantity: new BN(300), salt: randomBytes(32) },
+    ];
+
+    await helpers.waitUntil(startSaleTime.toNumber());
+    for (const { bidder, price, quantity, salt } of bids) {
+      await helpers.airdropSol(provider, bidder.publicKey, 10);
+      await program.methods
+        .commitBid(
+          helpers.batchBidCommitment(price, quantity, salt, bidder.publicKey),
+          price.mul(quantity)
+        )
+        .accounts({
+          participant: bidder.publicKey,
+          idoCampaignOwner: owner.publicKey,
+        })
+        .signers([bidder])
+        .rpc();
+    }
+
+    await helpers.waitUntil(endSaleTime.toNumber());
+
+    try {
+      await program.methods
+        .revealBid(bids[0].price, bids[0].quantity.addn(1), [...bids[0].salt])
+        .accounts({
+          participant: bids[0].bidder.publicKey,
+          idoCampaignOwner: owner.publicKey,
+        })
+        .signers([bids[0].bidder])
+        .rpc();
+      expect.fail("Expected revealBid to throw");
+    } catch (error: any) {
+      helpers.expectIdlError(program, error, { msg: "Bid does not match commitment" });
+    }
+
+    for (const { bidder, price, quantity, salt } of bids) {
+      await program.methods
+        .revealBid(price, quantity, [...salt])
+        .accounts({
+          participant: bidder.publicKey,
+          idoCampaignOwner: owner.publicKey,
+        })
+        .signers([bidder])
+        .rpc();
+    }
+
+    await helpers.waitUntil(revealEndTime.toNumber());
+
+    await program.methods
+      .settleBatchAuction()
+      .accounts({ idoCampaignOwner: owner.publicKey })
+      .rpc();
+
+    const campaign = await program.account.idoCampaign.fetch(idoCampaignPda);
+    expect(campaign.status).to.deep.equal({ succeeded: {} });
+    expect(campaign.clearingPriceLamports.toString()).to.equal("120000");
+    expect(campaign.totalSold.toString()).to.equal(helpers.hardCap.toString());
+
+    // Above the clearing price: filled in full, pays 500 * 120_000 out of a 65_000_000 deposit.
+    const balanceBefore = await provider.connection.getBalance(bids[0].bidder.publicKey);
+    await program.methods
+      .refundExcessDeposit()
+      .accounts({
+        participant: bids[0].bidder.publicKey,
+        idoCampaignOwner: owner.publicKey,
+      })
+      .signers([bids[0].bidder])
+      .rpc();
+    const balanceAfter = await provider.connection.getBalance(bids[0].bidder.publicKey);
+    expect(balanceAfter - balanceBefore).to.equal(5_000_000);
+
+    // At the clearing price: 500 of the 800 demanded tokens are shared pro-rata. The 312.5
+    // token share rounds down and only the 312 filled tokens are paid for.
+    const marginal = await program.methods
+      .getClaimable()
+      .accounts({
+        participant: bids[1].bidder.publicKey,
+        idoCampaignOwner: owner.publicKey,
+      })
+      .view();
+    expect(marginal.total.toString()).to.equal("312");
+    expect(marginal.refundableLamports.toString()).to.equal("22560000");
+
+    const [userPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user"), idoCampaignPda.toBuffer(), bids[0].bidder.publicKey.toBuffer()],
+      program.programId
+    );
+    const user = await program.account.user.fetch(userPda);
+    expect(user.amount.toString()).to.equal("500");
+    expect(user.paidLamports.toString()).to.equal("60000000");
+
+    // The last marginal bid to settle gets what is left, so the fills add up to total_sold.
+    const filled: number[] = [];
+    for (const { bidder } of bids.slice(1)) {
+      await program.methods
+        .refundExcessDeposit()
+        .accounts({
+          participant: bidder.publicKey,
+          idoCampaignOwner: owner.publicKey,
+        })
+        .signers([bidder])
+        .rpc();
+      const [pda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user"), idoCampaignPda.toBuffer(), bidder.publicKey.toBuffer()],
+        program.programId
+      );
+      const settled = await program.account.user.fetch(pda);
+      expect(settled.paidLamports.toString()).to.equal(settled.amount.muln(120_000).toString());
+      filled.push(settled.amount.toNumber());
+    }
+    expect(filled).to.deep.equal([312, 188]);
+  });
+
+  it("a marginal bid whose share rounds down to zero pays nothing", async () => {
+    const owner = Keypair.generate();
+    const { mint } = await helpers.createMintAndMintToOwner(provider, owner.publicKey);
+
+    const now = Math.floor(Date.now() / 1000);
+    const startSaleTime = new BN(now + 2);
+    const endSaleTime = new BN(now + 14);
+    const revealEndTime = new BN(now + 24);
+
+    const { idoCampaignPda } = await helpers.setupCampaign({
+      program,
+      provider,
+      owner,
+      mint,
+      startSaleTime,
+      endSaleTime,
+      cliff: new BN(now + 26),
+      vestingEndTime: new BN(now + 100),
+      priceLamports: helpers.priceLamports,
+      allocation: helpers.allocation,
+      softCap: helpers.softCap,
+      hardCap: helpers.hardCap,
+      availableTokensAfterCliffPtc: helpers.availableTokensAfterCliffPtc,
+      availableAllocationsPerParticipant: helpers.availableAllocationsPerParticipant,
+      configure: async () => {
+        await program.methods
+          .configureBatchAuction(bidTickLamports, revealEndTime)
+          .accounts({ owner: owner.publicKey })
+          .signers([owner])
+          .rpc();
+      },
+    });
+
+    // 999 tokens fill above the clearing price, the two 1 token bids at it share the last one.
+    const bids: Bid[] = [
+      { bidder: Keypair.generate(), price: new BN(130_000), quantity: new BN(500), salt: randomBytes(32) },
+      { bidder: Keypair.generate(), price: new BN(130_000), quantity: new BN(499), salt: randomBytes(32) },
+      { bidder: Keypair.generate(), price: new BN(120_000), quantity: new BN(1), salt: randomBytes(32) },
+      { bidder: Keypair.generate(), price: new BN(120_000), quantity: new BN(1), salt: randomBytes(32) },
+    ];
+
+    await helpers.waitUntil(startSaleTime.toNumber());
+    for (const { bidder, price, quantity, salt } of bids) {
+      await helpers.airdropSol(provider, bidder.publicKey, 10);
+      await program.methods
+        .commitBid(
+          helpers.batchBidCommitment(price, quantity, salt, bidder.publicKey),
+          price.mul(quantity)
+        )
+        .accounts({
+          participant: bidder.publicKey,
+          idoCampaignOwner: owner.publicKey,
+        })
+        .signers([bidder])
+        .rpc();
+    }
+
+    await helpers.waitUntil(endSaleTime.toNumber());
+    for (const { bidder, price, quantity, salt } of bids) {
+      await program.methods
+        .revealBid(price, quantity, [...salt])
+        .accounts({
+          participant: bidder.publicKey,
+          idoCampaignOwner: owner.publicKey,
+        })
+        .signers([bidder])
+        .rpc();
+    }
+
+    await helpers.waitUntil(revealEndTime.toNumber());
+    await program.methods
+      .settleBatchAuction()
+      .accounts({ idoCampaignOwner: owner.publicKey })
+      .rpc();
+
+    const [first, last] = bids.slice(2);
+    const balanceBefore = await provider.connection.getBalance(first.bidder.publicKey);
+    await program.methods
+      .refundExcessDeposit()
+      .accounts({
+        participant: first.bidder.publicKey,
+        idoCampaignOwner: owner.publicKey,
+      })
+      .signers([first.bidder])
+      .rpc();
+    const balanceAfter = await provider.connection.getBalance(first.bidder.publicKey);
+    expect(balanceAfter - balanceBefore).to.equal(120_000);
+
+    const [firstUserPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("user"), idoCampaignPda.toBuffer(), first.bidder.publicKey.toBuffer()],
+      program.programId
+    );
+    const firstUser = await program.account.user.fetch(firstUserPda);
+    expect(firstUser.amount.toString()).to.equal("0");
+    expect(firstUser.paidLamports.toString()).to.equal("0");
+
+    const lastClaimable = await program.methods
+      .getClaimable()
+      .accounts({
+        participant: last.bidder.publicKey,
+        idoCampaignOwner: owner.publicKey,
+      })
+      .view();
+    expect(lastClaimable.total.toString()).to.equal("1");
+    expect(lastClaimable.refundableLamports.toString()).to.equal("0");
+  });
+
+  it("join_ido is rejected for batch auctions", async () => {
+    const owner = Keypair.generate();
+    const participant = Keypair.generate();
+    const { mint } = await helpers.createMintAndMintToOwner(provider, owner.publicKey);
+
+    const now = Math.floor(Date.now() / 1000);
+    const startSaleTime = new BN(now + 1);
+    const endSaleTime = new BN(now + 10);
+
+    const { idoCampaignPda } = await helpers.setupCampaign({
+      program,
+      provider,
+      owner,
+      mint,
+      startSaleTime,
+      endSaleTime,
+      cliff: new BN(now + 20),
+      vestingEndTime: new BN(now + 30),
+      priceLamports: helpers.priceLamports,
+      allocation: helpers.allocation,
+      softCap: helpers.softCap,
+      hardCap: helpers.hardCap,
+      availableTokensAfterCliffPtc: helpers.availableTokensAfterCliffPtc,
+      availableAllocationsPerParticipant: helpers.availableAllocationsPerParticipant,
+      configure: async () => {
+        await program.methods
+          .configureBatchAuction(bidTickLamports, new BN(now + 15))
+          .accounts({ owner: owner.publicKey })
+          .signers([owner])
+          .rpc();
+      },
+    });
+
+    try {
+      await helpers.joinAsParticipant({
+        program,
+        provider,
+        owner,
+        participant,
+        idoCampaignPda,
+        startSaleTime,
+        endSaleTime,
+        allocations: new BN(1),
+      });
+      expect.fail("Expected joinIdo to throw");
+    } catch (error: any) {
+      helpers.expectIdlError(program, error, { msg: "Invalid sale mode" });
+    }
+  });
+});
@@ -0,0 +1,178 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { SolanaIdo } from "../target/types/solana_ido";
+import * as helpers from "../tests/helpers";
+import { expect } from "chai";
+import BN from "bn.js";
+import { Keypair } from "@solana/web3.js";
+
+describe("bonding curve tests", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.solanaIdo as Program<SolanaIdo>;
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+
+  before(async () => {
+    await helpers.ensureCampaignRegistry(program);
+  });
+
+  it("join_ido charges the integral of a linear curve over the purchased range", async () => {
+    const owner = Keypair.generate();
+    const first = Keypair.generate();
+    const second = Keypair.generate();
+    const { mint } = await helpers.createMintAndMintToOwner(provider, owner.publicKey);
+
+    const now = Math.floor(Date.now() / 1000);
+    const startSaleTime = new BN(now + 2);
+    const endSaleTime = new BN(now + 10);
+    const curve = { linear: { slopeLamports: new BN(50) } };
+
+    const { idoCampaignPda } = await helpers.setupCampaign({
+      program,
+      provider,
+      owner,
+      mint,
+      startSaleTime,
+      endSaleTime,
+      cliff: new BN(now + 12),
+      vestingEndTime: new BN(now + 100),
+      priceLamports: helpers.priceLamports,
+      allocation: helpers.allocation,
+      softCap: helpers.softCap,
+      hardCap: helpers.hardCap,
+      availableTokensAfterCliffPtc: helpers.availableTokensAfterCliffPtc,
+      availableAllocationsPerParticipant: helpers.availableAllocationsPerParticipant,
+      configure: async () => {
+        await program.methods
+          .configureBondingCurve(curve)
+          .accounts({ owner: owner.publicKey })
+          .signers([owner])
+          .rpc();
+      },
+    });
+
+    const quote = await program.methods
+      .quotePurchase(new BN(3))
+      .accounts({ idoCampaignOwner: owner.publicKey })
+      .view();
+    const expectedFirstCost = helpers.bondingCurveCost(
+      curve,
+      helpers.priceLamports,
+      new BN(0),
+      new BN(300)
+    );
+    expect(quote.totalCostLamports.toString()).to.equal(expectedFirstCost.toString());
+
+    const { userPda: firstUserPda } = await helpers.joinAsParticipant({
+      program,
+      provider,
+      owner,
+      participant: first,
+      idoCampaignPda,
+      startSaleTime,
+      endSaleTime,
+      allocations: new BN(3),
+    });
+    const { userPda: secondUserPda } = await helpers.joinAsParticipant({
+      program,
+      provider,
+      owner,
+      participant: second,
+      idoCampaignPda,
+      startSaleTime,
+      endSaleTime,
+      allocations: new BN(3),
+    });
+
+    const firstUser = await program.account.user.fetch(firstUserPda);
+    const secondUser = await program.account.user.fetch(secondUserPda);
+    expect(firstUser.paidLamports.toString()).to.equal(expectedFirstCost.toString());
+    expect(secondUser.paidLamports.toString()).to.equal(
+      helpers.bondingCurveCost(curve, helpers.priceLamports, new BN(300), new BN(300)).toString()
+    );
+
+    const status = await program.methods
+      .getCampaignStatus()
+      .accounts({ idoCampaignOwner: owner.publicKey })
+      .view();
+    expect(status.currentPriceLamports.toString()).to.equal(
+      helpers.priceLamports.add(new BN(50 * 600)).toString()
+    );
+  });
+
+  it("quote_purchase matches the client side integral of exponential steps", async () => {
+    const owner = Keypair.generate();
+    const { mint } = await helpers.createMintAndMintToOwner(provider, owner.publicKey);
+
+    const now = Math.floor(Date.now() / 1000);
+    const curve = { exponentialSteps: { stepTokens: new BN(150), growthBps: 1_000 } };
+
+    await helpers.setupCampaign({
+      program,
+      provider,
+      owner,
+      mint,
+      startSaleTime: new BN(now + 10),
+      endSaleTime: new BN(now + 20),
+      cliff: new BN(now + 30),
+      vestingEndTime: new BN(now + 40),
+      priceLamports: helpers.priceLamports,
+      allocation: helpers.allocation,
+      softCap: helpers.softCap,
+      hardCap: helpers.hardCap,
+      availableTokensAfterCliffPtc: helpers.availableTokensAfterCliffPtc,
+      availableAllocationsPerParticipant: helpers.availableAllocationsPerParticipant,
+      configure: async () => {
+        await program.methods
+          .configureBondingCurve(curve)
+          .accounts({ owner: owner.publicKey })
+          .signers([owner])
+          .rpc();
+      },
+    });
+
+    // 500 tokens span 4 steps: 150 at 100_000, 150 at 110_000, 150 at 121_000, 50 at 133_100.
+    const quote = await program.methods
+      .quotePurchase(new BN(5))
+      .accounts({ idoCampaignOwner: owner.publicKey })
+      .view();
+    expect(quote.totalCostLamports.toString()).to.equal("56305000");
+    expect(quote.totalCostLamports.toString()).to.equal(
+      helpers.bondingCurveCost(curve, helpers.priceLamports, new BN(0), new BN(500)).toString()
+    );
+  });
+
+  it("rejects curves with too many steps up to hard cap", async () => {
+    const owner = Keypair.generate();
+    const { mint } = await helpers.createMintAndMintToOwner(provider, owner.publicKey);
+
+    const now = Math.floor(Date.now() / 1000);
+    await helpers.setupCampaign({
+      program,
+      provider,
+      owner,
+      mint,
+      startSaleTime: new BN(now + 10),
+      endSaleTime: new BN(now + 20),
+      cliff: new BN(now + 30),
+      vestingEndTime: new BN(now + 40),
+      priceLamports: helpers.priceLamports,
+      allocation: helpers.allocation,
+      softCap: helpers.softCap,
+      hardCap: helpers.hardCap,
+      availableTokensAfterCliffPtc: helpers.availableTokensAfterCliffPtc,
+      availableAllocationsPerParticipant: helpers.availableAllocationsPerParticipant,
+      configure: async () => {
+        try {
+          await program.methods
+            .configureBondingCurve({ exponentialSteps: { stepTokens: new BN(1), growthBps: 1 } })
+            .accounts({ owner: owner.publicKey })
+            .signers([owner])
+            .rpc();
+          expect.fail("Expected configureBondingCurve to throw");
+        } catch (error: any) {
+          helpers.expectIdlError(program, error, { msg: "Invalid bonding curve" });
+        }
+      },
+    });
+  });
+});
@@ -0,0 +1,85 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { SolanaIdo } from "../target/types/solana_ido";
+import * as helpers from "../tests/helpers";
+import { expect } from "chai";
+import BN from "bn.js";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { getAccount, getMint } from "@solana/spl-token";
+
+const LAUNCHPAD_OWNER = new PublicKey("BRhY2VPGiDvEnQphYjgvbCXRkGTLBY4bXzeYjDuKYkv6");
+
+describe("burn unsold tokens tests", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.solanaIdo as Program<SolanaIdo>;
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+
+  before(async () => {
+    await helpers.ensureCampaignRegistry(program);
+    await helpers.airdropSol(provider, LAUNCHPAD_OWNER, 0.01);
+  });
+
+  it("withdraw_funds burns the unsold tokens and records the amount", async () => {
+    const owner = Keypair.generate();
+    const participant = Keypair.generate();
+    const { mint } = await helpers.createMintAndMintToOwner(provider, owner.publicKey);
+
+    const now = Math.floor(Date.now() / 1000);
+    const startSaleTime = new BN(now + 4);
+    const endSaleTime = new BN(now + 8);
+
+    const { idoCampaignPda, ownerAta } = await helpers.setupCampaign({
+      program,
+      provider,
+      owner,
+      mint,
+      startSaleTime,
+      endSaleTime,
+      cliff: new BN(now + 10),
+      vestingEndTime: new BN(now + 100),
+      priceLamports: helpers.priceLamports,
+      allocation: helpers.allocation,
+      softCap: helpers.softCap,
+      hardCap: helpers.hardCap,
+      availableTokensAfterCliffPtc: helpers.availableTokensAfterCliffPtc,
+      availableAllocationsPerParticipant: helpers.availableAllocationsPerParticipant,
+      configure: async () => {
+        await program.methods
+          .configureUnsoldTokenBurn(true)
+          .accounts({ owner: owner.publicKey })
+          .signers([owner])
+          .rpc();
+      },
+    });
+
+    await helpers.joinAsParticipant({
+      program,
+      provider,
+      owner,
+      participant,
+      idoCampaignPda,
+      startSaleTime,
+      endSaleTime,
+      allocations: new BN(5),
+    });
+
+    await helpers.waitUntil(endSaleTime.toNumber());
+    const supplyBefore = (await getMint(provider.connection, mint)).supply;
+    await program.methods
+      .withdrawFunds()
+      .accounts({
+        launchpadOwner: LAUNCHPAD_OWNER,
+        owner: owner.publicKey,
+        ownerTokenAccount: ownerAta,
+        tokenMint: mint,
+        milestoneEscrow: null,
+      })
+      .signers([owner])
+      .rpc();
+
+    const campaign = await program.account.idoCampaign.fetch(idoCampaignPda);
+    expect(campaign.unsoldTokensBurned.toNumber()).to.equal(500);
+    expect(Number(supplyBefore - (await getMint(provider.connection, mint)).supply)).to.equal(500);
+    expect(Number((await getAccount(provider.connection, ownerAta)).amount)).to.equal(0);
+  });
+});
@@ -0,0 +1,143 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { SolanaIdo } from "../target/types/solana_ido";
+import * as helpers from "../tests/helpers";
+import * as client from "../client";
+import { expect } from "chai";
+import BN from "bn.js";
+import { Keypair, PublicKey } from "@solana/web3.js";
+
+describe("campaign metadata tests", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.solanaIdo as Program<SolanaIdo>;
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+
+  before(async () => {
+    await helpers.ensureCampaignRegistry(program);
+  });
+
+  const initializeSale = async (
+    owner: Keypair,
+    mint: PublicKey,
+    startSaleTime: BN,
+    metadata = helpers.campaignMetadata
+  ) => {
+    const endSaleTime = startSaleTime.add(new BN(10));
+    await program.methods
+      .initializeSale(
+        startSaleTime,
+        endSaleTime,
+        endSaleTime.add(new BN(10)),
+        endSaleTime.add(new BN(100)),
+        helpers.priceLamports,
+        helpers.allocation,
+        helpers.softCap,
+        helpers.hardCap,
+        helpers.availableTokensAfterCliffPtc,
+        helpers.availableAllocationsPerParticipant,
+        metadata
+      )
+      .accounts({
+        owner: owner.publicKey,
+        tokenMint: mint,
+      })
+      .signers([owner])
+      .rpc();
+
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("ido_campaign"), owner.publicKey.toBuffer()],
+      program.programId
+    )[0];
+  };
+
+  it("metadata is set at creation and updatable before the sale starts", async () => {
+    const owner = Keypair.generate();
+    await helpers.airdropSol(provider, owner.publicKey, 10);
+    const { mint } = await helpers.createMintAndMintToOwner(provider, owner.publicKey);
+
+    const now = Math.floor(Date.now() / 1000);
+    const idoCampaignPda = await initializeSale(owner, mint, new BN(now + 100), {
+      name: "Project",
+      symbol: "PRJ",
+      uri: "https://example.com/logo.png",
+      description: "First description",
+      socialLinks: ["https://x.com/project"],
+    });
+
+    let metadata = await client.fetchCampaignMetadata(program, idoCampaignPda);
+    expect(metadata.name).to.equal("Project");
+    expect(metadata.description).to.equal("First description");
+
+    // Metadata already exists, the backfill instruction is only for older campaigns.
+    try {
+      await program.methods
+        .initializeCampaignMetadata("Project", "PRJ", "", "", [])
+        .accounts({ owner: owner.publicKey })
+        .signers([owner])
+        .rpc();
+      expect.fail("Expected initializeCampaignMetadata to throw");
+    } catch (error: any) {
+      expect(error).to.not.be.null;
+    }
+
+    await program.methods
+      .updateCampaignMetadata(
+        "Project Renamed",
+        "PRJ",
+        "https://example.com/logo-v2.png",
+        "Second description",
+        ["https://x.com/project", "https://t.me/project"]
+      )
+      .accounts({ owner: owner.publicKey })
+      .signers([owner])
+      .rpc();
+
+    metadata = await client.fetchCampaignMetadata(program, idoCampaignPda);
+    expect(metadata.idoCampaign.toString()).to.equal(idoCampaignPda.toString());
+    expect(metadata.name).to.equal("Project Renamed");
+    expect(metadata.uri).to.equal("https://example.com/logo-v2.png");
+    expect(metadata.description).to.equal("Second description");
+    expect(metadata.socialLinks).to.deep.equal(["https://x.com/project", "https://t.me/project"]);
+  });
+
+  it("rejects names longer than the bound", async () => {
+    const owner = Keypair.generate();
+    await helpers.airdropSol(provider, owner.publicKey, 10);
+    const { mint } = await helpers.createMintAndMintToOwner(provider, owner.publicKey);
+
+    const now = Math.floor(Date.now() / 1000);
+
+    try {
+      await initializeSale(owner, mint, new BN(now + 100), {
+        ...helpers.campaignMetadata,
+        name: "x".repeat(33),
+      });
+      expect.fail("Expected initializeSale to throw");
+    } catch (error: any) {
+      helpers.expectIdlError(program, error, { msg: "Invalid campaign metadata" });
+    }
+  });
+
+  it("update fails once the sale has started", async () => {
+    const owner = Keypair.generate();
+    await helpers.airdropSol(provider, owner.publicKey, 10);
+    const { mint } = await helpers.createMintAndMintToOwner(provider, owner.publicKey);
+
+    const now = Math.floor(Date.now() / 1000);
+    const startSaleTime = new BN(now + 3);
+    await initializeSale(owner, mint, startSaleTime);
+
+    await helpers.waitUntil(startSaleTime.toNumber());
+
+    try {
+      await program.methods
+        .updateCampaignMetadata("Too late", "PRJ", "", "", [])
+        .accounts({ owner: owner.publicKey })
+        .signers([owner])
+        .rpc();
+      expect.fail("Expected updateCampaignMetadata to throw");
+    } catch (error: any) {
+      helpers.expectIdlError(program, error, { msg: "Sale already started" });
+    }
+  });
+});
@@ -0,0 +1,137 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { SolanaIdo } from "../target/types/solana_ido";
+import * as helpers from "../tests/helpers";
+import * as client from "../client";
+import { expect } from "chai";
+import BN from "bn.js";
+import { Keypair, PublicKey } from "@solana/web3.js";
+
+describe("campaign registry tests", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.solanaIdo as Program<SolanaIdo>;
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+
+  before(async () => {
+    await helpers.ensureCampaignRegistry(program);
+  });
+
+  it("initialize_sale appends the campaign to the registry", async () => {
+    const owner = Keypair.generate();
+    await helpers.airdropSol(provider, owner.publicKey, 10);
+    const { mint } = await helpers.createMintAndMintToOwner(provider, owner.publicKey);
+
+    const registryPda = client.findCampaignRegistryPda(program.programId);
+    const registryBefore = await program.account.campaignRegistry.fetch(registryPda);
+
+    const now = Math.floor(Date.now() / 1000);
+    const startSaleTime = new BN(now + 100);
+    const endSaleTime = startSaleTime.add(new BN(100));
+    await program.methods
+      .initializeSale(
+        startSaleTime,
+        endSaleTime,
+        endSaleTime.add(new BN(10)),
+        endSaleTime.add(new BN(100)),
+        helpers.priceLamports,
+        helpers.allocation,
+        helpers.softCap,
+        helpers.hardCap,
+        helpers.availableTokensAfterCliffPtc,
+        helpers.availableAllocationsPerParticipant,
+        helpers.campaignMetadata
+      )
+      .accounts({
+        owner: owner.publicKey,
+        tokenMint: mint,
+      })
+      .signers([owner])
+      .rpc();
+
+    const [idoCampaignPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("ido_campaign"), owner.publicKey.toBuffer()],
+      program.programId
+    );
+
+    const registryAfter = await program.account.campaignRegistry.fetch(registryPda);
+    expect(registryAfter.campaignCount.toString()).to.equal(
+      registryBefore.campaignCount.add(new BN(1)).toString()
+    );
+
+    const entry = await program.account.registryEntry.fetch(
+      client.findRegistryEntryPda(program.programId, registryBefore.campaignCount)
+    );
+    expect(entry.index.toString()).to.equal(registryBefore.campaignCount.toString());
+    expect(entry.idoCampaign.toString()).to.equal(idoCampaignPda.toString());
+    expect(entry.authority.toString()).to.equal(owner.publicKey.toString());
+    expect(entry.tokenMint.toString()).to.equal(mint.toString());
+
+    expect(entry.status).to.deep.equal({ created: {} });
+
+    const byAuthority = await client.fetchRegisteredCampaigns(program, {
+      authority: owner.publicKey,
+    });
+    expect(byAuthority.length).to.equal(1);
+    expect(byAuthority[0].entry.idoCampaign.toString()).to.equal(idoCampaignPda.toString());
+
+    const byAuthorityAndStatus = await client.fetchRegisteredCampaigns(program, {
+      authority: owner.publicKey,
+      status: "created",
+    });
+    expect(byAuthorityAndStatus.length).to.equal(1);
+
+    const wrongStatus = await client.fetchRegisteredCampaigns(program, {
+      authority: owner.publicKey,
+      status: "live",
+    });
+    expect(wrongStatus.length).to.equal(0);
+  });
+
+  it("mirrors status changes into the registry entry", async () => {
+    const owner = Keypair.generate();
+    const { mint } = await helpers.createMintAndMintToOwner(provider, owner.publicKey);
+    const registryPda = client.findCampaignRegistryPda(program.programId);
+    const index = (await program.account.campaignRegistry.fetch(registryPda)).campaignCount;
+    const registryEntry = client.findRegistryEntryPda(program.programId, index);
+
+    const now = Math.floor(Date.now() / 1000);
+    const startSaleTime = new BN(now + 6);
+    const endSaleTime = new BN(now + 40);
+    const { ownerAta } = await helpers.setupCampaign({
+      program,
+      provider,
+      owner,
+      mint,
+      startSaleTime,
+      endSaleTime,
+      cliff: new BN(now + 42),
+      vestingEndTime: new BN(now + 100),
+      priceLamports: helpers.priceLamports,
+      allocation: helpers.allocation,
+      softCap: helpers.softCap,
+      hardCap: helpers.hardCap,
+      availableTokensAfterCliffPtc: helpers.availableTokensAfterCliffPtc,
+      availableAllocationsPerParticipant: helpers.availableAllocationsPerParticipant,
+    });
+
+    // setupCampaign deposits without the entry, the sync catches up.
+    const statusOf = async () => (await program.account.registryEntry.fetch(registryEntry)).status;
+    expect(await statusOf()).to.deep.equal({ created: {} });
+    await client.syncRegistryEntry(program, owner.publicKey, registryEntry);
+    expect(await statusOf()).to.deep.equal({ funded: {} });
+
+    const funded = await client.fetchRegisteredCampaigns(program, { authority: owner.publicKey, status: "funded" });
+    expect(funded.length).to.equal(1);
+
+    await helpers.waitUntil(startSaleTime.toNumber());
+    await program.methods
+      .closeCampaign()
+      .accounts({ owner: owner.publicKey, ownerTokenAccount: ownerAta, tokenMint: mint, registryEntry })
+      .signers([owner])
+      .rpc();
+    expect(await statusOf()).to.deep.equal({ cancelled: {} });
+
+    const cancelled = await client.fetchRegisteredCampaigns(program, { status: "cancelled" });
+    expect(cancelled.map(({ entry }: any) => entry.authority.toBase58())).to.include(owner.publicKey.toBase58());
+  });
+});
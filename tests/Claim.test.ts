@@ -16,6 +16,10 @@ describe("claim tests", () => {
   const program = anchor.workspace.solanaIdo as Program<SolanaIdo>;
   const provider = anchor.getProvider() as anchor.AnchorProvider;
 
+  before(async () => {
+    await helpers.ensureCampaignRegistry(program);
+  });
+
   it("claim before cliff returns ErrNothingToClaim", async () => {
     const owner = Keypair.generate();
     const participant = Keypair.generate();
@@ -90,6 +94,76 @@ describe("claim tests", () => {
     }
   });
 
+  it("claim after a sale that missed the soft cap returns ErrSoftCapNotReached", async () => {
+    const owner = Keypair.generate();
+    const participant = Keypair.generate();
+    const { mint } = await helpers.createMintAndMintToOwner(provider, owner.publicKey);
+
+    const now = Math.floor(Date.now() / 1000);
+    const startSaleTime = new BN(now + 1);
+    const endSaleTime = new BN(now + 6);
+    const cliff = new BN(now + 8);
+    const vestingEndTime = new BN(now + 12);
+
+    const { idoCampaignPda, tokensTreasuryPda } = await helpers.setupCampaign({
+      program,
+      provider,
+      owner,
+      mint,
+      startSaleTime,
+      endSaleTime,
+      cliff,
+      vestingEndTime,
+      priceLamports: helpers.priceLamports,
+      allocation: new BN(100),
+      softCap: new BN(500),
+      hardCap: new BN(1000),
+      availableTokensAfterCliffPtc: 20,
+      availableAllocationsPerParticipant: new BN(20),
+    });
+
+    const { userPda } = await helpers.joinAsParticipant({
+      program,
+      provider,
+      owner,
+      participant,
+      idoCampaignPda,
+      startSaleTime,
+      endSaleTime,
+      allocations: new BN(1),
+    });
+
+    const participantAta = getAssociatedTokenAddressSync(mint, participant.publicKey, false);
+
+    // Vested by now, but the sale never succeeded.
+    await helpers.waitUntil(vestingEndTime.toNumber());
+
+    try {
+      await program.methods
+        .claim()
+        .accountsStrict({
+          participant: participant.publicKey,
+          idoCampaignOwner: owner.publicKey,
+          idoCampaign: idoCampaignPda,
+          tokensTreasury: tokensTreasuryPda,
+          tokenMint: mint,
+          participantTokenAccount: participantAta,
+          user: userPda,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([participant])
+        .rpc();
+      expect.fail("Expected claim to throw");
+    } catch (error: any) {
+      helpers.expectIdlError(program, error, { msg: "Soft cap not reached" });
+    }
+
+    const idoCampaign = await program.account.idoCampaign.fetch(idoCampaignPda);
+    expect(idoCampaign.status).to.deep.equal({ ended: {} });
+  });
+
   it("claim after cliff unlocks initial pct and transfers to participant ATA", async () => {
     const owner = Keypair.generate();
     const participant = Keypair.generate();
@@ -135,6 +209,18 @@ describe("claim tests", () => {
       allocations: new BN(1),
     });
 
+    // A second participant tops the sale up to the soft cap so it succeeds.
+    await helpers.joinAsParticipant({
+      program,
+      provider,
+      owner,
+      participant: Keypair.generate(),
+      idoCampaignPda,
+      startSaleTime,
+      endSaleTime,
+      allocations: new BN(4),
+    });
+
     const participantAta = getAssociatedTokenAddressSync(mint, participant.publicKey, false);
 
     await helpers.waitUntil(cliff.toNumber() + 3);
@@ -230,6 +316,18 @@ describe("claim tests", () => {
       allocations: new BN(1),
     });
 
+    // A second participant tops the sale up to the soft cap so it succeeds.
+    await helpers.joinAsParticipant({
+      program,
+      provider,
+      owner,
+      participant: Keypair.generate(),
+      idoCampaignPda,
+      startSaleTime,
+      endSaleTime,
+      allocations: new BN(4),
+    });
+
     const participantAta = getAssociatedTokenAddressSync(mint, participant.publicKey, false);
 
     await helpers.waitUntil(cliff.toNumber() + 1);
@@ -350,6 +448,18 @@ describe("claim tests", () => {
       allocations: new BN(1),
     });
 
+    // A second participant tops the sale up to the soft cap so it succeeds.
+    await helpers.joinAsParticipant({
+      program,
+      provider,
+      owner,
+      participant: Keypair.generate(),
+      idoCampaignPda,
+      startSaleTime,
+      endSaleTime,
+      allocations: new BN(4),
+    });
+
     const participantAta = getAssociatedTokenAddressSync(mint, participant.publicKey, false);
 
     await helpers.waitUntil(vestingEndTime.toNumber());
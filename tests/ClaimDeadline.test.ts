@@ -0,0 +1,131 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { SolanaIdo } from "../target/types/solana_ido";
+import * as helpers from "../tests/helpers";
+import { expect } from "chai";
+import BN from "bn.js";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { getAccount } from "@solana/spl-token";
+
+const LAUNCHPAD_OWNER = new PublicKey("BRhY2VPGiDvEnQphYjgvbCXRkGTLBY4bXzeYjDuKYkv6");
+
+describe("claim deadline tests", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.solanaIdo as Program<SolanaIdo>;
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+
+  before(async () => {
+    await helpers.ensureCampaignRegistry(program);
+    await helpers.airdropSol(provider, LAUNCHPAD_OWNER, 0.01);
+  });
+
+  it("authority sweeps tokens unclaimed by the deadline and finalizes the campaign", async () => {
+    const owner = Keypair.generate();
+    const [claimer, absentee] = [Keypair.generate(), Keypair.generate()];
+    const { mint } = await helpers.createMintAndMintToOwner(provider, owner.publicKey);
+
+    const now = Math.floor(Date.now() / 1000);
+    const startSaleTime = new BN(now + 4);
+    const endSaleTime = new BN(now + 8);
+    const vestingEndTime = new BN(now + 14);
+    const claimDeadline = new BN(now + 18);
+
+    const { idoCampaignPda, ownerAta } = await helpers.setupCampaign({
+      program,
+      provider,
+      owner,
+      mint,
+      startSaleTime,
+      endSaleTime,
+      cliff: new BN(now + 10),
+      vestingEndTime,
+      priceLamports: helpers.priceLamports,
+      allocation: helpers.allocation,
+      softCap: helpers.softCap,
+      hardCap: helpers.hardCap,
+      availableTokensAfterCliffPtc: helpers.availableTokensAfterCliffPtc,
+      availableAllocationsPerParticipant: helpers.availableAllocationsPerParticipant,
+      configure: async () => {
+        try {
+          await program.methods
+            .configureClaimDeadline(vestingEndTime)
+            .accounts({ owner: owner.publicKey })
+            .signers([owner])
+            .rpc();
+          expect.fail("Expected configureClaimDeadline to throw");
+        } catch (error: any) {
+          helpers.expectIdlError(program, error, { msg: "Invalid claim deadline" });
+        }
+
+        await program.methods
+          .configureClaimDeadline(claimDeadline)
+          .accounts({ owner: owner.publicKey })
+          .signers([owner])
+          .rpc();
+      },
+    });
+
+    for (const participant of [claimer, absentee]) {
+      await helpers.joinAsParticipant({
+        program,
+        provider,
+        owner,
+        participant,
+        idoCampaignPda,
+        startSaleTime,
+        endSaleTime,
+        allocations: new BN(5),
+      });
+    }
+
+    const claim = (participant: Keypair) =>
+      program.methods
+        .claim()
+        .accounts({ participant: participant.publicKey, idoCampaignOwner: owner.publicKey, tokenMint: mint })
+        .signers([participant])
+        .rpc();
+    const sweep = () =>
+      program.methods
+        .sweepUnclaimedTokens()
+        .accounts({ owner: owner.publicKey, ownerTokenAccount: ownerAta, tokenMint: mint })
+        .signers([owner])
+        .rpc();
+
+    await helpers.waitUntil(endSaleTime.toNumber());
+    await program.methods
+      .withdrawFunds()
+      .accounts({
+        launchpadOwner: LAUNCHPAD_OWNER,
+        owner: owner.publicKey,
+        ownerTokenAccount: ownerAta,
+        tokenMint: mint,
+        milestoneEscrow: null,
+      })
+      .signers([owner])
+      .rpc();
+
+    await helpers.waitUntil(vestingEndTime.toNumber());
+    await claim(claimer);
+
+    try {
+      await sweep();
+      expect.fail("Expected sweepUnclaimedTokens to throw");
+    } catch (error: any) {
+      helpers.expectIdlError(program, error, { msg: "Claim deadline not reached" });
+    }
+
+    await helpers.waitUntil(claimDeadline.toNumber());
+    try {
+      await claim(absentee);
+      expect.fail("Expected claim to throw");
+    } catch (error: any) {
+      helpers.expectIdlError(program, error, { msg: "Claim deadline passed" });
+    }
+
+    await sweep();
+    expect(Number((await getAccount(provider.connection, ownerAta)).amount)).to.equal(500);
+
+    const campaign = await program.account.idoCampaign.fetch(idoCampaignPda);
+    expect(campaign.status).to.deep.equal({ finalized: {} });
+  });
+});
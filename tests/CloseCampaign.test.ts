@@ -18,6 +18,10 @@ describe("close_campaign tests", () => {
   const program = anchor.workspace.solanaIdo as Program<SolanaIdo>;
   const provider = anchor.getProvider() as anchor.AnchorProvider;
 
+  before(async () => {
+    await helpers.ensureCampaignRegistry(program);
+  });
+
   function derive(idoCampaignOwner: PublicKey) {
     const [idoCampaignPda] = PublicKey.findProgramAddressSync(
       [Buffer.from("ido_campaign"), idoCampaignOwner.toBuffer()],
@@ -56,7 +60,8 @@ describe("close_campaign tests", () => {
         helpers.softCap,
         helpers.hardCap,
         helpers.availableTokensAfterCliffPtc,
-        new BN(20)
+        new BN(20),
+        helpers.campaignMetadata
       )
       .accounts({ owner: owner.publicKey, tokenMint: mint })
       .signers([owner])
@@ -80,6 +85,7 @@ describe("close_campaign tests", () => {
           tokensTreasury: tokensTreasuryPda,
           tokenMint: mint,
           tokenProgram: TOKEN_PROGRAM_ID,
+          registryEntry: null,
         })
         .signers([owner])
         .rpc();
@@ -129,6 +135,7 @@ describe("close_campaign tests", () => {
           tokensTreasury: tokensTreasuryPda,
           tokenMint: mint,
           tokenProgram: TOKEN_PROGRAM_ID,
+          registryEntry: null,
         })
         .signers([owner])
         .rpc();
@@ -194,13 +201,13 @@ describe("close_campaign tests", () => {
         tokensTreasury: tokensTreasuryPda,
         tokenMint: mint,
         tokenProgram: TOKEN_PROGRAM_ID,
+        registryEntry: null,
       })
       .signers([owner])
       .rpc();
 
     const idoCampaign = await program.account.idoCampaign.fetch(idoCampaignPda);
-    expect(idoCampaign.saleClosed).to.equal(true);
-    expect(idoCampaign.fundsWithdrawn).to.equal(true);
+    expect(idoCampaign.status).to.deep.equal({ cancelled: {} });
 
     const treasuryAfter = await getAccount(provider.connection, tokensTreasuryPda);
     expect(treasuryAfter.amount.toString()).to.equal("0");
@@ -227,6 +234,17 @@ describe("close_campaign tests", () => {
             [Buffer.from("user"), idoCampaignPda.toBuffer(), another.publicKey.toBuffer()],
             program.programId
           )[0],
+          stakeAccount: null,
+          stakeTiers: null,
+          lottery: null,
+          lotteryTicket: null,
+          instructionsSysvar: null,
+          joinThrottle: null,
+          allowlistEntry: null,
+          blocklistEntry: helpers.findBlocklistEntryPda(program.programId, another.publicKey),
+          gateTokenAccount: null,
+          gateListEntry: null,
+          referral: null,
           systemProgram: SystemProgram.programId,
         })
         .signers([another])
@@ -271,6 +289,7 @@ describe("close_campaign tests", () => {
           tokensTreasury: tokensTreasuryPda,
           tokenMint: mint,
           tokenProgram: TOKEN_PROGRAM_ID,
+          registryEntry: null,
         })
         .signers([owner])
         .rpc();
@@ -316,6 +335,7 @@ describe("close_campaign tests", () => {
         tokensTreasury: tokensTreasuryPda,
         tokenMint: mint,
         tokenProgram: TOKEN_PROGRAM_ID,
+        registryEntry: null,
       })
       .signers([owner])
       .rpc();
@@ -330,6 +350,7 @@ describe("close_campaign tests", () => {
           tokensTreasury: tokensTreasuryPda,
           tokenMint: mint,
           tokenProgram: TOKEN_PROGRAM_ID,
+          registryEntry: null,
         })
         .signers([owner])
         .rpc();
@@ -17,6 +17,7 @@ describe("deposit_tokens_to_sale tests", () => {
   let ownerAta: anchor.web3.PublicKey;
 
   before(async () => {
+    await helpers.ensureCampaignRegistry(program);
     await helpers.airdropSol(provider, payer.publicKey);
     ({ mint } = await helpers.createMintAndMintToOwner(
       provider,
@@ -38,7 +39,8 @@ describe("deposit_tokens_to_sale tests", () => {
         helpers.softCap,
         helpers.hardCap,
         helpers.availableTokensAfterCliffPtc,
-        helpers.availableAllocationsPerParticipant
+        helpers.availableAllocationsPerParticipant,
+        helpers.campaignMetadata
     ).accounts({
         owner: payer.publicKey,
         tokenMint: mint,
@@ -158,6 +160,6 @@ describe("deposit_tokens_to_sale tests", () => {
     const idoCampaign = await program.account.idoCampaign.fetch(idoCampaignPda);
 
     expect(tokensTreasuryAccount.amount.toString()).to.equal((helpers.hardCap.toString()).toString());
-    expect(idoCampaign.tokenSupplyDeposited).to.equal(true);
+    expect(idoCampaign.status).to.deep.equal({ funded: {} });
   });
 });
\ No newline at end of file
@@ -0,0 +1,156 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { SolanaIdo } from "../target/types/solana_ido";
+import * as helpers from "../tests/helpers";
+import { expect } from "chai";
+import BN from "bn.js";
+import { Keypair } from "@solana/web3.js";
+
+describe("dutch auction tests", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.solanaIdo as Program<SolanaIdo>;
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+
+  before(async () => {
+    await helpers.ensureCampaignRegistry(program);
+  });
+
+  const floorPriceLamports = helpers.priceLamports.div(new BN(2));
+
+  it("charges the decayed price and refunds the difference to the clearing price", async () => {
+    const owner = Keypair.generate();
+    const early = Keypair.generate();
+    const late = Keypair.generate();
+    const { mint } = await helpers.createMintAndMintToOwner(provider, owner.publicKey);
+
+    const now = Math.floor(Date.now() / 1000);
+    const startSaleTime = new BN(now + 2);
+    const endSaleTime = new BN(now + 14);
+
+    const { idoCampaignPda } = await helpers.setupCampaign({
+      program,
+      provider,
+      owner,
+      mint,
+      startSaleTime,
+      endSaleTime,
+      cliff: new BN(now + 16),
+      vestingEndTime: new BN(now + 100),
+      priceLamports: helpers.priceLamports,
+      allocation: helpers.allocation,
+      softCap: helpers.softCap,
+      hardCap: helpers.hardCap,
+      availableTokensAfterCliffPtc: helpers.availableTokensAfterCliffPtc,
+      availableAllocationsPerParticipant: helpers.availableAllocationsPerParticipant,
+      configure: async () => {
+        await program.methods
+          .configureDutchAuction(floorPriceLamports, { linear: {} }, true)
+          .accounts({ owner: owner.publicKey })
+          .signers([owner])
+          .rpc();
+      },
+    });
+
+    const { userPda: earlyUserPda } = await helpers.joinAsParticipant({
+      program,
+      provider,
+      owner,
+      participant: early,
+      idoCampaignPda,
+      startSaleTime,
+      endSaleTime,
+      allocations: new BN(5),
+    });
+
+    await helpers.waitUntil(startSaleTime.toNumber() + 6);
+
+    const { userPda: lateUserPda } = await helpers.joinAsParticipant({
+      program,
+      provider,
+      owner,
+      participant: late,
+      idoCampaignPda,
+      startSaleTime,
+      endSaleTime,
+      allocations: new BN(5),
+    });
+
+    const earlyUser = await program.account.user.fetch(earlyUserPda);
+    const lateUser = await program.account.user.fetch(lateUserPda);
+    expect(earlyUser.paidLamports.gt(lateUser.paidLamports)).to.equal(true);
+    expect(lateUser.paidLamports.gte(new BN(500).mul(floorPriceLamports))).to.equal(true);
+
+    const campaign = await program.account.idoCampaign.fetch(idoCampaignPda);
+    expect(campaign.saleMode).to.deep.equal({ dutchAuction: {} });
+    expect(campaign.clearingPriceLamports.mul(new BN(500)).toString()).to.equal(
+      lateUser.paidLamports.toString()
+    );
+
+    await helpers.waitUntil(endSaleTime.toNumber());
+
+    const expectedRefund = earlyUser.paidLamports.sub(lateUser.paidLamports);
+    const balanceBefore = await provider.connection.getBalance(early.publicKey);
+    await program.methods
+      .refundPriceDifference()
+      .accounts({
+        participant: early.publicKey,
+        idoCampaignOwner: owner.publicKey,
+      })
+      .signers([early])
+      .rpc();
+    const balanceAfter = await provider.connection.getBalance(early.publicKey);
+    expect(balanceAfter - balanceBefore).to.equal(expectedRefund.toNumber());
+
+    const settledUser = await program.account.user.fetch(earlyUserPda);
+    expect(settledUser.paidLamports.toString()).to.equal(lateUser.paidLamports.toString());
+
+    try {
+      await program.methods
+        .refundPriceDifference()
+        .accounts({
+          participant: late.publicKey,
+          idoCampaignOwner: owner.publicKey,
+        })
+        .signers([late])
+        .rpc();
+      expect.fail("Expected refundPriceDifference to throw");
+    } catch (error: any) {
+      helpers.expectIdlError(program, error, { msg: "Nothing to refund" });
+    }
+  });
+
+  it("floor price has to be below the start price", async () => {
+    const owner = Keypair.generate();
+    const { mint } = await helpers.createMintAndMintToOwner(provider, owner.publicKey);
+
+    const now = Math.floor(Date.now() / 1000);
+    await helpers.setupCampaign({
+      program,
+      provider,
+      owner,
+      mint,
+      startSaleTime: new BN(now + 10),
+      endSaleTime: new BN(now + 20),
+      cliff: new BN(now + 30),
+      vestingEndTime: new BN(now + 40),
+      priceLamports: helpers.priceLamports,
+      allocation: helpers.allocation,
+      softCap: helpers.softCap,
+      hardCap: helpers.hardCap,
+      availableTokensAfterCliffPtc: helpers.availableTokensAfterCliffPtc,
+      availableAllocationsPerParticipant: helpers.availableAllocationsPerParticipant,
+      configure: async () => {
+        try {
+          await program.methods
+            .configureDutchAuction(helpers.priceLamports, { stepped: { interval: new BN(2) } }, false)
+            .accounts({ owner: owner.publicKey })
+            .signers([owner])
+            .rpc();
+          expect.fail("Expected configureDutchAuction to throw");
+        } catch (error: any) {
+          helpers.expectIdlError(program, error, { msg: "Invalid price" });
+        }
+      },
+    });
+  });
+});
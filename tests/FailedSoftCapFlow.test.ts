@@ -47,6 +47,10 @@ describe("failed soft cap flow tests", () => {
   const program = anchor.workspace.solanaIdo as Program<SolanaIdo>;
   const provider = anchor.getProvider() as anchor.AnchorProvider;
 
+  before(async () => {
+    await helpers.ensureCampaignRegistry(program);
+  });
+
   it("permissionless close + multi-participant exact refunds + owner token withdraw", async () => {
     const owner = Keypair.generate();
     const checker = Keypair.generate();
@@ -133,12 +137,13 @@ describe("failed soft cap flow tests", () => {
         checker: checker.publicKey,
         idoCampaignOwner: owner.publicKey,
         idoCampaign: idoCampaignPda,
+        registryEntry: null,
       })
       .signers([checker])
       .rpc();
 
     const ido = await program.account.idoCampaign.fetch(idoCampaignPda);
-    expect(ido.saleClosed).to.eq(true);
+    expect(ido.status).to.deep.equal({ failed: {} });
 
     async function refundExact(params: {
       participant: Keypair;
@@ -288,6 +293,7 @@ describe("failed soft cap flow tests", () => {
           checker: checker.publicKey,
           idoCampaignOwner: owner.publicKey,
           idoCampaign: idoCampaignPda,
+          registryEntry: null,
         })
         .signers([checker])
         .rpc();
@@ -350,6 +356,7 @@ describe("failed soft cap flow tests", () => {
           checker: checker.publicKey,
           idoCampaignOwner: owner.publicKey,
           idoCampaign: idoCampaignPda,
+          registryEntry: null,
         })
         .signers([checker])
         .rpc();
@@ -430,6 +437,7 @@ describe("failed soft cap flow tests", () => {
         checker: checker.publicKey,
         idoCampaignOwner: owner.publicKey,
         idoCampaign: idoCampaignPda,
+        registryEntry: null,
       })
       .signers([checker])
       .rpc();
@@ -507,6 +515,7 @@ describe("failed soft cap flow tests", () => {
         checker: checker.publicKey,
         idoCampaignOwner: owner.publicKey,
         idoCampaign: idoCampaignPda,
+        registryEntry: null,
       })
       .signers([checker])
       .rpc();
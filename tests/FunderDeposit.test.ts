@@ -0,0 +1,120 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { SolanaIdo } from "../target/types/solana_ido";
+import * as helpers from "../tests/helpers";
+import { expect } from "chai";
+import BN from "bn.js";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { getAccount, getOrCreateAssociatedTokenAccount, mintTo } from "@solana/spl-token";
+
+describe("funder deposit tests", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.solanaIdo as Program<SolanaIdo>;
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+  const payer = (provider.wallet as anchor.Wallet).payer;
+
+  before(async () => {
+    await helpers.ensureCampaignRegistry(program);
+  });
+
+  it("funders cover the supply in parts and get it back when the sale fails", async () => {
+    const owner = Keypair.generate();
+    const foundation = Keypair.generate();
+    const { mint } = await helpers.createMintAndMintToOwner(provider, owner.publicKey);
+    await helpers.airdropSol(provider, owner.publicKey, 10);
+    await helpers.airdropSol(provider, foundation.publicKey, 1);
+
+    const now = Math.floor(Date.now() / 1000);
+    const endSaleTime = new BN(now + 8);
+    await program.methods
+      .initializeSale(
+        new BN(now + 4),
+        endSaleTime,
+        new BN(now + 10),
+        new BN(now + 100),
+        helpers.priceLamports,
+        helpers.allocation,
+        helpers.softCap,
+        helpers.hardCap,
+        helpers.availableTokensAfterCliffPtc,
+        helpers.availableAllocationsPerParticipant,
+        helpers.campaignMetadata
+      )
+      .accounts({ owner: owner.publicKey, tokenMint: mint })
+      .signers([owner])
+      .rpc();
+
+    const [ownerAta, foundationAta] = await Promise.all(
+      [owner.publicKey, foundation.publicKey].map((wallet) =>
+        getOrCreateAssociatedTokenAccount(provider.connection, payer, mint, wallet).then((ata) => ata.address)
+      )
+    );
+    await mintTo(provider.connection, payer, mint, ownerAta, owner, 300);
+    await mintTo(provider.connection, payer, mint, foundationAta, owner, 1000);
+
+    const fund = (amount: number) =>
+      program.methods
+        .fundSaleSupply(new BN(amount))
+        .accounts({
+          funder: foundation.publicKey,
+          funderTokenAccount: foundationAta,
+          idoCampaignOwner: owner.publicKey,
+          tokenMint: mint,
+        })
+        .signers([foundation])
+        .rpc();
+
+    await fund(400);
+    try {
+      await fund(700);
+      expect.fail("Expected fundSaleSupply to throw");
+    } catch (error: any) {
+      helpers.expectIdlError(program, error, { msg: "Invalid balance of tokens to deposit" });
+    }
+    await fund(300);
+
+    const [idoCampaignPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("ido_campaign"), owner.publicKey.toBuffer()],
+      program.programId
+    );
+    let campaign = await program.account.idoCampaign.fetch(idoCampaignPda);
+    expect(campaign.status).to.deep.equal({ created: {} });
+    expect(campaign.totalDeposited.toNumber()).to.equal(700);
+
+    // The owner only tops up what the funder did not cover.
+    await program.methods
+      .depositTokensToSale()
+      .accounts({ owner: owner.publicKey, tokenMint: mint, ownerTokenAccount: ownerAta })
+      .signers([owner])
+      .rpc();
+    campaign = await program.account.idoCampaign.fetch(idoCampaignPda);
+    expect(campaign.status).to.deep.equal({ funded: {} });
+    expect(Number((await getAccount(provider.connection, ownerAta)).amount)).to.equal(0);
+
+    await helpers.waitUntil(endSaleTime.toNumber() + 1);
+    await program.methods
+      .closeCampaignIfSoftCapNotReached()
+      .accounts({ checker: owner.publicKey, idoCampaignOwner: owner.publicKey })
+      .signers([owner])
+      .rpc();
+
+    await program.methods
+      .returnFunderDeposit()
+      .accounts({
+        funder: foundation.publicKey,
+        funderTokenAccount: foundationAta,
+        idoCampaignOwner: owner.publicKey,
+        tokenMint: mint,
+      })
+      .signers([foundation])
+      .rpc();
+    expect(Number((await getAccount(provider.connection, foundationAta)).amount)).to.equal(1000);
+
+    await program.methods
+      .withdrawTokensToOwnerIfSoftCapNotReached()
+      .accounts({ owner: owner.publicKey, ownerTokenAccount: ownerAta, tokenMint: mint })
+      .signers([owner])
+      .rpc();
+    expect(Number((await getAccount(provider.connection, ownerAta)).amount)).to.equal(300);
+  });
+});
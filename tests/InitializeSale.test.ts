@@ -14,6 +14,7 @@ describe("initialize_sale tests", () => {
   let mint: anchor.web3.PublicKey;
 
   before(async () => {
+    await helpers.ensureCampaignRegistry(program);
     await helpers.airdropSol(provider, payer.publicKey);
     ({ mint } = await helpers.createMintAndMintToOwner(
       provider,
@@ -40,7 +41,8 @@ describe("initialize_sale tests", () => {
           helpers.softCap,
           helpers.hardCap,
           helpers.availableTokensAfterCliffPtc,
-          helpers.availableAllocationsPerParticipant
+          helpers.availableAllocationsPerParticipant,
+          helpers.campaignMetadata
         )
         .accounts({
           owner: payer.publicKey,
@@ -72,7 +74,8 @@ describe("initialize_sale tests", () => {
           helpers.softCap,
           helpers.hardCap,
           helpers.availableTokensAfterCliffPtc,
-          helpers.availableAllocationsPerParticipant
+          helpers.availableAllocationsPerParticipant,
+          helpers.campaignMetadata
         )
         .accounts({
           owner: payer.publicKey,
@@ -104,7 +107,8 @@ describe("initialize_sale tests", () => {
           helpers.softCap,
           helpers.hardCap,
           helpers.availableTokensAfterCliffPtc,
-          helpers.availableAllocationsPerParticipant
+          helpers.availableAllocationsPerParticipant,
+          helpers.campaignMetadata
         )
         .accounts({
           owner: payer.publicKey,
@@ -136,7 +140,8 @@ describe("initialize_sale tests", () => {
           helpers.softCap,
           helpers.hardCap,
           helpers.availableTokensAfterCliffPtc,
-          helpers.availableAllocationsPerParticipant
+          helpers.availableAllocationsPerParticipant,
+          helpers.campaignMetadata
         )
         .accounts({
           owner: payer.publicKey,
@@ -168,7 +173,8 @@ describe("initialize_sale tests", () => {
           helpers.softCap,
           helpers.hardCap,
           helpers.availableTokensAfterCliffPtc,
-          helpers.availableAllocationsPerParticipant
+          helpers.availableAllocationsPerParticipant,
+          helpers.campaignMetadata
         )
         .accounts({
           owner: payer.publicKey,
@@ -200,7 +206,8 @@ describe("initialize_sale tests", () => {
           helpers.softCap,
           helpers.hardCap,
           helpers.availableTokensAfterCliffPtc,
-          helpers.availableAllocationsPerParticipant
+          helpers.availableAllocationsPerParticipant,
+          helpers.campaignMetadata
         )
         .accounts({
           owner: payer.publicKey,
@@ -232,7 +239,8 @@ describe("initialize_sale tests", () => {
           helpers.softCap,
           helpers.hardCap,
           helpers.availableTokensAfterCliffPtc,
-          helpers.availableAllocationsPerParticipant
+          helpers.availableAllocationsPerParticipant,
+          helpers.campaignMetadata
         )
         .accounts({
           owner: payer.publicKey,
@@ -264,7 +272,8 @@ describe("initialize_sale tests", () => {
           helpers.softCap,
           helpers.hardCap,
           0,
-          helpers.availableAllocationsPerParticipant
+          helpers.availableAllocationsPerParticipant,
+          helpers.campaignMetadata
         )
         .accounts({
           owner: payer.publicKey,
@@ -296,7 +305,8 @@ describe("initialize_sale tests", () => {
           new BN(0),
           helpers.hardCap,
           helpers.availableTokensAfterCliffPtc,
-          helpers.availableAllocationsPerParticipant
+          helpers.availableAllocationsPerParticipant,
+          helpers.campaignMetadata
         )
         .accounts({
           owner: payer.publicKey,
@@ -328,7 +338,8 @@ describe("initialize_sale tests", () => {
           helpers.softCap,
           new BN(0),
           helpers.availableTokensAfterCliffPtc,
-          helpers.availableAllocationsPerParticipant
+          helpers.availableAllocationsPerParticipant,
+          helpers.campaignMetadata
         )
         .accounts({
           owner: payer.publicKey,
@@ -360,7 +371,8 @@ describe("initialize_sale tests", () => {
           helpers.softCap,
           helpers.softCap,
           helpers.availableTokensAfterCliffPtc,
-          helpers.availableAllocationsPerParticipant
+          helpers.availableAllocationsPerParticipant,
+          helpers.campaignMetadata
         )
         .accounts({
           owner: payer.publicKey,
@@ -392,7 +404,8 @@ describe("initialize_sale tests", () => {
           helpers.softCap,
           helpers.hardCap,
           helpers.availableTokensAfterCliffPtc,
-          new BN(0)
+          new BN(0),
+          helpers.campaignMetadata
         )
         .accounts({
           owner: payer.publicKey,
@@ -428,7 +441,8 @@ describe("initialize_sale tests", () => {
         helpers.softCap,
         helpers.hardCap,
         helpers.availableTokensAfterCliffPtc,
-        helpers.availableAllocationsPerParticipant
+        helpers.availableAllocationsPerParticipant,
+        helpers.campaignMetadata
     ).accounts({
         owner: newPayer.publicKey,
         tokenMint: newMint,
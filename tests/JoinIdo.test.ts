@@ -21,6 +21,7 @@ describe("join_ido tests", () => {
   let solTreasuryPda: PublicKey;
 
   before(async () => {
+    await helpers.ensureCampaignRegistry(program);
     await helpers.airdropSol(provider, owner.publicKey, 10);
     await helpers.airdropSol(provider, participant.publicKey, 10);
 
@@ -44,7 +45,8 @@ describe("join_ido tests", () => {
       helpers.softCap,
       helpers.hardCap,
       helpers.availableTokensAfterCliffPtc,
-      helpers.availableAllocationsPerParticipant
+      helpers.availableAllocationsPerParticipant,
+      helpers.campaignMetadata
     ).accounts({
       owner: owner.publicKey,
       tokenMint: mint,
@@ -111,7 +113,8 @@ describe("join_ido tests", () => {
       helpers.softCap,
       helpers.hardCap,
       helpers.availableTokensAfterCliffPtc,
-      helpers.availableAllocationsPerParticipant
+      helpers.availableAllocationsPerParticipant,
+      helpers.campaignMetadata
     ).accounts({
       owner: newOwner.publicKey,
       tokenMint: newMint,
@@ -187,7 +190,8 @@ describe("join_ido tests", () => {
       helpers.softCap,
       helpers.hardCap,
       helpers.availableTokensAfterCliffPtc,
-      helpers.availableAllocationsPerParticipant
+      helpers.availableAllocationsPerParticipant,
+      helpers.campaignMetadata
     ).accounts({
       owner: newOwner.publicKey,
       tokenMint: newMint,
@@ -258,7 +262,8 @@ describe("join_ido tests", () => {
       helpers.softCap,
       helpers.hardCap,
       helpers.availableTokensAfterCliffPtc,
-      helpers.availableAllocationsPerParticipant
+      helpers.availableAllocationsPerParticipant,
+      helpers.campaignMetadata
     ).accounts({
       owner: newOwner.publicKey,
       tokenMint: newMint,
@@ -336,7 +341,8 @@ describe("join_ido tests", () => {
       helpers.softCap,
       smallHardCap,
       helpers.availableTokensAfterCliffPtc,
-      helpers.availableAllocationsPerParticipant
+      helpers.availableAllocationsPerParticipant,
+      helpers.campaignMetadata
     ).accounts({
       owner: newOwner.publicKey,
       tokenMint: newMint,
@@ -0,0 +1,102 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { SolanaIdo } from "../target/types/solana_ido";
+import * as helpers from "../tests/helpers";
+import { expect } from "chai";
+import BN from "bn.js";
+import { Keypair, SYSVAR_INSTRUCTIONS_PUBKEY, TransactionInstruction } from "@solana/web3.js";
+
+describe("kyc tests", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.solanaIdo as Program<SolanaIdo>;
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+
+  before(async () => {
+    await helpers.ensureCampaignRegistry(program);
+  });
+
+  it("join requires a ticket signed by the campaign kyc signer", async () => {
+    const owner = Keypair.generate();
+    const kycSigner = Keypair.generate();
+    const participant = Keypair.generate();
+    const { mint } = await helpers.createMintAndMintToOwner(provider, owner.publicKey);
+
+    const now = Math.floor(Date.now() / 1000);
+    const startSaleTime = new BN(now + 4);
+    const endSaleTime = new BN(now + 30);
+
+    const { idoCampaignPda } = await helpers.setupCampaign({
+      program,
+      provider,
+      owner,
+      mint,
+      startSaleTime,
+      endSaleTime,
+      cliff: new BN(now + 32),
+      vestingEndTime: new BN(now + 100),
+      priceLamports: helpers.priceLamports,
+      allocation: helpers.allocation,
+      softCap: helpers.softCap,
+      hardCap: helpers.hardCap,
+      availableTokensAfterCliffPtc: helpers.availableTokensAfterCliffPtc,
+      availableAllocationsPerParticipant: helpers.availableAllocationsPerParticipant,
+      configure: async () => {
+        await program.methods
+          .configureKyc(kycSigner.publicKey)
+          .accounts({ owner: owner.publicKey })
+          .signers([owner])
+          .rpc();
+      },
+    });
+
+    await helpers.airdropSol(provider, participant.publicKey, 10);
+    await helpers.waitUntil(startSaleTime.toNumber());
+
+    const join = (allocations: number, preInstructions: TransactionInstruction[]) =>
+      program.methods
+        .joinIdo(new BN(allocations))
+        .accounts({
+          participant: participant.publicKey,
+          idoCampaignOwner: owner.publicKey,
+          stakeAccount: null,
+          stakeTiers: null,
+          lottery: null,
+          lotteryTicket: null,
+          instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+        })
+        .preInstructions(preInstructions)
+        .signers([participant])
+        .rpc();
+    const ticket = (signer: Keypair, expiresAt: BN) =>
+      helpers.kycTicketInstruction(signer, idoCampaignPda, participant.publicKey, new BN(2), expiresAt);
+    const expiresAt = endSaleTime;
+
+    const cases: { preInstructions: TransactionInstruction[]; msg: string }[] = [
+      { preInstructions: [], msg: "KYC ticket required" },
+      { preInstructions: [ticket(Keypair.generate(), expiresAt)], msg: "Invalid KYC ticket" },
+      { preInstructions: [ticket(kycSigner, new BN(now))], msg: "KYC ticket expired" },
+    ];
+    for (const { preInstructions, msg } of cases) {
+      try {
+        await join(1, preInstructions);
+        expect.fail("Expected joinIdo to throw");
+      } catch (error: any) {
+        helpers.expectIdlError(program, error, { msg });
+      }
+    }
+
+    // The ticket caps the allocations below the campaign wide limit.
+    try {
+      await join(3, [ticket(kycSigner, expiresAt)]);
+      expect.fail("Expected joinIdo to throw");
+    } catch (error: any) {
+      helpers.expectIdlError(program, error, { msg: "Invalid number of allocations" });
+    }
+
+    await join(2, [ticket(kycSigner, expiresAt)]);
+
+    const campaign = await program.account.idoCampaign.fetch(idoCampaignPda);
+    expect(campaign.kycSigner.toBase58()).to.equal(kycSigner.publicKey.toBase58());
+    expect(campaign.totalSold.toString()).to.equal(helpers.allocation.muln(2).toString());
+  });
+});
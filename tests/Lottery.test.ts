@@ -0,0 +1,243 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { SolanaIdo } from "../target/types/solana_ido";
+import * as helpers from "../tests/helpers";
+import { expect } from "chai";
+import BN from "bn.js";
+import { Keypair } from "@solana/web3.js";
+
+describe("lottery tests", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.solanaIdo as Program<SolanaIdo>;
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+
+  before(async () => {
+    await helpers.ensureCampaignRegistry(program);
+  });
+
+  const registrationDeposit = new BN(10_000_000);
+
+  it("only drawn winners can join during the guaranteed window", async () => {
+    const owner = Keypair.generate();
+    const registrants = [Keypair.generate(), Keypair.generate(), Keypair.generate()];
+    const { mint } = await helpers.createMintAndMintToOwner(provider, owner.publicKey);
+
+    const now = Math.floor(Date.now() / 1000);
+    const startSaleTime = new BN(now + 12);
+    const guaranteedWindowEnd = new BN(now + 18);
+    const endSaleTime = new BN(now + 24);
+
+    const { idoCampaignPda } = await helpers.setupCampaign({
+      program,
+      provider,
+      owner,
+      mint,
+      startSaleTime,
+      endSaleTime,
+      cliff: new BN(now + 26),
+      vestingEndTime: new BN(now + 100),
+      priceLamports: helpers.priceLamports,
+      allocation: helpers.allocation,
+      softCap: helpers.softCap,
+      hardCap: helpers.hardCap,
+      availableTokensAfterCliffPtc: helpers.availableTokensAfterCliffPtc,
+      availableAllocationsPerParticipant: helpers.availableAllocationsPerParticipant,
+      configure: async () => {
+        await program.methods
+          .configureLottery(registrationDeposit, new BN(1), guaranteedWindowEnd)
+          .accounts({ owner: owner.publicKey })
+          .signers([owner])
+          .rpc();
+      },
+    });
+
+    for (const registrant of registrants) {
+      await helpers.airdropSol(provider, registrant.publicKey, 10);
+      await program.methods
+        .registerForLottery()
+        .accounts({
+          participant: registrant.publicKey,
+          idoCampaignOwner: owner.publicKey,
+        })
+        .signers([registrant])
+        .rpc();
+    }
+
+    const lotteryPda = helpers.findLotteryPda(program.programId, idoCampaignPda);
+    const join = (participant: Keypair) =>
+      program.methods
+        .joinIdo(new BN(1))
+        .accounts({
+          participant: participant.publicKey,
+          idoCampaignOwner: owner.publicKey,
+          stakeAccount: null,
+          stakeTiers: null,
+          lottery: lotteryPda,
+          lotteryTicket: helpers.findLotteryTicketPda(
+            program.programId,
+            idoCampaignPda,
+            participant.publicKey
+          ),
+        })
+        .signers([participant])
+        .rpc();
+
+    try {
+      await program.methods
+        .scheduleLotteryDraw()
+        .accounts({ idoCampaignOwner: owner.publicKey })
+        .rpc();
+      expect.fail("Expected scheduleLotteryDraw to throw");
+    } catch (error: any) {
+      helpers.expectIdlError(program, error, { msg: "Now is not in sale period" });
+    }
+
+    try {
+      await program.methods
+        .drawLottery()
+        .accounts({ idoCampaignOwner: owner.publicKey })
+        .rpc();
+      expect.fail("Expected drawLottery to throw");
+    } catch (error: any) {
+      helpers.expectIdlError(program, error, { msg: "Lottery draw not scheduled" });
+    }
+
+    await helpers.waitUntil(startSaleTime.toNumber());
+
+    try {
+      await join(registrants[0]);
+      expect.fail("Expected joinIdo to throw");
+    } catch (error: any) {
+      helpers.expectIdlError(program, error, { msg: "Lottery not drawn" });
+    }
+
+    await program.methods
+      .scheduleLotteryDraw()
+      .accounts({ idoCampaignOwner: owner.publicKey })
+      .rpc();
+
+    try {
+      await program.methods
+        .drawLottery()
+        .accounts({ idoCampaignOwner: owner.publicKey })
+        .rpc();
+      expect.fail("Expected drawLottery to throw");
+    } catch (error: any) {
+      helpers.expectIdlError(program, error, { msg: "Lottery draw slot not reached" });
+    }
+
+    const { drawSlot } = await program.account.lottery.fetch(lotteryPda);
+    await helpers.waitForSlot(provider.connection, drawSlot.toNumber());
+
+    await program.methods
+      .drawLottery()
+      .accounts({ idoCampaignOwner: owner.publicKey })
+      .rpc();
+
+    const lottery = await program.account.lottery.fetch(lotteryPda);
+    expect(lottery.drawn).to.equal(true);
+    expect(lottery.registrantCount.toNumber()).to.equal(registrants.length);
+
+    const tickets = await Promise.all(
+      registrants.map((registrant) =>
+        program.account.lotteryTicket.fetch(
+          helpers.findLotteryTicketPda(program.programId, idoCampaignPda, registrant.publicKey)
+        )
+      )
+    );
+    const isWinner = (index: number) => (lottery.winners[index >> 3] & (1 << (index & 7))) !== 0;
+    const winners = registrants.filter((_, i) => isWinner(tickets[i].index.toNumber()));
+    const losers = registrants.filter((_, i) => !isWinner(tickets[i].index.toNumber()));
+    expect(winners.length).to.equal(1);
+
+    await join(winners[0]);
+
+    try {
+      await join(losers[0]);
+      expect.fail("Expected joinIdo to throw");
+    } catch (error: any) {
+      helpers.expectIdlError(program, error, { msg: "Not a lottery winner" });
+    }
+
+    try {
+      await program.methods
+        .closeLotteryTicket()
+        .accounts({
+          participant: winners[0].publicKey,
+          idoCampaignOwner: owner.publicKey,
+        })
+        .signers([winners[0]])
+        .rpc();
+      expect.fail("Expected closeLotteryTicket to throw");
+    } catch (error: any) {
+      helpers.expectIdlError(program, error, { msg: "Lottery ticket still needed" });
+    }
+
+    const balanceBefore = await provider.connection.getBalance(losers[0].publicKey);
+    await program.methods
+      .closeLotteryTicket()
+      .accounts({
+        participant: losers[0].publicKey,
+        idoCampaignOwner: owner.publicKey,
+      })
+      .signers([losers[0]])
+      .rpc();
+    const balanceAfter = await provider.connection.getBalance(losers[0].publicKey);
+    expect(balanceAfter - balanceBefore).to.be.greaterThan(registrationDeposit.toNumber());
+
+    await helpers.waitUntil(guaranteedWindowEnd.toNumber());
+
+    await join(losers[1]);
+  });
+
+  it("registration closes once the sale starts", async () => {
+    const owner = Keypair.generate();
+    const late = Keypair.generate();
+    const { mint } = await helpers.createMintAndMintToOwner(provider, owner.publicKey);
+
+    const now = Math.floor(Date.now() / 1000);
+    const startSaleTime = new BN(now + 3);
+    const endSaleTime = new BN(now + 20);
+
+    await helpers.setupCampaign({
+      program,
+      provider,
+      owner,
+      mint,
+      startSaleTime,
+      endSaleTime,
+      cliff: new BN(now + 22),
+      vestingEndTime: new BN(now + 100),
+      priceLamports: helpers.priceLamports,
+      allocation: helpers.allocation,
+      softCap: helpers.softCap,
+      hardCap: helpers.hardCap,
+      availableTokensAfterCliffPtc: helpers.availableTokensAfterCliffPtc,
+      availableAllocationsPerParticipant: helpers.availableAllocationsPerParticipant,
+      configure: async () => {
+        await program.methods
+          .configureLottery(new BN(0), new BN(10), endSaleTime)
+          .accounts({ owner: owner.publicKey })
+          .signers([owner])
+          .rpc();
+      },
+    });
+
+    await helpers.airdropSol(provider, late.publicKey, 1);
+    await helpers.waitUntil(startSaleTime.toNumber());
+
+    try {
+      await program.methods
+        .registerForLottery()
+        .accounts({
+          participant: late.publicKey,
+          idoCampaignOwner: owner.publicKey,
+        })
+        .signers([late])
+        .rpc();
+      expect.fail("Expected registerForLottery to throw");
+    } catch (error: any) {
+      helpers.expectIdlError(program, error, { msg: "Lottery registration closed" });
+    }
+  });
+});
@@ -0,0 +1,132 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { SolanaIdo } from "../target/types/solana_ido";
+import * as helpers from "../tests/helpers";
+import { expect } from "chai";
+import BN from "bn.js";
+import { Keypair } from "@solana/web3.js";
+
+describe("account migration tests", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.solanaIdo as Program<SolanaIdo>;
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+
+  before(async () => {
+    await helpers.ensureCampaignRegistry(program);
+  });
+
+  it("new campaign and user accounts are created at the current version and size", async () => {
+    const owner = Keypair.generate();
+    const participant = Keypair.generate();
+    const { mint } = await helpers.createMintAndMintToOwner(provider, owner.publicKey);
+
+    const now = Math.floor(Date.now() / 1000);
+    const startSaleTime = new BN(now + 1);
+    const endSaleTime = new BN(now + 30);
+
+    const { idoCampaignPda } = await helpers.setupCampaign({
+      program,
+      provider,
+      owner,
+      mint,
+      startSaleTime,
+      endSaleTime,
+      cliff: new BN(now + 40),
+      vestingEndTime: new BN(now + 60),
+      priceLamports: helpers.priceLamports,
+      allocation: helpers.allocation,
+      softCap: helpers.softCap,
+      hardCap: helpers.hardCap,
+      availableTokensAfterCliffPtc: helpers.availableTokensAfterCliffPtc,
+      availableAllocationsPerParticipant: helpers.availableAllocationsPerParticipant,
+    });
+
+    const { userPda } = await helpers.joinAsParticipant({
+      program,
+      provider,
+      owner,
+      participant,
+      idoCampaignPda,
+      startSaleTime,
+      endSaleTime,
+      allocations: new BN(1),
+    });
+
+    const idoCampaign = await program.account.idoCampaign.fetch(idoCampaignPda);
+    const user = await program.account.user.fetch(userPda);
+    expect(idoCampaign.version).to.equal(1);
+    expect(user.version).to.equal(1);
+
+    const campaignInfo = await provider.connection.getAccountInfo(idoCampaignPda);
+    const userInfo = await provider.connection.getAccountInfo(userPda);
+    expect(campaignInfo!.data.length).to.equal(program.account.idoCampaign.size);
+    expect(userInfo!.data.length).to.equal(program.account.user.size);
+  });
+
+  it("migrating an up to date campaign or user fails", async () => {
+    const owner = Keypair.generate();
+    const participant = Keypair.generate();
+    const { mint } = await helpers.createMintAndMintToOwner(provider, owner.publicKey);
+
+    const now = Math.floor(Date.now() / 1000);
+    const startSaleTime = new BN(now + 1);
+    const endSaleTime = new BN(now + 30);
+
+    const { idoCampaignPda } = await helpers.setupCampaign({
+      program,
+      provider,
+      owner,
+      mint,
+      startSaleTime,
+      endSaleTime,
+      cliff: new BN(now + 40),
+      vestingEndTime: new BN(now + 60),
+      priceLamports: helpers.priceLamports,
+      allocation: helpers.allocation,
+      softCap: helpers.softCap,
+      hardCap: helpers.hardCap,
+      availableTokensAfterCliffPtc: helpers.availableTokensAfterCliffPtc,
+      availableAllocationsPerParticipant: helpers.availableAllocationsPerParticipant,
+    });
+
+    await helpers.joinAsParticipant({
+      program,
+      provider,
+      owner,
+      participant,
+      idoCampaignPda,
+      startSaleTime,
+      endSaleTime,
+      allocations: new BN(1),
+    });
+
+    try {
+      await program.methods
+        .migrateCampaign()
+        .accounts({
+          payer: owner.publicKey,
+          idoCampaignOwner: owner.publicKey,
+        })
+        .signers([owner])
+        .rpc();
+      expect.fail("Expected migrateCampaign to throw");
+    } catch (error: any) {
+      helpers.expectIdlError(program, error, { msg: "Account already migrated" });
+    }
+
+    try {
+      await program.methods
+        .migrateUser()
+        .accounts({
+          payer: participant.publicKey,
+          participant: participant.publicKey,
+          idoCampaignOwner: owner.publicKey,
+        })
+        .signers([participant])
+        .rpc();
+      expect.fail("Expected migrateUser to throw");
+    } catch (error: any) {
+      helpers.expectIdlError(program, error, { msg: "Account already migrated" });
+    }
+  });
+});
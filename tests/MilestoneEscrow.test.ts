@@ -0,0 +1,146 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { SolanaIdo } from "../target/types/solana_ido";
+import * as helpers from "../tests/helpers";
+import { expect } from "chai";
+import BN from "bn.js";
+import { Keypair, PublicKey } from "@solana/web3.js";
+
+const LAUNCHPAD_OWNER = new PublicKey("BRhY2VPGiDvEnQphYjgvbCXRkGTLBY4bXzeYjDuKYkv6");
+
+describe("milestone escrow tests", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.solanaIdo as Program<SolanaIdo>;
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+
+  before(async () => {
+    await helpers.ensureCampaignRegistry(program);
+    await helpers.airdropSol(provider, LAUNCHPAD_OWNER, 0.01);
+    await helpers.ensureLaunchpadConfig(program, provider);
+  });
+
+  it("releases unlocked tranches and refunds the rest once marked failed", async () => {
+    const owner = Keypair.generate();
+    const participants = [Keypair.generate(), Keypair.generate()];
+    const { mint } = await helpers.createMintAndMintToOwner(provider, owner.publicKey);
+
+    const now = Math.floor(Date.now() / 1000);
+    const startSaleTime = new BN(now + 4);
+    const endSaleTime = new BN(now + 10);
+    const unlockTime = new BN(now + 14);
+
+    const { idoCampaignPda, ownerAta } = await helpers.setupCampaign({
+      program,
+      provider,
+      owner,
+      mint,
+      startSaleTime,
+      endSaleTime,
+      cliff: new BN(now + 12),
+      vestingEndTime: new BN(now + 100),
+      priceLamports: helpers.priceLamports,
+      allocation: helpers.allocation,
+      softCap: helpers.softCap,
+      hardCap: helpers.hardCap,
+      availableTokensAfterCliffPtc: helpers.availableTokensAfterCliffPtc,
+      availableAllocationsPerParticipant: helpers.availableAllocationsPerParticipant,
+      configure: async () => {
+        await program.methods
+          .configureMilestoneEscrow([
+            { unlock: { time: { unlockTime } }, releaseBps: 4000 },
+            { unlock: { milestone: {} }, releaseBps: 6000 },
+          ])
+          .accounts({ owner: owner.publicKey })
+          .signers([owner])
+          .rpc();
+      },
+    });
+
+    for (const participant of participants) {
+      await helpers.joinAsParticipant({
+        program,
+        provider,
+        owner,
+        participant,
+        idoCampaignPda,
+        startSaleTime,
+        endSaleTime,
+        allocations: new BN(5),
+      });
+    }
+
+    await helpers.waitUntil(endSaleTime.toNumber());
+
+    const milestoneEscrow = helpers.findMilestoneEscrowPda(program.programId, idoCampaignPda);
+    const ownerBalanceBefore = await provider.connection.getBalance(owner.publicKey);
+    await program.methods
+      .withdrawFunds()
+      .accounts({
+        launchpadOwner: LAUNCHPAD_OWNER,
+        owner: owner.publicKey,
+        ownerTokenAccount: ownerAta,
+        tokenMint: mint,
+        milestoneEscrow,
+      })
+      .signers([owner])
+      .rpc();
+    expect(await provider.connection.getBalance(owner.publicKey)).to.be.lessThan(ownerBalanceBefore);
+
+    const raised = new BN(1000).mul(helpers.priceLamports);
+    const escrowed = raised.sub(raised.divn(100).muln(5));
+    let escrow = await program.account.milestoneEscrow.fetch(milestoneEscrow);
+    expect(escrow.escrowedLamports.toString()).to.equal(escrowed.toString());
+
+    const release = (trancheIndex: number) =>
+      program.methods
+        .releaseTranche(trancheIndex)
+        .accounts({ owner: owner.publicKey })
+        .signers([owner])
+        .rpc();
+
+    for (const trancheIndex of [0, 1]) {
+      try {
+        await release(trancheIndex);
+        expect.fail("Expected releaseTranche to throw");
+      } catch (error: any) {
+        helpers.expectIdlError(program, error, { msg: "Tranche is locked" });
+      }
+    }
+
+    await helpers.waitUntil(unlockTime.toNumber());
+    await release(0);
+
+    await program.methods
+      .markEscrowFailed()
+      .accounts({ idoCampaignOwner: owner.publicKey })
+      .rpc();
+
+    escrow = await program.account.milestoneEscrow.fetch(milestoneEscrow);
+    const firstTranche = escrowed.muln(4000).divn(10_000);
+    expect(escrow.releasedLamports.toString()).to.equal(firstTranche.toString());
+    expect(escrow.refundableLamports.toString()).to.equal(escrowed.sub(firstTranche).toString());
+    expect(escrow.tranches[1].state).to.deep.equal({ refunded: {} });
+
+    const refund = (participant: Keypair) =>
+      program.methods
+        .refundEscrow()
+        .accounts({
+          participant: participant.publicKey,
+          idoCampaignOwner: owner.publicKey,
+        })
+        .signers([participant])
+        .rpc();
+
+    const balanceBefore = await provider.connection.getBalance(participants[0].publicKey);
+    await refund(participants[0]);
+    const balanceAfter = await provider.connection.getBalance(participants[0].publicKey);
+    expect(balanceAfter - balanceBefore).to.equal(escrow.refundableLamports.divn(2).toNumber());
+
+    try {
+      await refund(participants[0]);
+      expect.fail("Expected refundEscrow to throw");
+    } catch (error: any) {
+      helpers.expectIdlError(program, error, { msg: "Nothing to refund" });
+    }
+  });
+});
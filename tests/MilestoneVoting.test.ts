@@ -0,0 +1,168 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { SolanaIdo } from "../target/types/solana_ido";
+import * as helpers from "../tests/helpers";
+import { expect } from "chai";
+import BN from "bn.js";
+import { Keypair, PublicKey } from "@solana/web3.js";
+
+const LAUNCHPAD_OWNER = new PublicKey("BRhY2VPGiDvEnQphYjgvbCXRkGTLBY4bXzeYjDuKYkv6");
+
+describe("milestone voting tests", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.solanaIdo as Program<SolanaIdo>;
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+
+  before(async () => {
+    await helpers.ensureCampaignRegistry(program);
+    await helpers.airdropSol(provider, LAUNCHPAD_OWNER, 0.01);
+  });
+
+  it("releases approved tranches and refunds rejected ones", async () => {
+    const owner = Keypair.generate();
+    const [backer, critic] = [Keypair.generate(), Keypair.generate()];
+    const { mint } = await helpers.createMintAndMintToOwner(provider, owner.publicKey);
+
+    const now = Math.floor(Date.now() / 1000);
+    const startSaleTime = new BN(now + 4);
+    const endSaleTime = new BN(now + 10);
+
+    const { idoCampaignPda, ownerAta } = await helpers.setupCampaign({
+      program,
+      provider,
+      owner,
+      mint,
+      startSaleTime,
+      endSaleTime,
+      cliff: new BN(now + 12),
+      vestingEndTime: new BN(now + 100),
+      priceLamports: helpers.priceLamports,
+      allocation: helpers.allocation,
+      softCap: helpers.softCap,
+      hardCap: helpers.hardCap,
+      availableTokensAfterCliffPtc: helpers.availableTokensAfterCliffPtc,
+      availableAllocationsPerParticipant: helpers.availableAllocationsPerParticipant,
+      configure: async () => {
+        await program.methods
+          .configureMilestoneEscrow([
+            { unlock: { milestone: {} }, releaseBps: 5000 },
+            { unlock: { milestone: {} }, releaseBps: 5000 },
+          ])
+          .accounts({ owner: owner.publicKey })
+          .signers([owner])
+          .rpc();
+        await program.methods
+          .configureMilestoneVoting(5000, 7000)
+          .accounts({ owner: owner.publicKey })
+          .signers([owner])
+          .rpc();
+      },
+    });
+
+    // 300 and 200 tokens, the backer alone reaches quorum but falls short of 70% against the critic.
+    for (const [participant, allocations] of [
+      [backer, 3],
+      [critic, 2],
+    ] as [Keypair, number][]) {
+      await helpers.joinAsParticipant({
+        program,
+        provider,
+        owner,
+        participant,
+        idoCampaignPda,
+        startSaleTime,
+        endSaleTime,
+        allocations: new BN(allocations),
+      });
+    }
+
+    await helpers.waitUntil(endSaleTime.toNumber());
+
+    const milestoneEscrow = helpers.findMilestoneEscrowPda(program.programId, idoCampaignPda);
+    await program.methods
+      .withdrawFunds()
+      .accounts({
+        launchpadOwner: LAUNCHPAD_OWNER,
+        owner: owner.publicKey,
+        ownerTokenAccount: ownerAta,
+        tokenMint: mint,
+        milestoneEscrow,
+      })
+      .signers([owner])
+      .rpc();
+
+    const propose = async (trancheIndex: number) => {
+      const votingEnd = new BN(Math.floor(Date.now() / 1000) + 5);
+      await program.methods
+        .createMilestoneProposal(trancheIndex, votingEnd)
+        .accounts({ owner: owner.publicKey })
+        .signers([owner])
+        .rpc();
+      return {
+        proposal: helpers.findMilestoneProposalPda(program.programId, idoCampaignPda, trancheIndex),
+        votingEnd,
+      };
+    };
+    const vote = (participant: Keypair, milestoneProposal: PublicKey, approve: boolean) =>
+      program.methods
+        .voteOnMilestone(approve)
+        .accounts({
+          participant: participant.publicKey,
+          idoCampaignOwner: owner.publicKey,
+          milestoneProposal,
+        })
+        .signers([participant])
+        .rpc();
+    const execute = (milestoneProposal: PublicKey) =>
+      program.methods
+        .executeMilestoneProposal()
+        .accounts({ idoCampaignOwner: owner.publicKey, milestoneProposal })
+        .rpc();
+
+    const first = await propose(0);
+    await vote(backer, first.proposal, true);
+    try {
+      await vote(backer, first.proposal, true);
+      expect.fail("Expected voteOnMilestone to throw");
+    } catch (error: any) {
+      helpers.expectIdlError(program, error, { msg: "already in use" });
+    }
+
+    try {
+      await execute(first.proposal);
+      expect.fail("Expected executeMilestoneProposal to throw");
+    } catch (error: any) {
+      helpers.expectIdlError(program, error, { msg: "Voting has not ended" });
+    }
+
+    await helpers.waitUntil(first.votingEnd.toNumber());
+    const ownerBalanceBefore = await provider.connection.getBalance(owner.publicKey);
+    await execute(first.proposal);
+    const ownerBalanceAfter = await provider.connection.getBalance(owner.publicKey);
+
+    let escrow = await program.account.milestoneEscrow.fetch(milestoneEscrow);
+    const tranche = escrow.escrowedLamports.divn(2);
+    expect(ownerBalanceAfter - ownerBalanceBefore).to.equal(tranche.toNumber());
+    expect(escrow.tranches[0].state).to.deep.equal({ released: {} });
+
+    const second = await propose(1);
+    await vote(backer, second.proposal, true);
+    await vote(critic, second.proposal, false);
+    await helpers.waitUntil(second.votingEnd.toNumber());
+    await execute(second.proposal);
+
+    escrow = await program.account.milestoneEscrow.fetch(milestoneEscrow);
+    expect(escrow.tranches[1].state).to.deep.equal({ refunded: {} });
+
+    const balanceBefore = await provider.connection.getBalance(critic.publicKey);
+    await program.methods
+      .refundEscrow()
+      .accounts({ participant: critic.publicKey, idoCampaignOwner: owner.publicKey })
+      .signers([critic])
+      .rpc();
+    const balanceAfter = await provider.connection.getBalance(critic.publicKey);
+    expect(balanceAfter - balanceBefore).to.equal(
+      escrow.refundableLamports.muln(200).divn(500).toNumber()
+    );
+  });
+});
@@ -0,0 +1,126 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { SolanaIdo } from "../target/types/solana_ido";
+import * as helpers from "../tests/helpers";
+import { expect } from "chai";
+import BN from "bn.js";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { getAccount, getAssociatedTokenAddressSync, getMint, getOrCreateAssociatedTokenAccount } from "@solana/spl-token";
+
+const LAUNCHPAD_OWNER = new PublicKey("BRhY2VPGiDvEnQphYjgvbCXRkGTLBY4bXzeYjDuKYkv6");
+
+describe("mint on claim tests", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.solanaIdo as Program<SolanaIdo>;
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+
+  before(async () => {
+    await helpers.ensureCampaignRegistry(program);
+    await helpers.airdropSol(provider, LAUNCHPAD_OWNER, 0.01);
+  });
+
+  it("mints vested tokens on claim and revokes the mint authority once finalized", async () => {
+    const owner = Keypair.generate();
+    const participant = Keypair.generate();
+    const { mint } = await helpers.createMintAndMintToOwner(provider, owner.publicKey);
+    await helpers.airdropSol(provider, owner.publicKey, 10);
+
+    const now = Math.floor(Date.now() / 1000);
+    const startSaleTime = new BN(now + 4);
+    const endSaleTime = new BN(now + 8);
+    const vestingEndTime = new BN(now + 14);
+
+    await program.methods
+      .initializeSale(
+        startSaleTime,
+        endSaleTime,
+        new BN(now + 10),
+        vestingEndTime,
+        helpers.priceLamports,
+        helpers.allocation,
+        helpers.softCap,
+        helpers.hardCap,
+        helpers.availableTokensAfterCliffPtc,
+        helpers.availableAllocationsPerParticipant,
+        helpers.campaignMetadata
+      )
+      .accounts({ owner: owner.publicKey, tokenMint: mint })
+      .signers([owner])
+      .rpc();
+
+    await program.methods
+      .configureMintOnClaim()
+      .accounts({ owner: owner.publicKey, tokenMint: mint })
+      .signers([owner])
+      .rpc();
+
+    // Nothing is deposited, the owner token account stays empty.
+    const ownerAta = await getOrCreateAssociatedTokenAccount(provider.connection, owner, mint, owner.publicKey).then(
+      (ata) => ata.address
+    );
+    await program.methods
+      .depositTokensToSale()
+      .accounts({ owner: owner.publicKey, tokenMint: mint, ownerTokenAccount: ownerAta })
+      .signers([owner])
+      .rpc();
+
+    const [idoCampaignPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("ido_campaign"), owner.publicKey.toBuffer()],
+      program.programId
+    );
+    expect((await getMint(provider.connection, mint)).mintAuthority?.toBase58()).to.equal(idoCampaignPda.toBase58());
+
+    await helpers.joinAsParticipant({
+      program,
+      provider,
+      owner,
+      participant,
+      idoCampaignPda,
+      startSaleTime,
+      endSaleTime,
+      allocations: new BN(5),
+    });
+
+    const releaseMintAuthority = () =>
+      program.methods
+        .releaseMintAuthority(true)
+        .accounts({ owner: owner.publicKey, tokenMint: mint })
+        .signers([owner])
+        .rpc();
+
+    await helpers.waitUntil(endSaleTime.toNumber());
+    try {
+      await releaseMintAuthority();
+      expect.fail("Expected releaseMintAuthority to throw");
+    } catch (error: any) {
+      helpers.expectIdlError(program, error, { msg: "Invalid status transition" });
+    }
+
+    await program.methods
+      .withdrawFunds()
+      .accounts({
+        launchpadOwner: LAUNCHPAD_OWNER,
+        owner: owner.publicKey,
+        ownerTokenAccount: ownerAta,
+        tokenMint: mint,
+        milestoneEscrow: null,
+      })
+      .signers([owner])
+      .rpc();
+    expect(Number((await getMint(provider.connection, mint)).supply)).to.equal(0);
+
+    await helpers.waitUntil(vestingEndTime.toNumber());
+    await program.methods
+      .claim()
+      .accounts({ participant: participant.publicKey, idoCampaignOwner: owner.publicKey, tokenMint: mint })
+      .signers([participant])
+      .rpc();
+
+    const participantAta = getAssociatedTokenAddressSync(mint, participant.publicKey, false);
+    expect(Number((await getAccount(provider.connection, participantAta)).amount)).to.equal(500);
+    expect(Number((await getMint(provider.connection, mint)).supply)).to.equal(500);
+
+    await releaseMintAuthority();
+    expect((await getMint(provider.connection, mint)).mintAuthority).to.equal(null);
+  });
+});
@@ -0,0 +1,240 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { SolanaIdo } from "../target/types/solana_ido";
+import * as helpers from "../tests/helpers";
+import { expect } from "chai";
+import BN from "bn.js";
+import { Keypair, PublicKey } from "@solana/web3.js";
+
+const LAUNCHPAD_OWNER = new PublicKey("BRhY2VPGiDvEnQphYjgvbCXRkGTLBY4bXzeYjDuKYkv6");
+
+describe("pro-rata sale tests", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.solanaIdo as Program<SolanaIdo>;
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+
+  before(async () => {
+    await helpers.ensureCampaignRegistry(program);
+    await helpers.airdropSol(provider, LAUNCHPAD_OWNER, 0.01);
+  });
+
+  const configureProRata = async (owner: Keypair) => {
+    await program.methods
+      .configureProRataSale()
+      .accounts({ owner: owner.publicKey })
+      .signers([owner])
+      .rpc();
+  };
+
+  it("accepts commitments above hard cap and refunds the unfilled part", async () => {
+    const owner = Keypair.generate();
+    const participants = [Keypair.generate(), Keypair.generate(), Keypair.generate()];
+    const { mint } = await helpers.createMintAndMintToOwner(provider, owner.publicKey);
+
+    const now = Math.floor(Date.now() / 1000);
+    const startSaleTime = new BN(now + 2);
+    const endSaleTime = new BN(now + 10);
+    const vestingEndTime = new BN(now + 16);
+
+    const { idoCampaignPda, solTreasuryPda, ownerAta } = await helpers.setupCampaign({
+      program,
+      provider,
+      owner,
+      mint,
+      startSaleTime,
+      endSaleTime,
+      cliff: new BN(now + 12),
+      vestingEndTime,
+      priceLamports: helpers.priceLamports,
+      allocation: helpers.allocation,
+      softCap: helpers.softCap,
+      hardCap: helpers.hardCap,
+      availableTokensAfterCliffPtc: helpers.availableTokensAfterCliffPtc,
+      availableAllocationsPerParticipant: helpers.availableAllocationsPerParticipant,
+      configure: () => configureProRata(owner),
+    });
+
+    // 3 x 500 tokens committed against a hard cap of 1000.
+    const userPdas: PublicKey[] = [];
+    for (const participant of participants) {
+      const { userPda } = await helpers.joinAsParticipant({
+        program,
+        provider,
+        owner,
+        participant,
+        idoCampaignPda,
+        startSaleTime,
+        endSaleTime,
+        allocations: new BN(5),
+      });
+      userPdas.push(userPda);
+    }
+
+    const campaignDuringSale = await program.account.idoCampaign.fetch(idoCampaignPda);
+    expect(campaignDuringSale.saleMode).to.deep.equal({ proRata: {} });
+    expect(campaignDuringSale.totalCommitted.toString()).to.equal("1500");
+    expect(campaignDuringSale.totalSold.toString()).to.equal(helpers.hardCap.toString());
+
+    await helpers.waitUntil(endSaleTime.toNumber());
+
+    // Each commitment fills 2/3, the fractional token goes to whoever settles last.
+    const paid = new BN(500).mul(helpers.priceLamports);
+    const expectedFills = [333, 333, 334];
+    const expectedRefund = (filled: number) => new BN(500 - filled).mul(helpers.priceLamports);
+
+    const projected = await program.methods
+      .getClaimable()
+      .accounts({
+        participant: participants[1].publicKey,
+        idoCampaignOwner: owner.publicKey,
+      })
+      .view();
+    expect(projected.total.toNumber()).to.equal(expectedFills[0]);
+    expect(projected.refundableLamports.toString()).to.equal(expectedRefund(expectedFills[0]).toString());
+
+    const refundOversubscription = (participant: Keypair) =>
+      program.methods
+        .refundOversubscription()
+        .accounts({
+          participant: participant.publicKey,
+          idoCampaignOwner: owner.publicKey,
+        })
+        .signers([participant])
+        .rpc();
+
+    for (const [i, participant] of participants.entries()) {
+      const balanceBefore = await provider.connection.getBalance(participant.publicKey);
+      await refundOversubscription(participant);
+      const balanceAfter = await provider.connection.getBalance(participant.publicKey);
+      expect(balanceAfter - balanceBefore).to.equal(expectedRefund(expectedFills[i]).toNumber());
+
+      // Only the filled tokens are paid for.
+      const user = await program.account.user.fetch(userPdas[i]);
+      expect(user.settled).to.equal(true);
+      expect(user.amount.toNumber()).to.equal(expectedFills[i]);
+      expect(user.paidLamports.toString()).to.equal(
+        new BN(expectedFills[i]).mul(helpers.priceLamports).toString()
+      );
+      expect(user.refundableLamports.toString()).to.equal("0");
+      expect(paid.toString()).to.equal(user.paidLamports.add(expectedRefund(expectedFills[i])).toString());
+    }
+
+    try {
+      await refundOversubscription(participants[0]);
+      expect.fail("Expected refundOversubscription to throw");
+    } catch (error: any) {
+      helpers.expectIdlError(program, error, { msg: "Nothing to refund" });
+    }
+
+    // The owner takes the proceeds of exactly the filled tokens, nothing is left behind.
+    await program.methods
+      .withdrawFunds()
+      .accounts({
+        launchpadOwner: LAUNCHPAD_OWNER,
+        owner: owner.publicKey,
+        ownerTokenAccount: ownerAta,
+        tokenMint: mint,
+        milestoneEscrow: null,
+      })
+      .signers([owner])
+      .rpc();
+    expect(await provider.connection.getBalance(solTreasuryPda)).to.equal(0);
+
+    await helpers.waitUntil(vestingEndTime.toNumber());
+    for (const participant of participants) {
+      await program.methods
+        .claim()
+        .accounts({ participant: participant.publicKey, idoCampaignOwner: owner.publicKey, tokenMint: mint })
+        .signers([participant])
+        .rpc();
+    }
+
+    const campaign = await program.account.idoCampaign.fetch(idoCampaignPda);
+    expect(campaign.totalClaimed.toString()).to.equal(helpers.hardCap.toString());
+    expect(campaign.status).to.deep.equal({ finalized: {} });
+  });
+
+  it("sale mode can't be changed after the token supply is deposited", async () => {
+    const owner = Keypair.generate();
+    const { mint } = await helpers.createMintAndMintToOwner(provider, owner.publicKey);
+
+    const now = Math.floor(Date.now() / 1000);
+    await helpers.setupCampaign({
+      program,
+      provider,
+      owner,
+      mint,
+      startSaleTime: new BN(now + 10),
+      endSaleTime: new BN(now + 20),
+      cliff: new BN(now + 30),
+      vestingEndTime: new BN(now + 40),
+      priceLamports: helpers.priceLamports,
+      allocation: helpers.allocation,
+      softCap: helpers.softCap,
+      hardCap: helpers.hardCap,
+      availableTokensAfterCliffPtc: helpers.availableTokensAfterCliffPtc,
+      availableAllocationsPerParticipant: helpers.availableAllocationsPerParticipant,
+    });
+
+    try {
+      await configureProRata(owner);
+      expect.fail("Expected configureProRataSale to throw");
+    } catch (error: any) {
+      helpers.expectIdlError(program, error, { msg: "Token supply already deposited" });
+    }
+  });
+
+  it("refund_oversubscription fails for fixed price sales", async () => {
+    const owner = Keypair.generate();
+    const participant = Keypair.generate();
+    const { mint } = await helpers.createMintAndMintToOwner(provider, owner.publicKey);
+
+    const now = Math.floor(Date.now() / 1000);
+    const startSaleTime = new BN(now + 1);
+    const endSaleTime = new BN(now + 4);
+
+    const { idoCampaignPda } = await helpers.setupCampaign({
+      program,
+      provider,
+      owner,
+      mint,
+      startSaleTime,
+      endSaleTime,
+      cliff: new BN(now + 10),
+      vestingEndTime: new BN(now + 20),
+      priceLamports: helpers.priceLamports,
+      allocation: helpers.allocation,
+      softCap: helpers.softCap,
+      hardCap: helpers.hardCap,
+      availableTokensAfterCliffPtc: helpers.availableTokensAfterCliffPtc,
+      availableAllocationsPerParticipant: helpers.availableAllocationsPerParticipant,
+    });
+
+    await helpers.joinAsParticipant({
+      program,
+      provider,
+      owner,
+      participant,
+      idoCampaignPda,
+      startSaleTime,
+      endSaleTime,
+      allocations: new BN(5),
+    });
+
+    await helpers.waitUntil(endSaleTime.toNumber());
+
+    try {
+      await program.methods
+        .refundOversubscription()
+        .accounts({
+          participant: participant.publicKey,
+          idoCampaignOwner: owner.publicKey,
+        })
+        .signers([participant])
+        .rpc();
+      expect.fail("Expected refundOversubscription to throw");
+    } catch (error: any) {
+      helpers.expectIdlError(program, error, { msg: "Invalid sale mode" });
+    }
+  });
+});
@@ -0,0 +1,281 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { SolanaIdo } from "../target/types/solana_ido";
+import * as helpers from "../tests/helpers";
+import { expect } from "chai";
+import BN from "bn.js";
+import { Keypair, PublicKey } from "@solana/web3.js";
+
+const LAUNCHPAD_OWNER = new PublicKey("BRhY2VPGiDvEnQphYjgvbCXRkGTLBY4bXzeYjDuKYkv6");
+
+describe("referral tests", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.solanaIdo as Program<SolanaIdo>;
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+
+  // 5 allocations of 100 tokens, 10% of it goes to the referrer.
+  const purchaseLamports = new BN(500).mul(helpers.priceLamports);
+  const rewardLamports = purchaseLamports.divn(10);
+
+  before(async () => {
+    await helpers.ensureCampaignRegistry(program);
+    await helpers.airdropSol(provider, LAUNCHPAD_OWNER, 0.01);
+  });
+
+  const setupReferralCampaign = async (
+    owner: Keypair,
+    payout: { onSuccess: {} } | { atWithdraw: {} },
+    budgetLamports: BN
+  ) => {
+    const { mint } = await helpers.createMintAndMintToOwner(provider, owner.publicKey);
+
+    const now = Math.floor(Date.now() / 1000);
+    const startSaleTime = new BN(now + 4);
+    const endSaleTime = new BN(now + 12);
+
+    const { idoCampaignPda, ownerAta } = await helpers.setupCampaign({
+      program,
+      provider,
+      owner,
+      mint,
+      startSaleTime,
+      endSaleTime,
+      cliff: new BN(now + 14),
+      vestingEndTime: new BN(now + 100),
+      priceLamports: helpers.priceLamports,
+      allocation: helpers.allocation,
+      softCap: helpers.softCap,
+      hardCap: helpers.hardCap,
+      availableTokensAfterCliffPtc: helpers.availableTokensAfterCliffPtc,
+      availableAllocationsPerParticipant: helpers.availableAllocationsPerParticipant,
+      configure: async () => {
+        await program.methods
+          .configureReferralProgram(1000, payout, budgetLamports)
+          .accounts({ owner: owner.publicKey })
+          .signers([owner])
+          .rpc();
+      },
+    });
+
+    const referrer = Keypair.generate();
+    await helpers.airdropSol(provider, referrer.publicKey, 1);
+    await program.methods
+      .registerReferrer()
+      .accounts({ referrer: referrer.publicKey, idoCampaignOwner: owner.publicKey })
+      .signers([referrer])
+      .rpc();
+    const referral = helpers.findReferralPda(program.programId, idoCampaignPda, referrer.publicKey);
+
+    const joinReferred = async (participant: Keypair) => {
+      await helpers.airdropSol(provider, participant.publicKey, 10);
+      await helpers.waitUntil(startSaleTime.toNumber());
+      await program.methods
+        .joinIdo(new BN(5))
+        .accounts({
+          participant: participant.publicKey,
+          idoCampaignOwner: owner.publicKey,
+          referral,
+        })
+        .signers([participant])
+        .rpc();
+    };
+
+    const claimReward = () =>
+      program.methods
+        .claimReferralReward()
+        .accounts({ referrer: referrer.publicKey, idoCampaignOwner: owner.publicKey })
+        .signers([referrer])
+        .rpc();
+
+    return { mint, idoCampaignPda, ownerAta, endSaleTime, referrer, referral, joinReferred, claimReward };
+  };
+
+  const withdrawFunds = (owner: Keypair, mint: PublicKey, ownerAta: PublicKey) =>
+    program.methods
+      .withdrawFunds()
+      .accounts({
+        launchpadOwner: LAUNCHPAD_OWNER,
+        owner: owner.publicKey,
+        ownerTokenAccount: ownerAta,
+        tokenMint: mint,
+        milestoneEscrow: null,
+      })
+      .signers([owner])
+      .rpc();
+
+  it("pays on-success rewards once the sale succeeds, up to the budget", async () => {
+    const owner = Keypair.generate();
+    const participants = [Keypair.generate(), Keypair.generate()];
+    const budgetLamports = rewardLamports.muln(3).divn(2);
+    const { mint, idoCampaignPda, ownerAta, endSaleTime, referrer, referral, joinReferred, claimReward } =
+      await setupReferralCampaign(owner, { onSuccess: {} }, budgetLamports);
+
+    try {
+      await joinReferred(referrer);
+      expect.fail("Expected joinIdo to throw");
+    } catch (error: any) {
+      helpers.expectIdlError(program, error, { msg: "Self referral is not allowed" });
+    }
+
+    const balanceBefore = await provider.connection.getBalance(referrer.publicKey);
+    for (const participant of participants) {
+      await joinReferred(participant);
+    }
+    expect(await provider.connection.getBalance(referrer.publicKey)).to.equal(balanceBefore);
+
+    // The whole purchase stays refundable until the sale succeeds.
+    const userPda = PublicKey.findProgramAddressSync(
+      [Buffer.from("user"), idoCampaignPda.toBuffer(), participants[0].publicKey.toBuffer()],
+      program.programId
+    )[0];
+    const user = await program.account.user.fetch(userPda);
+    expect(user.paidLamports.toString()).to.equal(purchaseLamports.toString());
+
+    const referralAccount = await program.account.referral.fetch(referral);
+    expect(referralAccount.referredParticipants.toNumber()).to.equal(2);
+    expect(referralAccount.referredLamports.toString()).to.equal(purchaseLamports.muln(2).toString());
+    expect(referralAccount.rewardLamports.toString()).to.equal(budgetLamports.toString());
+
+    let campaign = await program.account.idoCampaign.fetch(idoCampaignPda);
+    expect(campaign.referralRewardsLamports.toString()).to.equal(budgetLamports.toString());
+
+    try {
+      await claimReward();
+      expect.fail("Expected claimReferralReward to throw");
+    } catch (error: any) {
+      helpers.expectIdlError(program, error, { msg: "Nothing to claim" });
+    }
+
+    // Claimable before the owner withdraws, the owner then gets the rest.
+    await helpers.waitUntil(endSaleTime.toNumber());
+    await claimReward();
+    expect((await provider.connection.getBalance(referrer.publicKey)) - balanceBefore).to.equal(
+      budgetLamports.toNumber()
+    );
+    campaign = await program.account.idoCampaign.fetch(idoCampaignPda);
+    expect(campaign.referralRewardsLamports.toNumber()).to.equal(0);
+
+    await withdrawFunds(owner, mint, ownerAta);
+    const solTreasuryPda = PublicKey.findProgramAddressSync(
+      [Buffer.from("sol_treasury"), idoCampaignPda.toBuffer()],
+      program.programId
+    )[0];
+    expect(await provider.connection.getBalance(solTreasuryPda)).to.equal(0);
+  });
+
+  it("refunds referred purchases in full when the sale is cancelled", async () => {
+    const owner = Keypair.generate();
+    const participant = Keypair.generate();
+    const { mint, idoCampaignPda, ownerAta, joinReferred, claimReward } = await setupReferralCampaign(
+      owner,
+      { onSuccess: {} },
+      purchaseLamports
+    );
+    await joinReferred(participant);
+
+    await program.methods
+      .closeCampaign()
+      .accounts({ owner: owner.publicKey, ownerTokenAccount: ownerAta, tokenMint: mint })
+      .signers([owner])
+      .rpc();
+
+    try {
+      await claimReward();
+      expect.fail("Expected claimReferralReward to throw");
+    } catch (error: any) {
+      helpers.expectIdlError(program, error, { msg: "Nothing to claim" });
+    }
+
+    const solTreasuryPda = PublicKey.findProgramAddressSync(
+      [Buffer.from("sol_treasury"), idoCampaignPda.toBuffer()],
+      program.programId
+    )[0];
+    const treasuryBefore = await provider.connection.getBalance(solTreasuryPda);
+    await program.methods
+      .refund()
+      .accounts({ participant: participant.publicKey, idoCampaignOwner: owner.publicKey })
+      .signers([participant])
+      .rpc();
+    expect(treasuryBefore - (await provider.connection.getBalance(solTreasuryPda))).to.equal(
+      purchaseLamports.toNumber()
+    );
+  });
+
+  it("keeps rewards paid at withdraw in the treasury for the referrer", async () => {
+    const owner = Keypair.generate();
+    const participants = [Keypair.generate(), Keypair.generate()];
+    const { mint, ownerAta, endSaleTime, referrer, joinReferred, claimReward } = await setupReferralCampaign(
+      owner,
+      { atWithdraw: {} },
+      purchaseLamports
+    );
+
+    const balanceBefore = await provider.connection.getBalance(referrer.publicKey);
+    for (const participant of participants) {
+      await joinReferred(participant);
+    }
+    expect(await provider.connection.getBalance(referrer.publicKey)).to.equal(balanceBefore);
+
+    try {
+      await claimReward();
+      expect.fail("Expected claimReferralReward to throw");
+    } catch (error: any) {
+      helpers.expectIdlError(program, error, { msg: "Funds not withdrawn" });
+    }
+
+    await helpers.waitUntil(endSaleTime.toNumber());
+    await withdrawFunds(owner, mint, ownerAta);
+
+    await claimReward();
+    expect((await provider.connection.getBalance(referrer.publicKey)) - balanceBefore).to.equal(
+      rewardLamports.muln(2).toNumber()
+    );
+
+    try {
+      await claimReward();
+      expect.fail("Expected claimReferralReward to throw");
+    } catch (error: any) {
+      helpers.expectIdlError(program, error, { msg: "Nothing to claim" });
+    }
+  });
+
+  it("referral program is rejected for pro-rata sales", async () => {
+    const owner = Keypair.generate();
+    const { mint } = await helpers.createMintAndMintToOwner(provider, owner.publicKey);
+
+    const now = Math.floor(Date.now() / 1000);
+    await helpers.setupCampaign({
+      program,
+      provider,
+      owner,
+      mint,
+      startSaleTime: new BN(now + 10),
+      endSaleTime: new BN(now + 20),
+      cliff: new BN(now + 30),
+      vestingEndTime: new BN(now + 40),
+      priceLamports: helpers.priceLamports,
+      allocation: helpers.allocation,
+      softCap: helpers.softCap,
+      hardCap: helpers.hardCap,
+      availableTokensAfterCliffPtc: helpers.availableTokensAfterCliffPtc,
+      availableAllocationsPerParticipant: helpers.availableAllocationsPerParticipant,
+      configure: async () => {
+        await program.methods
+          .configureProRataSale()
+          .accounts({ owner: owner.publicKey })
+          .signers([owner])
+          .rpc();
+        try {
+          await program.methods
+            .configureReferralProgram(1000, { onSuccess: {} }, new BN(1_000_000))
+            .accounts({ owner: owner.publicKey })
+            .signers([owner])
+            .rpc();
+          expect.fail("Expected configureReferralProgram to throw");
+        } catch (error: any) {
+          helpers.expectIdlError(program, error, { msg: "Invalid sale mode" });
+        }
+      },
+    });
+  });
+});
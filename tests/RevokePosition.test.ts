@@ -0,0 +1,148 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { SolanaIdo } from "../target/types/solana_ido";
+import * as helpers from "../tests/helpers";
+import { expect } from "chai";
+import BN from "bn.js";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { getAccount, getAssociatedTokenAddressSync } from "@solana/spl-token";
+
+describe("revoke position tests", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.solanaIdo as Program<SolanaIdo>;
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+
+  before(async () => {
+    await helpers.ensureCampaignRegistry(program);
+  });
+
+  it("claws back the unvested part and leaves the vested part claimable", async () => {
+    const owner = Keypair.generate();
+    const [advisor, buyer] = [Keypair.generate(), Keypair.generate()];
+    const { mint } = await helpers.createMintAndMintToOwner(provider, owner.publicKey);
+
+    const now = Math.floor(Date.now() / 1000);
+    const startSaleTime = new BN(now + 4);
+    const endSaleTime = new BN(now + 10);
+    const cliff = new BN(now + 12);
+    const vestingEndTime = new BN(now + 30);
+
+    const { idoCampaignPda, ownerAta } = await helpers.setupCampaign({
+      program,
+      provider,
+      owner,
+      mint,
+      startSaleTime,
+      endSaleTime,
+      cliff,
+      vestingEndTime,
+      priceLamports: helpers.priceLamports,
+      allocation: helpers.allocation,
+      softCap: helpers.softCap,
+      hardCap: helpers.hardCap,
+      availableTokensAfterCliffPtc: helpers.availableTokensAfterCliffPtc,
+      availableAllocationsPerParticipant: helpers.availableAllocationsPerParticipant,
+    });
+
+    const userPdas = new Map<Keypair, PublicKey>();
+    for (const participant of [advisor, buyer]) {
+      const { userPda } = await helpers.joinAsParticipant({
+        program,
+        provider,
+        owner,
+        participant,
+        idoCampaignPda,
+        startSaleTime,
+        endSaleTime,
+        allocations: new BN(5),
+      });
+      userPdas.set(participant, userPda);
+    }
+
+    const setRevocable = (participant: Keypair, signers: Keypair[]) =>
+      program.methods
+        .setPositionRevocable(true)
+        .accounts({ owner: owner.publicKey, participant: participant.publicKey })
+        .signers(signers)
+        .rpc();
+
+    // The authority alone can't make a paid position revocable.
+    try {
+      await setRevocable(buyer, [owner]);
+      expect.fail("Expected setPositionRevocable to throw");
+    } catch (error: any) {
+      expect(error.message).to.include("Missing signature");
+    }
+    await setRevocable(advisor, [owner, advisor]);
+
+    const revoke = (participant: Keypair) =>
+      program.methods
+        .revokePosition()
+        .accounts({
+          owner: owner.publicKey,
+          ownerTokenAccount: ownerAta,
+          tokenMint: mint,
+          participant: participant.publicKey,
+          milestoneEscrow: null,
+        })
+        .signers([owner])
+        .rpc();
+
+    await helpers.waitUntil(cliff.toNumber() + 3);
+
+    try {
+      await revoke(buyer);
+      expect.fail("Expected revokePosition to throw");
+    } catch (error: any) {
+      helpers.expectIdlError(program, error, { msg: "Position is not revocable" });
+    }
+
+    const paidBefore = (await program.account.user.fetch(userPdas.get(advisor)!)).paidLamports.toNumber();
+    const advisorLamportsBefore = await provider.connection.getBalance(advisor.publicKey);
+    await revoke(advisor);
+
+    const advisorUser = await program.account.user.fetch(userPdas.get(advisor)!);
+    expect(advisorUser.revoked).to.equal(true);
+    expect(advisorUser.amount.toNumber()).to.be.within(100, 499);
+    const ownerBalance = (await getAccount(provider.connection, ownerAta)).amount;
+    expect(Number(ownerBalance)).to.equal(500 - advisorUser.amount.toNumber());
+
+    // The authority pays back the revoked tokens at the price the advisor paid.
+    const refund = Math.floor((paidBefore * (500 - advisorUser.amount.toNumber())) / 500);
+    expect(advisorUser.paidLamports.toNumber()).to.equal(paidBefore - refund);
+    expect(await provider.connection.getBalance(advisor.publicKey)).to.equal(advisorLamportsBefore + refund);
+
+    try {
+      await revoke(advisor);
+      expect.fail("Expected revokePosition to throw");
+    } catch (error: any) {
+      helpers.expectIdlError(program, error, { msg: "Position already revoked" });
+    }
+
+    const claim = (participant: Keypair) =>
+      program.methods
+        .claim()
+        .accounts({
+          participant: participant.publicKey,
+          idoCampaignOwner: owner.publicKey,
+          tokenMint: mint,
+        })
+        .signers([participant])
+        .rpc();
+
+    // What vested before the revocation is claimable right away, not vested again.
+    await claim(advisor);
+    const advisorAta = getAssociatedTokenAddressSync(mint, advisor.publicKey, false);
+    expect(Number((await getAccount(provider.connection, advisorAta)).amount)).to.equal(
+      advisorUser.amount.toNumber()
+    );
+
+    await helpers.waitUntil(vestingEndTime.toNumber());
+    try {
+      await claim(advisor);
+      expect.fail("Expected claim to throw");
+    } catch (error: any) {
+      helpers.expectIdlError(program, error, { msg: "Nothing to claim" });
+    }
+  });
+});
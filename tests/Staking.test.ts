@@ -0,0 +1,164 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { SolanaIdo } from "../target/types/solana_ido";
+import * as helpers from "../tests/helpers";
+import { expect } from "chai";
+import BN from "bn.js";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { getOrCreateAssociatedTokenAccount, mintTo } from "@solana/spl-token";
+
+describe("staking tiers tests", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.solanaIdo as Program<SolanaIdo>;
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+  const payer = (provider.wallet as anchor.Wallet).payer;
+
+  let stakeMint: PublicKey;
+
+  before(async () => {
+    await helpers.ensureCampaignRegistry(program);
+    ({ stakeMint } = await helpers.ensureLaunchpadConfig(program, provider));
+  });
+
+  const stakeTokens = async (staker: Keypair, amount: number) => {
+    const stakerAta = await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      payer,
+      stakeMint,
+      staker.publicKey
+    ).then((ata) => ata.address);
+    await mintTo(provider.connection, payer, stakeMint, stakerAta, payer, amount);
+
+    await program.methods
+      .stake(new BN(amount))
+      .accounts({ owner: staker.publicKey, stakeMint, ownerTokenAccount: stakerAta })
+      .signers([staker])
+      .rpc();
+
+    return stakerAta;
+  };
+
+  it("join_ido caps allocations by the tier of the participant's stake", async () => {
+    const owner = Keypair.generate();
+    const whale = Keypair.generate();
+    const minnow = Keypair.generate();
+    const unstaked = Keypair.generate();
+    const { mint } = await helpers.createMintAndMintToOwner(provider, owner.publicKey);
+
+    const now = Math.floor(Date.now() / 1000);
+    const startSaleTime = new BN(now + 4);
+    const endSaleTime = new BN(now + 30);
+
+    const { idoCampaignPda } = await helpers.setupCampaign({
+      program,
+      provider,
+      owner,
+      mint,
+      startSaleTime,
+      endSaleTime,
+      cliff: new BN(now + 40),
+      vestingEndTime: new BN(now + 60),
+      priceLamports: helpers.priceLamports,
+      allocation: helpers.allocation,
+      softCap: helpers.softCap,
+      hardCap: helpers.hardCap,
+      availableTokensAfterCliffPtc: helpers.availableTokensAfterCliffPtc,
+      availableAllocationsPerParticipant: helpers.availableAllocationsPerParticipant,
+      configure: async () => {
+        await program.methods
+          .configureStakeTiers([
+            { minStake: new BN(100), maxAllocations: new BN(1) },
+            { minStake: new BN(500), maxAllocations: new BN(3) },
+          ])
+          .accounts({ owner: owner.publicKey })
+          .signers([owner])
+          .rpc();
+      },
+    });
+
+    for (const participant of [whale, minnow, unstaked]) {
+      await helpers.airdropSol(provider, participant.publicKey, 10);
+    }
+    await stakeTokens(whale, 500);
+    await stakeTokens(minnow, 100);
+
+    const stakeTiers = helpers.findStakeTiersPda(program.programId, idoCampaignPda);
+    const join = (participant: Keypair, allocations: number, staked: boolean) =>
+      program.methods
+        .joinIdo(new BN(allocations))
+        .accounts({
+          participant: participant.publicKey,
+          idoCampaignOwner: owner.publicKey,
+          stakeAccount: staked
+            ? helpers.findStakeAccountPda(program.programId, participant.publicKey)
+            : null,
+          stakeTiers,
+        })
+        .signers([participant])
+        .rpc();
+
+    await helpers.waitUntil(startSaleTime.toNumber());
+
+    await join(whale, 3, true);
+
+    try {
+      await join(minnow, 2, true);
+      expect.fail("Expected joinIdo to throw");
+    } catch (error: any) {
+      helpers.expectIdlError(program, error, { msg: "Invalid number of allocations" });
+    }
+    await join(minnow, 1, true);
+
+    try {
+      await join(unstaked, 1, false);
+      expect.fail("Expected joinIdo to throw");
+    } catch (error: any) {
+      helpers.expectIdlError(program, error, { msg: "Stake too low for any tier" });
+    }
+
+    const campaign = await program.account.idoCampaign.fetch(idoCampaignPda);
+    expect(campaign.stakeTiered).to.equal(true);
+    expect(campaign.totalSold.toString()).to.equal("400");
+  });
+
+  it("stake stays locked for the lock period", async () => {
+    const staker = Keypair.generate();
+    await helpers.airdropSol(provider, staker.publicKey, 1);
+
+    const setLockPeriod = (seconds: number) =>
+      program.methods
+        .updateLaunchpadConfig(payer.publicKey, new BN(seconds))
+        .accounts({ admin: payer.publicKey })
+        .rpc();
+
+    await setLockPeriod(3);
+    try {
+      const stakerAta = await stakeTokens(staker, 200);
+
+      const unstake = (amount: number) =>
+        program.methods
+          .unstake(new BN(amount))
+          .accounts({ owner: staker.publicKey, stakeMint, ownerTokenAccount: stakerAta })
+          .signers([staker])
+          .rpc();
+
+      try {
+        await unstake(200);
+        expect.fail("Expected unstake to throw");
+      } catch (error: any) {
+        helpers.expectIdlError(program, error, { msg: "Stake is locked" });
+      }
+
+      const { lockedUntil } = await program.account.stakeAccount.fetch(
+        helpers.findStakeAccountPda(program.programId, staker.publicKey)
+      );
+      await helpers.waitUntil(lockedUntil.toNumber());
+      await unstake(200);
+
+      const balance = await helpers.getTokenBalanceOrZero(provider, stakerAta);
+      expect(balance.toString()).to.equal("200");
+    } finally {
+      await setLockPeriod(0);
+    }
+  });
+});
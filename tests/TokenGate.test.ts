@@ -0,0 +1,206 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { SolanaIdo } from "../target/types/solana_ido";
+import * as helpers from "../tests/helpers";
+import { expect } from "chai";
+import BN from "bn.js";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { createMint, getOrCreateAssociatedTokenAccount, mintTo } from "@solana/spl-token";
+
+describe("token gate tests", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.solanaIdo as Program<SolanaIdo>;
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+  const payer = (provider.wallet as anchor.Wallet).payer;
+
+  before(async () => {
+    await helpers.ensureCampaignRegistry(program);
+  });
+
+  it("holders join with allocations scaled by their gate balance", async () => {
+    const owner = Keypair.generate();
+    const [holder, smallHolder, outsider] = [...Array(3)].map(() => Keypair.generate());
+    const { mint } = await helpers.createMintAndMintToOwner(provider, owner.publicKey);
+    const gateMint = await createMint(provider.connection, payer, payer.publicKey, null, 0);
+
+    const now = Math.floor(Date.now() / 1000);
+    const startSaleTime = new BN(now + 6);
+    const endSaleTime = new BN(now + 40);
+
+    await helpers.setupCampaign({
+      program,
+      provider,
+      owner,
+      mint,
+      startSaleTime,
+      endSaleTime,
+      cliff: new BN(now + 42),
+      vestingEndTime: new BN(now + 100),
+      priceLamports: helpers.priceLamports,
+      allocation: helpers.allocation,
+      softCap: helpers.softCap,
+      hardCap: helpers.hardCap,
+      availableTokensAfterCliffPtc: helpers.availableTokensAfterCliffPtc,
+      availableAllocationsPerParticipant: helpers.availableAllocationsPerParticipant,
+      configure: async () => {
+        await program.methods
+          .configureTokenGate(gateMint, false, new BN(100), new BN(100))
+          .accounts({ owner: owner.publicKey })
+          .signers([owner])
+          .rpc();
+      },
+    });
+
+    const gateAccounts = new Map<string, PublicKey>();
+    for (const [participant, held] of [
+      [holder, 300],
+      [smallHolder, 50],
+    ] as [Keypair, number][]) {
+      const ata = await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        payer,
+        gateMint,
+        participant.publicKey
+      );
+      await mintTo(provider.connection, payer, gateMint, ata.address, payer, held);
+      gateAccounts.set(participant.publicKey.toBase58(), ata.address);
+    }
+
+    for (const participant of [holder, smallHolder, outsider]) {
+      await helpers.airdropSol(provider, participant.publicKey, 10);
+    }
+    await helpers.waitUntil(startSaleTime.toNumber());
+
+    const join = (participant: Keypair, allocations: number) =>
+      program.methods
+        .joinIdo(new BN(allocations))
+        .accounts({
+          participant: participant.publicKey,
+          idoCampaignOwner: owner.publicKey,
+          gateTokenAccount: gateAccounts.get(participant.publicKey.toBase58()) ?? null,
+        })
+        .signers([participant])
+        .rpc();
+
+    const failures: [Keypair, number, string][] = [
+      [outsider, 1, "Gate token account required"],
+      [smallHolder, 1, "Insufficient gate token balance"],
+      [holder, 4, "Invalid number of allocations"],
+    ];
+    for (const [participant, allocations, msg] of failures) {
+      try {
+        await join(participant, allocations);
+        expect.fail("Expected joinIdo to throw");
+      } catch (error: any) {
+        helpers.expectIdlError(program, error, { msg });
+      }
+    }
+
+    await join(holder, 3);
+  });
+
+  it("holders of any listed mint join a campaign gated by a mint list", async () => {
+    const owner = Keypair.generate();
+    const [holder, unlistedHolder] = [Keypair.generate(), Keypair.generate()];
+    const { mint } = await helpers.createMintAndMintToOwner(provider, owner.publicKey);
+    const [listedMint, removedMint, unlistedMint] = await Promise.all(
+      [...Array(3)].map(() => createMint(provider.connection, payer, payer.publicKey, null, 0))
+    );
+
+    const now = Math.floor(Date.now() / 1000);
+    const startSaleTime = new BN(now + 6);
+    const endSaleTime = new BN(now + 40);
+
+    const { idoCampaignPda } = await helpers.setupCampaign({
+      program,
+      provider,
+      owner,
+      mint,
+      startSaleTime,
+      endSaleTime,
+      cliff: new BN(now + 42),
+      vestingEndTime: new BN(now + 100),
+      priceLamports: helpers.priceLamports,
+      allocation: helpers.allocation,
+      softCap: helpers.softCap,
+      hardCap: helpers.hardCap,
+      availableTokensAfterCliffPtc: helpers.availableTokensAfterCliffPtc,
+      availableAllocationsPerParticipant: helpers.availableAllocationsPerParticipant,
+      configure: async () => {
+        try {
+          await program.methods
+            .configureTokenGate(listedMint, true, new BN(1), new BN(0))
+            .accounts({ owner: owner.publicKey })
+            .signers([owner])
+            .rpc();
+          expect.fail("Expected configureTokenGate to throw");
+        } catch (error: any) {
+          helpers.expectIdlError(program, error, { msg: "Invalid token gate" });
+        }
+
+        await program.methods
+          .configureTokenGate(PublicKey.default, true, new BN(1), new BN(0))
+          .accounts({ owner: owner.publicKey })
+          .signers([owner])
+          .rpc();
+      },
+    });
+
+    const entryOf = (gateMint: PublicKey) => helpers.findGateListEntryPda(program.programId, idoCampaignPda, gateMint);
+    await program.methods
+      .addToGateList([listedMint, removedMint])
+      .accounts({ owner: owner.publicKey })
+      .remainingAccounts(
+        [listedMint, removedMint].map((gateMint) => ({ pubkey: entryOf(gateMint), isSigner: false, isWritable: true }))
+      )
+      .signers([owner])
+      .rpc();
+    await program.methods
+      .removeFromGateList()
+      .accounts({ owner: owner.publicKey })
+      .remainingAccounts([{ pubkey: entryOf(removedMint), isSigner: false, isWritable: true }])
+      .signers([owner])
+      .rpc();
+    expect(await program.account.gateListEntry.fetchNullable(entryOf(removedMint))).to.equal(null);
+
+    const gateAccountOf = async (participant: Keypair, gateMint: PublicKey) => {
+      const ata = await getOrCreateAssociatedTokenAccount(provider.connection, payer, gateMint, participant.publicKey);
+      await mintTo(provider.connection, payer, gateMint, ata.address, payer, 1);
+      return ata.address;
+    };
+    const holderGateAccount = await gateAccountOf(holder, listedMint);
+    const unlistedGateAccount = await gateAccountOf(unlistedHolder, unlistedMint);
+
+    for (const participant of [holder, unlistedHolder]) {
+      await helpers.airdropSol(provider, participant.publicKey, 10);
+    }
+    await helpers.waitUntil(startSaleTime.toNumber());
+
+    const join = (participant: Keypair, gateTokenAccount: PublicKey, gateListEntry: PublicKey | null) =>
+      program.methods
+        .joinIdo(new BN(1))
+        .accounts({
+          participant: participant.publicKey,
+          idoCampaignOwner: owner.publicKey,
+          gateTokenAccount,
+          gateListEntry,
+        })
+        .signers([participant])
+        .rpc();
+
+    const failures: [PublicKey | null, string][] = [
+      [null, "Gate mint not listed"],
+      [entryOf(listedMint), "Gate mint not listed"],
+    ];
+    for (const [gateListEntry, msg] of failures) {
+      try {
+        await join(unlistedHolder, unlistedGateAccount, gateListEntry);
+        expect.fail("Expected joinIdo to throw");
+      } catch (error: any) {
+        helpers.expectIdlError(program, error, { msg });
+      }
+    }
+
+    await join(holder, holderGateAccount, entryOf(listedMint));
+  });
+});
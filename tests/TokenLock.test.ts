@@ -0,0 +1,143 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { SolanaIdo } from "../target/types/solana_ido";
+import * as helpers from "../tests/helpers";
+import { expect } from "chai";
+import BN from "bn.js";
+import { Keypair } from "@solana/web3.js";
+import { getAccount, getAssociatedTokenAddressSync, mintTo } from "@solana/spl-token";
+
+describe("token lock tests", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.solanaIdo as Program<SolanaIdo>;
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+
+  before(async () => {
+    await helpers.ensureCampaignRegistry(program);
+  });
+
+  it("vests locked tokens and returns the unvested part on revocation", async () => {
+    const owner = Keypair.generate();
+    const [advisor, teamMember] = [Keypair.generate(), Keypair.generate()];
+    const { mint } = await helpers.createMintAndMintToOwner(provider, owner.publicKey);
+
+    const now = Math.floor(Date.now() / 1000);
+    const { ownerAta } = await helpers.setupCampaign({
+      program,
+      provider,
+      owner,
+      mint,
+      startSaleTime: new BN(now + 10),
+      endSaleTime: new BN(now + 20),
+      cliff: new BN(now + 30),
+      vestingEndTime: new BN(now + 40),
+      priceLamports: helpers.priceLamports,
+      allocation: helpers.allocation,
+      softCap: helpers.softCap,
+      hardCap: helpers.hardCap,
+      availableTokensAfterCliffPtc: helpers.availableTokensAfterCliffPtc,
+      availableAllocationsPerParticipant: helpers.availableAllocationsPerParticipant,
+    });
+    await mintTo(provider.connection, owner, mint, ownerAta, owner, 2000);
+
+    const lockCliff = now + 4;
+    const lockVestingEnd = now + 16;
+    for (const [beneficiary, name, revocable] of [
+      [advisor, "advisor", true],
+      [teamMember, "team", false],
+    ] as [Keypair, string, boolean][]) {
+      await program.methods
+        .createTokenLock(name, new BN(1000), new BN(lockCliff), new BN(lockVestingEnd), 20, revocable)
+        .accounts({
+          owner: owner.publicKey,
+          ownerTokenAccount: ownerAta,
+          beneficiary: beneficiary.publicKey,
+          tokenMint: mint,
+        })
+        .signers([owner])
+        .rpc();
+      await helpers.airdropSol(provider, beneficiary.publicKey, 1);
+    }
+
+    const claimLock = (beneficiary: Keypair) =>
+      program.methods
+        .claimTokenLock()
+        .accounts({
+          beneficiary: beneficiary.publicKey,
+          idoCampaignOwner: owner.publicKey,
+          tokenMint: mint,
+        })
+        .signers([beneficiary])
+        .rpc();
+    const revokeLock = (beneficiary: Keypair) =>
+      program.methods
+        .revokeTokenLock()
+        .accounts({
+          owner: owner.publicKey,
+          ownerTokenAccount: ownerAta,
+          beneficiary: beneficiary.publicKey,
+          tokenMint: mint,
+        })
+        .signers([owner])
+        .rpc();
+
+    try {
+      await claimLock(advisor);
+      expect.fail("Expected claimTokenLock to throw");
+    } catch (error: any) {
+      helpers.expectIdlError(program, error, { msg: "Nothing to claim" });
+    }
+
+    try {
+      await revokeLock(teamMember);
+      expect.fail("Expected revokeTokenLock to throw");
+    } catch (error: any) {
+      helpers.expectIdlError(program, error, { msg: "Token lock is not revocable" });
+    }
+
+    await helpers.waitUntil(lockCliff + 4);
+    const ownerBalanceBefore = (await getAccount(provider.connection, ownerAta)).amount;
+    await revokeLock(advisor);
+
+    const [idoCampaignPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("ido_campaign"), owner.publicKey.toBuffer()],
+      program.programId
+    );
+    const [advisorLockPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("token_lock"), idoCampaignPda.toBuffer(), advisor.publicKey.toBuffer()],
+      program.programId
+    );
+    const advisorLock = await program.account.tokenLock.fetch(advisorLockPda);
+    expect(advisorLock.revoked).to.equal(true);
+    expect(advisorLock.amount.toNumber()).to.be.within(200, 999);
+
+    const ownerBalanceAfter = (await getAccount(provider.connection, ownerAta)).amount;
+    expect(Number(ownerBalanceAfter - ownerBalanceBefore)).to.equal(1000 - advisorLock.amount.toNumber());
+
+    try {
+      await revokeLock(advisor);
+      expect.fail("Expected revokeTokenLock to throw");
+    } catch (error: any) {
+      helpers.expectIdlError(program, error, { msg: "Token lock already revoked" });
+    }
+
+    // What vested before the revocation is claimable right away, not vested again.
+    await claimLock(advisor);
+    const advisorAta = getAssociatedTokenAddressSync(mint, advisor.publicKey, false);
+    expect(Number((await getAccount(provider.connection, advisorAta)).amount)).to.equal(
+      advisorLock.amount.toNumber()
+    );
+
+    await helpers.waitUntil(lockVestingEnd);
+    try {
+      await claimLock(advisor);
+      expect.fail("Expected claimTokenLock to throw");
+    } catch (error: any) {
+      helpers.expectIdlError(program, error, { msg: "Nothing to claim" });
+    }
+    await claimLock(teamMember);
+
+    const teamMemberAta = getAssociatedTokenAddressSync(mint, teamMember.publicKey, false);
+    expect(Number((await getAccount(provider.connection, teamMemberAta)).amount)).to.equal(1000);
+  });
+});
@@ -0,0 +1,147 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { SolanaIdo } from "../target/types/solana_ido";
+import * as helpers from "../tests/helpers";
+import { expect } from "chai";
+import { Keypair } from "@solana/web3.js";
+import BN from "bn.js";
+
+describe("view instructions tests", () => {
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.solanaIdo as Program<SolanaIdo>;
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+
+  before(async () => {
+    await helpers.ensureCampaignRegistry(program);
+  });
+
+  it("quote_purchase, get_campaign_status and get_claimable match on-chain rules", async () => {
+    const owner = Keypair.generate();
+    const participant = Keypair.generate();
+    const { mint } = await helpers.createMintAndMintToOwner(provider, owner.publicKey);
+
+    const now = Math.floor(Date.now() / 1000);
+    const startSaleTime = new BN(now + 2);
+    const endSaleTime = new BN(now + 6);
+    const cliff = new BN(now + 8);
+    const vestingEndTime = new BN(now + 100);
+
+    const { idoCampaignPda } = await helpers.setupCampaign({
+      program,
+      provider,
+      owner,
+      mint,
+      startSaleTime,
+      endSaleTime,
+      cliff,
+      vestingEndTime,
+      priceLamports: helpers.priceLamports,
+      allocation: helpers.allocation,
+      softCap: helpers.softCap,
+      hardCap: helpers.hardCap,
+      availableTokensAfterCliffPtc: helpers.availableTokensAfterCliffPtc,
+      availableAllocationsPerParticipant: helpers.availableAllocationsPerParticipant,
+    });
+
+    const quote = await program.methods
+      .quotePurchase(new BN(2))
+      .accounts({ idoCampaignOwner: owner.publicKey })
+      .view();
+
+    const expectedAmount = helpers.allocation.mul(new BN(2));
+    expect(quote.amount.toString()).to.equal(expectedAmount.toString());
+    expect(quote.totalCostLamports.toString()).to.equal(
+      expectedAmount.mul(helpers.priceLamports).toString()
+    );
+    expect(quote.remainingTokens.toString()).to.equal(helpers.hardCap.toString());
+
+    const statusBefore = await program.methods
+      .getCampaignStatus()
+      .accounts({ idoCampaignOwner: owner.publicKey })
+      .view();
+    expect(statusBefore.saleActive).to.equal(false);
+    expect(statusBefore.refundAvailable).to.equal(false);
+
+    await helpers.joinAsParticipant({
+      program,
+      provider,
+      owner,
+      participant,
+      idoCampaignPda,
+      startSaleTime,
+      endSaleTime,
+      allocations: new BN(5),
+    });
+
+    const statusDuringSale = await program.methods
+      .getCampaignStatus()
+      .accounts({ idoCampaignOwner: owner.publicKey })
+      .view();
+    expect(statusDuringSale.saleActive).to.equal(true);
+    expect(statusDuringSale.softCapReached).to.equal(true);
+    expect(statusDuringSale.totalSold.toString()).to.equal("500");
+
+    const claimableBeforeCliff = await program.methods
+      .getClaimable()
+      .accounts({
+        participant: participant.publicKey,
+        idoCampaignOwner: owner.publicKey,
+      })
+      .view();
+    expect(claimableBeforeCliff.claimableNow.toString()).to.equal("0");
+    expect(claimableBeforeCliff.total.toString()).to.equal("500");
+
+    await helpers.waitUntil(cliff.toNumber());
+
+    const claimableAfterCliff = await program.methods
+      .getClaimable()
+      .accounts({
+        participant: participant.publicKey,
+        idoCampaignOwner: owner.publicKey,
+      })
+      .view();
+    expect(claimableAfterCliff.claimableNow.toNumber()).to.be.greaterThanOrEqual(
+      Math.floor((500 * helpers.availableTokensAfterCliffPtc) / 100)
+    );
+
+    const statusAfterSale = await program.methods
+      .getCampaignStatus()
+      .accounts({ idoCampaignOwner: owner.publicKey })
+      .view();
+    expect(statusAfterSale.saleEnded).to.equal(true);
+    expect(statusAfterSale.fundsWithdrawable).to.equal(true);
+  });
+
+  it("quote_purchase rejects more allocations than allowed per participant", async () => {
+    const owner = Keypair.generate();
+    const { mint } = await helpers.createMintAndMintToOwner(provider, owner.publicKey);
+
+    const now = Math.floor(Date.now() / 1000);
+    await helpers.setupCampaign({
+      program,
+      provider,
+      owner,
+      mint,
+      startSaleTime: new BN(now + 10),
+      endSaleTime: new BN(now + 20),
+      cliff: new BN(now + 30),
+      vestingEndTime: new BN(now + 40),
+      priceLamports: helpers.priceLamports,
+      allocation: helpers.allocation,
+      softCap: helpers.softCap,
+      hardCap: helpers.hardCap,
+      availableTokensAfterCliffPtc: helpers.availableTokensAfterCliffPtc,
+      availableAllocationsPerParticipant: helpers.availableAllocationsPerParticipant,
+    });
+
+    try {
+      await program.methods
+        .quotePurchase(helpers.availableAllocationsPerParticipant.add(new BN(1)))
+        .accounts({ idoCampaignOwner: owner.publicKey })
+        .view();
+      expect.fail("Expected quotePurchase to throw");
+    } catch (error: any) {
+      helpers.expectIdlError(program, error, { msg: "Invalid number of allocations" });
+    }
+  });
+});
@@ -38,6 +38,7 @@ describe("withdraw_funds tests", () => {
   const provider = anchor.getProvider() as anchor.AnchorProvider;
 
   before(async () => {
+    await helpers.ensureCampaignRegistry(program);
     await helpers.airdropSol(provider, LAUNCHPAD_OWNER, 0.01);
   });
 
@@ -63,7 +64,8 @@ describe("withdraw_funds tests", () => {
         helpers.softCap,
         helpers.hardCap,
         helpers.availableTokensAfterCliffPtc,
-        new BN(20)
+        new BN(20),
+        helpers.campaignMetadata
       )
       .accounts({ owner: owner.publicKey, tokenMint: mint })
       .signers([owner])
@@ -102,8 +104,10 @@ describe("withdraw_funds tests", () => {
           tokensTreasury: tokensTreasuryPda,
           solTreasury: solTreasuryPda,
           tokenMint: mint,
+          milestoneEscrow: null,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
+          registryEntry: null,
         })
         .signers([owner])
         .rpc();
@@ -158,8 +162,10 @@ describe("withdraw_funds tests", () => {
           tokensTreasury: tokensTreasuryPda,
           solTreasury: solTreasuryPda,
           tokenMint: mint,
+          milestoneEscrow: null,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
+          registryEntry: null,
         })
         .signers([owner])
         .rpc();
@@ -232,8 +238,10 @@ describe("withdraw_funds tests", () => {
           tokensTreasury: tokensTreasuryPda,
           solTreasury: solTreasuryPda,
           tokenMint: mint,
+          milestoneEscrow: null,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
+          registryEntry: null,
         })
         .signers([owner])
         .rpc();
@@ -303,8 +311,10 @@ describe("withdraw_funds tests", () => {
           tokensTreasury: tokensTreasuryPda,
           solTreasury: solTreasuryPda,
           tokenMint: mint,
+          milestoneEscrow: null,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
+          registryEntry: null,
         })
         .signers([owner])
         .rpc();
@@ -378,8 +388,10 @@ describe("withdraw_funds tests", () => {
         tokensTreasury: tokensTreasuryPda,
         solTreasury: solTreasuryPda,
         tokenMint: mint,
+        milestoneEscrow: null,
         tokenProgram: TOKEN_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
+        registryEntry: null,
       })
       .signers([owner])
       .rpc();
@@ -388,6 +400,7 @@ describe("withdraw_funds tests", () => {
 
     const idoCampaign = await program.account.idoCampaign.fetch(idoCampaignPda);
     expect(idoCampaign.fundsWithdrawn).to.equal(true);
+    expect(idoCampaign.status).to.deep.equal({ succeeded: {} });
 
     const solAfter = await provider.connection.getBalance(solTreasuryPda);
     expect(solAfter).to.equal(0);
@@ -428,8 +441,10 @@ describe("withdraw_funds tests", () => {
           tokensTreasury: tokensTreasuryPda,
           solTreasury: solTreasuryPda,
           tokenMint: mint,
+          milestoneEscrow: null,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
+          registryEntry: null,
         })
         .signers([owner])
         .rpc();
@@ -1,6 +1,13 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
-import { LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import {
+  Connection,
+  Ed25519Program,
+  Keypair,
+  LAMPORTS_PER_SOL,
+  PublicKey,
+  TransactionInstruction,
+} from "@solana/web3.js";
 import {
   createMint,
   getAccount,
@@ -9,6 +16,8 @@ import {
 } from "@solana/spl-token";
 import BN from "bn.js";
 import { expect } from "chai";
+import { createHash } from "crypto";
+import * as client from "../client";
 
 export const startTime = new BN(Math.floor(Date.now() / 1000) + 1);
 export const cliff = startTime.add(new BN(4));
@@ -20,6 +29,13 @@ export const softCap = new BN(500);
 export const hardCap = new BN(1000);
 export const availableTokensAfterCliffPtc = 20;
 export const availableAllocationsPerParticipant = new BN(5);
+export const campaignMetadata = {
+  name: "Project",
+  symbol: "PRJ",
+  uri: "",
+  description: "",
+  socialLinks: [] as string[],
+};
 
 export const airdropSol = async (
   provider: anchor.AnchorProvider,
@@ -52,6 +68,194 @@ export const createMintAndMintToOwner = async (provider: anchor.AnchorProvider,
     return { mint };
 }
 
+// Every campaign is appended to the registry on creation, so suites that create
+// campaigns call this in their before hook.
+export async function ensureCampaignRegistry(program: any) {
+  const registryPda = client.findCampaignRegistryPda(program.programId);
+  const existing = await program.account.campaignRegistry.fetchNullable(registryPda);
+  if (existing) return;
+
+  await program.methods.initializeCampaignRegistry().rpc();
+}
+
+export const BPF_LOADER_UPGRADEABLE_PROGRAM_ID = new PublicKey(
+  "BPFLoaderUpgradeab1e11111111111111111111111"
+);
+
+export const findLaunchpadConfigPda = (programId: PublicKey): PublicKey =>
+  PublicKey.findProgramAddressSync([Buffer.from("launchpad_config")], programId)[0];
+
+export const findStakeAccountPda = (programId: PublicKey, owner: PublicKey): PublicKey =>
+  PublicKey.findProgramAddressSync([Buffer.from("stake"), owner.toBuffer()], programId)[0];
+
+export const findStakeTiersPda = (programId: PublicKey, idoCampaignPda: PublicKey): PublicKey =>
+  PublicKey.findProgramAddressSync(
+    [Buffer.from("stake_tiers"), idoCampaignPda.toBuffer()],
+    programId
+  )[0];
+
+export const findLotteryPda = (programId: PublicKey, idoCampaignPda: PublicKey): PublicKey =>
+  PublicKey.findProgramAddressSync(
+    [Buffer.from("lottery"), idoCampaignPda.toBuffer()],
+    programId
+  )[0];
+
+export const findLotteryTicketPda = (
+  programId: PublicKey,
+  idoCampaignPda: PublicKey,
+  participant: PublicKey
+): PublicKey =>
+  PublicKey.findProgramAddressSync(
+    [Buffer.from("lottery_ticket"), idoCampaignPda.toBuffer(), participant.toBuffer()],
+    programId
+  )[0];
+
+export const findJoinThrottlePda = (programId: PublicKey, participant: PublicKey): PublicKey =>
+  PublicKey.findProgramAddressSync(
+    [Buffer.from("join_throttle"), participant.toBuffer()],
+    programId
+  )[0];
+
+export const findAllowlistEntryPda = (
+  programId: PublicKey,
+  idoCampaignPda: PublicKey,
+  participant: PublicKey
+): PublicKey =>
+  PublicKey.findProgramAddressSync(
+    [Buffer.from("allowlist"), idoCampaignPda.toBuffer(), participant.toBuffer()],
+    programId
+  )[0];
+
+export const findGateListEntryPda = (programId: PublicKey, idoCampaignPda: PublicKey, mint: PublicKey): PublicKey =>
+  PublicKey.findProgramAddressSync(
+    [Buffer.from("gate_list"), idoCampaignPda.toBuffer(), mint.toBuffer()],
+    programId
+  )[0];
+
+export const findBlocklistEntryPda = (programId: PublicKey, wallet: PublicKey): PublicKey =>
+  PublicKey.findProgramAddressSync([Buffer.from("blocklist"), wallet.toBuffer()], programId)[0];
+
+export const findMilestoneEscrowPda = (programId: PublicKey, idoCampaignPda: PublicKey): PublicKey =>
+  PublicKey.findProgramAddressSync(
+    [Buffer.from("milestone_escrow"), idoCampaignPda.toBuffer()],
+    programId
+  )[0];
+
+export const findMilestoneProposalPda = (
+  programId: PublicKey,
+  idoCampaignPda: PublicKey,
+  trancheIndex: number
+): PublicKey =>
+  PublicKey.findProgramAddressSync(
+    [Buffer.from("milestone_proposal"), idoCampaignPda.toBuffer(), Buffer.from([trancheIndex])],
+    programId
+  )[0];
+
+export const findReferralPda = (
+  programId: PublicKey,
+  idoCampaignPda: PublicKey,
+  referrer: PublicKey
+): PublicKey =>
+  PublicKey.findProgramAddressSync(
+    [Buffer.from("referral"), idoCampaignPda.toBuffer(), referrer.toBuffer()],
+    programId
+  )[0];
+
+// The launchpad config is a singleton, the first caller creates it with a fresh stake
+// mint whose authority is the provider wallet, so every test can mint stake tokens.
+export async function ensureLaunchpadConfig(
+  program: any,
+  provider: anchor.AnchorProvider
+): Promise<{ stakeMint: PublicKey }> {
+  const configPda = findLaunchpadConfigPda(program.programId);
+  const existing = await program.account.launchpadConfig.fetchNullable(configPda);
+  if (existing) return { stakeMint: existing.stakeMint };
+
+  const payer = (provider.wallet as anchor.Wallet).payer;
+  const stakeMint = await createMint(provider.connection, payer, payer.publicKey, null, 0);
+  const [programData] = PublicKey.findProgramAddressSync(
+    [program.programId.toBuffer()],
+    BPF_LOADER_UPGRADEABLE_PROGRAM_ID
+  );
+
+  await program.methods
+    .initializeLaunchpadConfig(new BN(0))
+    .accounts({ stakeMint, programData })
+    .rpc();
+
+  return { stakeMint };
+}
+
+// Mirrors BondingCurve::cost on-chain, so clients quote exactly what join_ido charges.
+export function bondingCurveCost(
+  curve: any,
+  basePrice: BN,
+  sold: BN,
+  amount: BN
+): BN {
+  const end = sold.add(amount);
+
+  if (curve.linear) {
+    const triangle = amount.isZero()
+      ? new BN(0)
+      : amount.mul(amount.subn(1)).divn(2);
+    return amount
+      .mul(basePrice)
+      .add(amount.mul(sold).add(triangle).mul(curve.linear.slopeLamports));
+  }
+
+  const { stepTokens, growthBps } = curve.exponentialSteps;
+  let cost = new BN(0);
+  let price = basePrice.clone();
+  let stepStart = new BN(0);
+  while (stepStart.lt(end)) {
+    const stepEnd = stepStart.add(stepTokens);
+    if (stepEnd.gt(sold)) {
+      cost = cost.add(price.mul(BN.min(end, stepEnd).sub(BN.max(sold, stepStart))));
+    }
+    price = price.muln(10_000 + growthBps).divn(10_000);
+    stepStart = stepEnd;
+  }
+  return cost;
+}
+
+// Same preimage reveal_bid hashes: price and quantity as little endian u64, salt, bidder.
+export function batchBidCommitment(
+  priceLamports: BN,
+  quantity: BN,
+  salt: Buffer,
+  participant: PublicKey
+): number[] {
+  const preimage = Buffer.concat([
+    priceLamports.toArrayLike(Buffer, "le", 8),
+    quantity.toArrayLike(Buffer, "le", 8),
+    salt,
+    participant.toBuffer(),
+  ]);
+  return [...createHash("sha256").update(preimage).digest()];
+}
+
+// Ed25519 program instruction carrying the KycTicket join_ido reads from the preceding
+// instruction: campaign, participant, max allocations and expiry as little endian u64.
+export function kycTicketInstruction(
+  kycSigner: Keypair,
+  idoCampaignPda: PublicKey,
+  participant: PublicKey,
+  maxAllocations: BN,
+  expiresAt: BN
+): TransactionInstruction {
+  const message = Buffer.concat([
+    idoCampaignPda.toBuffer(),
+    participant.toBuffer(),
+    maxAllocations.toArrayLike(Buffer, "le", 8),
+    expiresAt.toArrayLike(Buffer, "le", 8),
+  ]);
+  return Ed25519Program.createInstructionWithPrivateKey({
+    privateKey: kycSigner.secretKey,
+    message,
+  });
+}
+
 export const sleep = (ms: number) => new Promise((resolve) => setTimeout(resolve, ms));
 
 export const waitUntil = async (unixTs: number) => {
@@ -61,6 +265,12 @@ export const waitUntil = async (unixTs: number) => {
   await sleep((delta + 1) * 1000);
 };
 
+export const waitForSlot = async (connection: Connection, slot: number) => {
+  while ((await connection.getSlot()) <= slot) {
+    await sleep(400);
+  }
+};
+
 export function expectedUnlockedTotal(params: {
   total: number;
   pctAfterCliff: number;
@@ -107,6 +317,8 @@ export async function setupCampaign(params: {
   hardCap: BN;
   availableTokensAfterCliffPtc: number;
   availableAllocationsPerParticipant: BN;
+  // Runs between initializeSale and depositTokensToSale, while the sale is still configurable.
+  configure?: (idoCampaignPda: PublicKey) => Promise<void>;
 }) {
   const {
     program,
@@ -123,6 +335,7 @@ export async function setupCampaign(params: {
     hardCap,
     availableTokensAfterCliffPtc,
     availableAllocationsPerParticipant,
+    configure,
   } = params;
 
   await airdropSol(provider, owner.publicKey, 10);
@@ -152,7 +365,8 @@ export async function setupCampaign(params: {
       softCap,
       hardCap,
       availableTokensAfterCliffPtc,
-      availableAllocationsPerParticipant
+      availableAllocationsPerParticipant,
+      campaignMetadata
     )
     .accounts({
       owner: owner.publicKey,
@@ -161,6 +375,10 @@ export async function setupCampaign(params: {
     .signers([owner])
     .rpc();
 
+  if (configure) {
+    await configure(idoCampaignPda);
+  }
+
   const ownerAta = await getOrCreateAssociatedTokenAccount(
     provider.connection,
     owner,